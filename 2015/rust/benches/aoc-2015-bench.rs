@@ -0,0 +1,46 @@
+#[macro_use]
+extern crate criterion;
+extern crate aoc_common;
+extern crate advent_of_code_2015;
+
+use criterion::Criterion;
+use aoc_common::bench::{bench_day, bench_scaling};
+use advent_of_code_2015::*;
+
+fn day01(c: &mut Criterion) { bench_day::<day01::Day01>(c) }
+fn day02(c: &mut Criterion) { bench_day::<day02::Day02>(c) }
+fn day03(c: &mut Criterion) { bench_day::<day03::Day03>(c) }
+fn day04(c: &mut Criterion) { bench_day::<day04::Day04>(c) }
+fn day05(c: &mut Criterion) { bench_day::<day05::Day05>(c) }
+fn day06(c: &mut Criterion) { bench_day::<day06::Day06>(c) }
+fn day07(c: &mut Criterion) { bench_day::<day07::Day07>(c) }
+fn day08(c: &mut Criterion) { bench_day::<day08::Day08>(c) }
+fn day09(c: &mut Criterion) { bench_day::<day09::Day09>(c) }
+fn day10(c: &mut Criterion) { bench_day::<day10::Day10>(c) }
+fn day11(c: &mut Criterion) { bench_day::<day11::Day11>(c) }
+fn day12(c: &mut Criterion) { bench_day::<day12::Day12>(c) }
+fn day13(c: &mut Criterion) { bench_day::<day13::Day13>(c) }
+fn day14(c: &mut Criterion) { bench_day::<day14::Day14>(c) }
+fn day15(c: &mut Criterion) { bench_day::<day15::Day15>(c) }
+fn day16(c: &mut Criterion) { bench_day::<day16::Day16>(c) }
+fn day17(c: &mut Criterion) { bench_day::<day17::Day17>(c) }
+fn day18(c: &mut Criterion) { bench_day::<day18::Day18>(c) }
+fn day19(c: &mut Criterion) { bench_day::<day19::Day19>(c) }
+fn day20(c: &mut Criterion) { bench_day::<day20::Day20>(c) }
+fn day21(c: &mut Criterion) { bench_day::<day21::Day21>(c) }
+fn day22(c: &mut Criterion) { bench_day::<day22::Day22>(c) }
+fn day23(c: &mut Criterion) { bench_day::<day23::Day23>(c) }
+fn day24(c: &mut Criterion) { bench_day::<day24::Day24>(c) }
+fn day25(c: &mut Criterion) { bench_day::<day25::Day25>(c) }
+
+fn day10_scaling(c: &mut Criterion) { bench_scaling::<day10::Day10>(c) }
+
+criterion_group!{
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = day01, day02, day03, day04, day05, day06, day07, day08, day09, day10,
+    day11, day12, day13, day14, day15, day16, day17, day18, day19, day20,
+    day21, day22, day23, day24, day25
+}
+criterion_group!(scaling_benches, day10_scaling);
+criterion_main!(benches, scaling_benches);
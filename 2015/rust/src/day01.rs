@@ -1,3 +1,5 @@
+use aoc_common::{Solution, ParseResult};
+
 fn symbol_delta(sym: char) -> i32 {
     match sym {
         '(' => 1,
@@ -22,3 +24,27 @@ pub fn p2(input: &str) -> u32 {
         None         => panic!("Should not happen")
     }
 }
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 1;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day01.txt");
+
+    type Parsed<'a> = &'a str;
+    type Part1 = i32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
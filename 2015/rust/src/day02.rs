@@ -1,24 +1,29 @@
-struct PresentDimension {
+use aoc_common::{Solution, ParseResult, parse};
+
+pub struct PresentDimension {
     length: u32,
     width: u32,
     height: u32,
 }
 
-fn parse_present_dimension(raw_dimensions: &str) -> PresentDimension {
-    let dimensions: Vec<u32> = raw_dimensions.split('x')
-                                             .map(|s| s.parse().unwrap())
-                                             .collect();
+pub fn parse_input(input: &str) -> ParseResult<Vec<PresentDimension>> {
+    input.lines()
+         .map(|raw_dimensions| parse_present_dimension(input, raw_dimensions))
+         .collect()
+}
 
-    assert_eq!(dimensions.len(), 3);
+fn parse_present_dimension(input: &str, raw_dimensions: &str) -> ParseResult<PresentDimension> {
+    let mut dimensions = raw_dimensions.split('x');
+    let mut dimension = || parse::next_field(input, &mut dimensions, raw_dimensions, "a dimension");
 
-    PresentDimension {
-        length: dimensions.get(0).unwrap().clone(),
-        width: dimensions.get(1).unwrap().clone(),
-        height: dimensions.get(2).unwrap().clone(),
-    }
+    Ok(PresentDimension {
+        length: dimension()?,
+        width: dimension()?,
+        height: dimension()?,
+    })
 }
 
-fn wrapping_paper_size(dim: PresentDimension) -> u32 {
+fn wrapping_paper_size(dim: &PresentDimension) -> u32 {
     let sides = [
         dim.length * dim.width,
         dim.width * dim.height,
@@ -31,26 +36,48 @@ fn wrapping_paper_size(dim: PresentDimension) -> u32 {
     base_area + extra_area
 }
 
-fn ribbon_size(dim: PresentDimension) -> u32 {
+fn ribbon_size(dim: &PresentDimension) -> u32 {
     let mut sides = [dim.length, dim.width, dim.height];
     sides.sort();
 
     let smallest_perimeter = 2 * (sides[0] + sides[1]);
-    let bow_size = sides.iter().fold(1, |t, x| t * x);
+    let bow_size = sides.iter().product::<u32>();
 
     smallest_perimeter + bow_size
 }
 
-pub fn p1(input: &str) -> u32 {
-    input.trim().split('\n')
-         .map(parse_present_dimension)
-         .map(wrapping_paper_size)
-         .sum()
+pub fn p1(dimensions: &[PresentDimension]) -> u32 {
+    dimensions.iter()
+              .map(wrapping_paper_size)
+              .sum()
 }
 
-pub fn p2(input: &str) -> u32 {
-    input.trim().split('\n')
-         .map(parse_present_dimension)
-         .map(ribbon_size)
-         .sum()
+pub fn p2(dimensions: &[PresentDimension]) -> u32 {
+    dimensions.iter()
+              .map(ribbon_size)
+              .sum()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 2;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day02.txt");
+
+    type Parsed<'a> = Vec<PresentDimension>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
 }
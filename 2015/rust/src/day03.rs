@@ -1,20 +1,23 @@
 use std::collections::HashSet;
+use aoc_common::{Solution, ParseError, ParseResult};
 
-enum Direction { Up, Down, Left, Right }
+pub enum Direction { Up, Down, Left, Right }
 #[derive(PartialEq, Eq, Hash, Clone)]
 struct Position { x: i32, y: i32 }
 
-fn parse_direction(sym: char) -> Direction {
-    match sym {
-        '^' => Direction::Up,
-        'v' => Direction::Down,
-        '<' => Direction::Left,
-        '>' => Direction::Right,
-        _ => panic!("Invalid direction")
-    }
+pub fn parse_input(input: &str) -> ParseResult<Vec<Direction>> {
+    input.trim_end().char_indices()
+         .map(|(offset, sym)| match sym {
+             '^' => Ok(Direction::Up),
+             'v' => Ok(Direction::Down),
+             '<' => Ok(Direction::Left),
+             '>' => Ok(Direction::Right),
+             _ => Err(ParseError::new(input, offset, "a direction"))
+         })
+         .collect()
 }
 
-fn move_position(pos: &mut Position, dir: Direction) {
+fn move_position(pos: &mut Position, dir: &Direction) {
     match dir {
         Direction::Up    => pos.y -= 1,
         Direction::Down  => pos.y += 1,
@@ -23,14 +26,12 @@ fn move_position(pos: &mut Position, dir: Direction) {
     }
 }
 
-pub fn p1(input: &str) -> usize {
+pub fn p1(directions: &[Direction]) -> usize {
     let mut position = Position { x: 0, y: 0 };
     let mut visited = HashSet::new();
     visited.insert(position.clone());
 
-    let directions = input.trim().chars().map(parse_direction);
-
-    directions.for_each(|dir| {
+    directions.iter().for_each(|dir| {
         move_position(&mut position, dir);
         visited.insert(position.clone());
     });
@@ -38,19 +39,42 @@ pub fn p1(input: &str) -> usize {
     visited.len()
 }
 
-pub fn p2(input: &str) -> usize {
+pub fn p2(directions: &[Direction]) -> usize {
     let mut santa_position = Position { x: 0, y: 0 };
     let mut robot_position = Position { x: 0, y: 0 };
     let mut visited = HashSet::new();
     visited.insert(santa_position.clone());
 
-    let directions = input.trim().chars().map(parse_direction);
     let mut positions = [&mut santa_position, &mut robot_position];
 
-    directions.zip([0, 1].iter().cycle()).for_each(|(dir, i)| {
-        move_position(&mut positions[*i], dir);
+    directions.iter().zip([0, 1].iter().cycle()).for_each(|(dir, i)| {
+        move_position(positions[*i], dir);
         visited.insert(positions[*i].clone());
     });
 
     visited.len()
 }
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 3;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day03.txt");
+
+    type Parsed<'a> = Vec<Direction>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
@@ -5,6 +5,7 @@ use day04::crypto::digest::Digest;
 use std::thread;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU32, Ordering};
+use aoc_common::{Solution, ParseResult};
 
 fn md5(input: &str) -> String {
     let mut md5 = Md5::new();
@@ -41,10 +42,7 @@ fn mine_block_threaded(th_count: u32, key: &str, pattern: &'static str) -> u32 {
     }).collect();
 
     for thread in threads {
-        match thread.join() {
-            Err(e) => panic!(e),
-            _ => ()
-        }
+        if let Err(e) = thread.join() { std::panic::panic_any(e) }
     }
 
     spinlock.load(Ordering::SeqCst)
@@ -59,3 +57,27 @@ pub fn p2(input: &str) -> u32 {
     let key = input.trim();
     mine_block_threaded(4, key, "000000")
 }
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 4;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day04.txt");
+
+    type Parsed<'a> = &'a str;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
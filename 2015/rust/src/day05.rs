@@ -1,3 +1,5 @@
+use aoc_common::{Solution, ParseResult};
+
 fn is_nice_string_p1(s: &str) -> bool {
     let vowels = "aeiou";
     let anomalies = ["ab", "cd", "pq", "xy"];
@@ -36,3 +38,27 @@ pub fn p2(input: &str) -> usize {
                 .filter(|s| is_nice_string_p2(s))
                 .count()
 }
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 5;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day05.txt");
+
+    type Parsed<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
@@ -1,13 +1,19 @@
 extern crate nom;
 
+use aoc_common::{Solution, ParseError, ParseResult};
+use nom_parse;
+
 #[derive(Clone, Copy, PartialEq)]
 enum CandleStateP1 { On, Off }
 type CandleStateP2 = u32;
 
-struct Coord { x: u32, y: u32 }
-struct Range { from: Coord, to: Coord }
+#[derive(Clone, Copy)]
+pub struct Coord { x: u32, y: u32 }
+#[derive(Clone, Copy)]
+pub struct Range { from: Coord, to: Coord }
 
-enum Instruction {
+#[derive(Clone, Copy)]
+pub enum Instruction {
     TurnOff(Range),
     TurnOn(Range),
     Toggle(Range),
@@ -61,67 +67,100 @@ fn apply_instruction_p2(grid: &mut P2Grid, instruction: Instruction) {
     alter_grid(grid, range, action)
 }
 
-fn parse_instruction(raw_instr: &str) -> Instruction {
+fn parse_instruction(input: &str, raw_instr: &str) -> ParseResult<Instruction> {
     use self::nom::*;
     use std::str::FromStr;
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
     type InstrBuilder = fn(Range) -> Instruction;
 
-    named!(prefixes<InstrBuilder>, alt!(
+    named!(prefixes<Input, InstrBuilder>, alt!(
         map!(tag!("turn off "), |_| Instruction::TurnOff as InstrBuilder) |
         map!(tag!("turn on "),  |_| Instruction::TurnOn  as InstrBuilder) |
         map!(tag!("toggle "),   |_| Instruction::Toggle  as InstrBuilder)
     ));
-    named!(number<u32>, map!(
-        digit,
-        |d| FromStr::from_str(from_utf8(d).unwrap()).unwrap()
+    named!(number<Input, u32>, map_res!(
+        map_res!(digit, utf8),
+        FromStr::from_str
     ));
-    named!(coord<Coord>, do_parse!(
+    named!(coord<Input, Coord>, do_parse!(
         x: number >>
         char!(',') >>
         y: number >>
-        (Coord { x: x, y: y })
+        (Coord { x, y })
     ));
-    named!(instruction<Instruction>, do_parse!(
+    named!(instruction<Input, Instruction>, do_parse!(
         prefix: prefixes    >>
         from:   coord       >>
-        tag_s!(" through ") >>
+        tag!(" through ") >>
         to:     coord       >>
-        (prefix(Range { from: from, to: to }))
+        (prefix(Range { from, to }))
     ));
 
-    match instruction(raw_instr.as_bytes()) {
-        IResult::Done(_, instr) => instr,
-        _                       => panic!("Wrong instruction format")
+    let instr = nom_parse::complete(input, raw_instr, instruction, "an instruction like `toggle 0,0 through 9,9`")?;
+
+    match instr {
+        Instruction::TurnOff(range) | Instruction::TurnOn(range) | Instruction::Toggle(range)
+            if range.to.x >= 1000 || range.to.y >= 1000 =>
+        {
+            Err(ParseError::at(input, raw_instr, "coordinates within the 1000x1000 grid"))
+        },
+        _ => Ok(instr)
     }
 }
 
+pub fn parse_input(input: &str) -> ParseResult<Vec<Instruction>> {
+    input.lines()
+         .map(|raw_instr| parse_instruction(input, raw_instr))
+         .collect()
+}
+
 fn run_part<R, State: Copy>(
-    input: &str,
+    instructions: &[Instruction],
     state: State,
     apply_instr: Apply<State>,
     transform: fn(CandleGrid<State>) -> R
 ) -> R {
     let mut grid: CandleGrid<State> = [state; GRID_SIZE];
-    let instructions = input.trim().split('\n')
-                                   .map(parse_instruction);
-
-    for instr in instructions {
+    for &instr in instructions {
         apply_instr(&mut grid, instr)
     }
 
     transform(grid)
 }
 
-pub fn p1(input: &str) -> u32 {
-    run_part(input, CandleStateP1::Off, apply_instruction_p1, |grid|
+pub fn p1(instructions: &[Instruction]) -> u32 {
+    run_part(instructions, CandleStateP1::Off, apply_instruction_p1, |grid|
         grid.iter().filter(|cs| **cs == CandleStateP1::On).count() as u32
     )
 }
 
-pub fn p2(input: &str) -> u32 {
-    run_part(input, 0u32, apply_instruction_p2, |grid|
+pub fn p2(instructions: &[Instruction]) -> u32 {
+    run_part(instructions, 0u32, apply_instruction_p2, |grid|
         grid.iter().sum()
     )
 }
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 6;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day06.txt");
+
+    type Parsed<'a> = Vec<Instruction>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
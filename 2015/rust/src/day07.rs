@@ -1,16 +1,18 @@
 extern crate nom;
 
 use std::collections::HashMap;
+use aoc_common::{Solution, ParseResult};
+use nom_parse;
 
 type WireID = String;
 type Signal = u16;
 
-enum SignalProvider {
+pub enum SignalProvider {
     Wire(WireID),
     Value(Signal),
 }
 
-enum Gate {
+pub enum Gate {
     Assign(SignalProvider),
     And(SignalProvider, SignalProvider),
     Or(SignalProvider, SignalProvider),
@@ -18,8 +20,8 @@ enum Gate {
     Rshift(SignalProvider, SignalProvider),
     Not(SignalProvider),
 }
-type Instruction = (Gate, WireID);
-type Circuit = Vec<Instruction>;
+pub type Instruction = (Gate, WireID);
+pub type Circuit = Vec<Instruction>;
 type Wires = HashMap<WireID, Signal>;
 
 fn is_gate_resolvable(gate: &Gate, wires: &Wires) -> bool {
@@ -29,17 +31,17 @@ fn is_gate_resolvable(gate: &Gate, wires: &Wires) -> bool {
     let resolvable = |sp| {
         match sp {
             &Value(_) => true,
-            &Wire(ref id) => wires.contains_key(id),
+            Wire(id) => wires.contains_key(id),
         }
     };
 
     match gate {
-        &Assign(ref s)          => resolvable(s),
-        &And(ref s1, ref s2)    => resolvable(s1) && resolvable(s2),
-        &Or(ref s1, ref s2)     => resolvable(s1) && resolvable(s2),
-        &Lshift(ref s1, ref s2) => resolvable(s1) && resolvable(s2),
-        &Rshift(ref s1, ref s2) => resolvable(s1) && resolvable(s2),
-        &Not(ref s)             => resolvable(s),
+        Assign(s)      => resolvable(s),
+        And(s1, s2)    => resolvable(s1) && resolvable(s2),
+        Or(s1, s2)     => resolvable(s1) && resolvable(s2),
+        Lshift(s1, s2) => resolvable(s1) && resolvable(s2),
+        Rshift(s1, s2) => resolvable(s1) && resolvable(s2),
+        Not(s)         => resolvable(s),
     }
 }
 
@@ -50,17 +52,17 @@ fn compute_gate(gate: &Gate, wires: &Wires) -> Signal {
     let get_value = |sp| {
         match sp {
             &Value(x) => x,
-            &Wire(ref id) => wires.get(id).unwrap().clone(),
+            Wire(id) => *wires.get(id).unwrap(),
         }
     };
 
     match gate {
-        &Assign(ref s)          => get_value(s),
-        &And(ref s1, ref s2)    => get_value(s1) &  get_value(s2),
-        &Or(ref s1, ref s2)     => get_value(s1) |  get_value(s2),
-        &Lshift(ref s1, ref s2) => get_value(s1) << get_value(s2),
-        &Rshift(ref s1, ref s2) => get_value(s1) >> get_value(s2),
-        &Not(ref s)             => !get_value(s),
+        Assign(s)      => get_value(s),
+        And(s1, s2)    => get_value(s1) &  get_value(s2),
+        Or(s1, s2)     => get_value(s1) |  get_value(s2),
+        Lshift(s1, s2) => get_value(s1) << get_value(s2),
+        Rshift(s1, s2) => get_value(s1) >> get_value(s2),
+        Not(s)         => !get_value(s),
     }
 }
 
@@ -70,9 +72,9 @@ fn run_circuit(circuit: &Circuit, initial_wires: Wires) -> Vec<(WireID, Signal)>
     loop {
         let mut altered = false;
 
-        for &(ref gate, ref wid) in circuit {
-            if !wires.contains_key(wid) && is_gate_resolvable(&gate, &wires) {
-                let output_signal = compute_gate(&gate, &wires);
+        for (gate, wid) in circuit {
+            if !wires.contains_key(wid) && is_gate_resolvable(gate, &wires) {
+                let output_signal = compute_gate(gate, &wires);
                 wires.insert(wid.clone(), output_signal);
                 altered = true;
             }
@@ -84,91 +86,110 @@ fn run_circuit(circuit: &Circuit, initial_wires: Wires) -> Vec<(WireID, Signal)>
     wires.iter().map(|(wid, sig)| (wid.clone(), *sig)).collect()
 }
 
-fn parse_instruction(raw_instr: &str) -> Instruction {
+fn parse_instruction(input: &str, raw_instr: &str) -> ParseResult<Instruction> {
     use self::Gate::*;
     use self::SignalProvider::*;
     use self::nom::*;
 
     use std::str::FromStr;
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
-    named!(wire_id<WireID>, map!(
-        take_while1!(is_alphabetic),
-        |s| from_utf8(s).unwrap().to_string())
+    named!(wire_id<Input, WireID>, map!(
+        map_res!(take_while1!(is_alphabetic), utf8),
+        String::from)
     );
-    named!(signal<Signal>, map!(
-        digit,
-        |d| FromStr::from_str(from_utf8(d).unwrap()).unwrap()
+    named!(signal<Input, Signal>, map_res!(
+        map_res!(digit, utf8),
+        FromStr::from_str
     ));
-    named!(signal_provider<SignalProvider>, alt!(
+    named!(signal_provider<Input, SignalProvider>, alt!(
         map!(wire_id, Wire) |
         map!(signal, Value)
     ));
 
-    named!(assign<Gate>, map!(signal_provider, Assign));
-    named!(and<Gate>, do_parse!(
+    named!(assign<Input, Gate>, map!(signal_provider, Assign));
+    named!(and<Input, Gate>, do_parse!(
         s1: signal_provider >>
-        tag_s!(" AND ")     >>
+        tag!(" AND ")     >>
         s2: signal_provider >>
         (And(s1, s2))
     ));
-    named!(or<Gate>, do_parse!(
+    named!(or<Input, Gate>, do_parse!(
         s1: signal_provider >>
-        tag_s!(" OR ")     >>
+        tag!(" OR ")     >>
         s2: signal_provider >>
         (Or(s1, s2))
     ));
-    named!(lshift<Gate>, do_parse!(
+    named!(lshift<Input, Gate>, do_parse!(
         s1: signal_provider >>
-        tag_s!(" LSHIFT ")     >>
+        tag!(" LSHIFT ")     >>
         s2: signal_provider >>
         (Lshift(s1, s2))
     ));
-    named!(rshift<Gate>, do_parse!(
+    named!(rshift<Input, Gate>, do_parse!(
         s1: signal_provider >>
-        tag_s!(" RSHIFT ")     >>
+        tag!(" RSHIFT ")     >>
         s2: signal_provider >>
         (Rshift(s1, s2))
     ));
-    named!(not<Gate>, do_parse!(
-        tag_s!("NOT ")     >>
+    named!(not<Input, Gate>, do_parse!(
+        tag!("NOT ")     >>
         s: signal_provider >>
         (Not(s))
     ));
 
-    named!(gate<Gate>, alt!(and | or | lshift | rshift | not | assign));
+    named!(gate<Input, Gate>, alt!(and | or | lshift | rshift | not | assign));
 
-    named!(instruction_parser<Instruction>, do_parse!(
+    named!(instruction_parser<Input, Instruction>, do_parse!(
         gate: gate     >>
-        tag_s!(" -> ") >>
+        tag!(" -> ") >>
         wid:  wire_id  >>
         ((gate, wid))
     ));
 
-    match instruction_parser(raw_instr.as_bytes()) {
-        IResult::Done(_, instr) => instr,
-        _                       => panic!("Wrong instruction format")
-    }
+    nom_parse::complete(input, raw_instr, instruction_parser, "an instruction like `x AND y -> z`")
 }
 
-pub fn p1(input: &str) -> Signal {
-    let circuit: Circuit = input.trim().split('\n')
-                                       .map(parse_instruction)
-                                       .collect();
+pub fn parse_input(input: &str) -> ParseResult<Circuit> {
+    input.lines()
+         .map(|raw_instr| parse_instruction(input, raw_instr))
+         .collect()
+}
 
-    let final_values = run_circuit(&circuit, Wires::new());
+pub fn p1(circuit: &Circuit) -> Signal {
+    let final_values = run_circuit(circuit, Wires::new());
 
-    final_values.iter().find(|&&(ref wid, _)| wid == "a").unwrap().1
+    final_values.iter().find(|&(wid, _)| wid == "a").unwrap().1
 }
 
-pub fn p2(input: &str) -> Signal {
-    let circuit: Circuit = input.trim().split('\n')
-                                       .map(parse_instruction)
-                                       .collect();
-
+pub fn p2(circuit: &Circuit) -> Signal {
     let mut wires = Wires::new();
-    wires.insert("b".to_string(), p1(&input));
-    let final_values = run_circuit(&circuit, wires);
+    wires.insert("b".to_string(), p1(circuit));
+    let final_values = run_circuit(circuit, wires);
+
+    final_values.iter().find(|&(wid, _)| wid == "a").unwrap().1
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 7;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day07.txt");
 
-    final_values.iter().find(|&&(ref wid, _)| wid == "a").unwrap().1
+    type Parsed<'a> = Circuit;
+    type Part1 = Signal;
+    type Part2 = Signal;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
 }
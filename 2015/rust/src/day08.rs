@@ -1,3 +1,5 @@
+use aoc_common::{Solution, ParseResult};
+
 fn memory_size(s: &str) -> usize {
     let mut total = 0usize;
     let mut iter = s.chars();
@@ -5,15 +7,13 @@ fn memory_size(s: &str) -> usize {
     loop {
         match iter.next() {
             None => break,
-            Some(c) => match c {
-                '\\' => {
-                    if iter.next() == Some('x') {
-                        iter.next();
-                        iter.next();
-                    }
-                },
-                _ => ()
-            }
+            Some('\\') => {
+                if iter.next() == Some('x') {
+                    iter.next();
+                    iter.next();
+                }
+            },
+            Some(_) => ()
         };
         total += 1
     }
@@ -49,3 +49,27 @@ pub fn p2(input: &str) -> usize {
     input.trim().split('\n').map(|s| encoded_size(s) - s.len())
                             .sum()
 }
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 8;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day08.txt");
+
+    type Parsed<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
@@ -1,10 +1,12 @@
 extern crate nom;
 use std::collections::{HashMap, HashSet};
+use aoc_common::{Solution, ParseResult};
+use nom_parse;
 
 type City = String;
 type Distance = u32;
 
-type DistanceMap = HashMap<(City, City), Distance>;
+pub type DistanceMap = HashMap<(City, City), Distance>;
 type TripMap = HashMap<City, Vec<(City, Distance)>>;
 type Path = Vec<City>;
 
@@ -28,14 +30,14 @@ fn paths_from(trips: &TripMap, path: Path, l: usize) -> Vec<Path> {
         vec![path]
     }
     else {
-        let city = path.get(path.len() - 1).unwrap();
+        let city = path.last().unwrap();
         let no_destinations = Vec::new();
         let possible_destinations = trips.get(city).unwrap_or(&no_destinations)
                                                    .iter();
-        let unvisited_destinations = possible_destinations.filter(|&&(ref city, _)|
+        let unvisited_destinations = possible_destinations.filter(|&(city, _)|
             !path.contains(city)
         );
-        unvisited_destinations.flat_map(|&(ref to, _)| {
+        unvisited_destinations.flat_map(|(to, _)| {
             let mut new_path = path.clone();
             new_path.push(to.clone());
             paths_from(trips, new_path, l)
@@ -47,16 +49,16 @@ fn all_paths(map: &DistanceMap) -> Vec<Path> {
     let mut unique_cities = HashSet::new();
     let mut trip_map = TripMap::new();
 
-    map.iter().for_each(|(&(ref from, ref to), &d)| {
+    map.iter().for_each(|((from, to), &d)| {
         {
-        let destinations_from = trip_map.entry(to.clone()).or_insert(Vec::new());
+        let destinations_from = trip_map.entry(to.clone()).or_default();
         destinations_from.push((from.clone(), d));
         }
-        let destinations_to = trip_map.entry(from.clone()).or_insert(Vec::new());
+        let destinations_to = trip_map.entry(from.clone()).or_default();
         destinations_to.push((to.clone(), d));
     });
 
-    map.keys().for_each(|&(ref from, ref to)| {
+    map.keys().for_each(|(from, to)| {
         unique_cities.insert(from);
         unique_cities.insert(to);
     });
@@ -70,50 +72,71 @@ fn all_paths(map: &DistanceMap) -> Vec<Path> {
     unique_cities.iter().flat_map(paths_from_city).collect()
 }
 
-fn parse_map_entry(input: &str) -> ((City, City), Distance) {
+fn parse_map_entry(input: &str, raw_entry: &str) -> ParseResult<((City, City), Distance)> {
     use self::nom::*;
 
     use std::str::FromStr;
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
-    named!(city<City>, map!(
-        take_while1!(is_alphabetic),
-        |s| from_utf8(s).unwrap().to_string()
+    named!(city<Input, City>, map!(
+        map_res!(take_while1!(is_alphabetic), utf8),
+        String::from
     ));
-    named!(distance<Distance>, map!(
-        digit,
-        |d| FromStr::from_str(from_utf8(d).unwrap()).unwrap()
+    named!(distance<Input, Distance>, map_res!(
+        map_res!(digit, utf8),
+        FromStr::from_str
     ));
 
-    named!(parser<((City, City), Distance)>, do_parse!(
+    named!(parser<Input, ((City, City), Distance)>, do_parse!(
         from: city     >>
-        tag_s!(" to ") >>
+        tag!(" to ") >>
         to: city       >>
-        tag_s!(" = ")  >>
+        tag!(" = ")  >>
         dist: distance >>
         ((from, to), dist)
     ));
 
-    match parser(input.as_bytes()) {
-        IResult::Done(_, entry) => entry,
-        _                       => panic!("Wrong map format")
-    }
+    nom_parse::complete(input, raw_entry, parser, "a distance like `A to B = 42`")
+}
+
+pub fn parse_input(input: &str) -> ParseResult<DistanceMap> {
+    input.lines()
+         .map(|raw_entry| parse_map_entry(input, raw_entry))
+         .collect()
 }
 
-pub fn p1(input: &str) -> Distance {
-    let map: DistanceMap = input.trim().split('\n')
-                                       .map(parse_map_entry)
-                                       .collect();
-    let paths = all_paths(&map);
+pub fn p1(map: &DistanceMap) -> Distance {
+    let paths = all_paths(map);
 
-    paths.iter().map(|p| path_distance(&map, &p)).min().unwrap_or(0)
+    paths.iter().map(|p| path_distance(map, p)).min().unwrap_or(0)
 }
 
-pub fn p2(input: &str) -> u32 {
-    let map: DistanceMap = input.trim().split('\n')
-                                       .map(parse_map_entry)
-                                       .collect();
-    let paths = all_paths(&map);
+pub fn p2(map: &DistanceMap) -> u32 {
+    let paths = all_paths(map);
+
+    paths.iter().map(|p| path_distance(map, p)).max().unwrap_or(0)
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 9;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day09.txt");
 
-    paths.iter().map(|p| path_distance(&map, &p)).max().unwrap_or(0)
+    type Parsed<'a> = DistanceMap;
+    type Part1 = Distance;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
 }
@@ -1,8 +1,9 @@
+use aoc_common::{Solution, Generate, ParseResult};
+use aoc_common::generate::Rng;
+
 type Seq = String;
 
 fn next_sequence(seq: &Seq) -> Seq {
-    use std::str::FromStr;
-
     let mut next = Seq::new();
     let mut iter = seq.chars();
     let mut x = iter.next();
@@ -41,3 +42,46 @@ pub fn p1(input: &str) -> usize {
 pub fn p2(input: &str) -> usize {
     look_and_say(input.trim(), 50).len()
 }
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 10;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day10.txt");
+
+    type Parsed<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
+
+impl Generate for Day10 {
+    const SIZES: &'static [usize] = &[5, 10, 20, 40];
+
+    // `size` is the length of the seed. Like the real ones, it is made of 1s, 2s
+    // and 3s without a run of more than three of the same digit
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut seed = String::with_capacity(size + 1);
+
+        while seed.len() < size {
+            let digit = *rng.pick(&['1', '2', '3']);
+            if !seed.ends_with(&digit.to_string().repeat(3)) {
+                seed.push(digit);
+            }
+        }
+
+        seed + "\n"
+    }
+}
@@ -1,7 +1,10 @@
 use std::collections::HashSet;
+use aoc_common::{Solution, ParseError, ParseResult};
 
 type Password = String;
 
+const PASSWORD_LEN: usize = 8;
+
 fn valid_password(pwd: &Password) -> bool {
     let has_straight = pwd.chars().zip(pwd.chars().skip(1))
                                   .zip(pwd.chars().skip(2))
@@ -68,3 +71,34 @@ pub fn p2(input: &str) -> Password {
 
     password
 }
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 11;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day11.txt");
+
+    type Parsed<'a> = &'a str;
+    type Part1 = Password;
+    type Part2 = Password;
+
+    // Anything else would never reach a valid password
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        let password = input.trim();
+
+        if password.len() != PASSWORD_LEN || !password.bytes().all(|c| c.is_ascii_lowercase()) {
+            return Err(ParseError::at(input, password, format!("a password of {} lowercase letters", PASSWORD_LEN)))
+        }
+
+        Ok(password)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
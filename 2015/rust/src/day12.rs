@@ -1,15 +1,16 @@
 extern crate serde_json;
 
-use self::serde_json::{Value, Error};
+use self::serde_json::Value;
+use aoc_common::{Solution, ParseError, ParseResult};
 
 fn count_numbers(data: &Value) -> i32 {
     use self::Value::*;
 
     match data {
-        &Object(ref map) => map.values().map(count_numbers).sum(),
-        &Array(ref arr)  => arr.iter().map(count_numbers).sum(),
-        &Number(ref n)   => n.as_i64().unwrap() as i32,
-        _                => 0
+        Object(map) => map.values().map(count_numbers).sum(),
+        Array(arr)  => arr.iter().map(count_numbers).sum(),
+        Number(n)   => n.as_i64().unwrap() as i32,
+        _           => 0
     }
 }
 
@@ -17,26 +18,51 @@ fn count_numbers_filtered(data: &Value) -> i32 {
     use self::Value::*;
 
     match data {
-        &Object(ref map) => {
+        Object(map) => {
             match map.values().any(|v| v == &String("red".to_string())) {
                 true  => 0,
                 false => map.values().map(count_numbers_filtered).sum()
             }
         },
-        &Array(ref arr)  => arr.iter().map(count_numbers_filtered).sum(),
-        &Number(ref n)   => n.as_i64().unwrap() as i32,
-        _                => 0
+        Array(arr)  => arr.iter().map(count_numbers_filtered).sum(),
+        Number(n)   => n.as_i64().unwrap() as i32,
+        _           => 0
     }
 }
 
-pub fn p1(input: &str) -> i32 {
-    let data = serde_json::from_str::<Value>(input.trim()).unwrap();
+pub fn parse_input(input: &str) -> ParseResult<Value> {
+    serde_json::from_str(input)
+        .map_err(|e| ParseError { line: e.line(), column: e.column(), expected: "a JSON document".into() })
+}
+
+pub fn p1(data: &Value) -> i32 {
+    count_numbers(data)
+}
 
-    count_numbers(&data)
+pub fn p2(data: &Value) -> i32 {
+    count_numbers_filtered(data)
 }
 
-pub fn p2(input: &str) -> i32 {
-    let data = serde_json::from_str::<Value>(input.trim()).unwrap();
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 12;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day12.txt");
+
+    type Parsed<'a> = Value;
+    type Part1 = i32;
+    type Part2 = i32;
 
-    count_numbers_filtered(&data)
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
 }
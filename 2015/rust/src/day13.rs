@@ -1,9 +1,12 @@
 extern crate nom;
 
 use std::collections::{VecDeque, HashMap, HashSet};
+use aoc_common::{Solution, ParseResult};
+use nom_parse;
 
 type Guest = String;
 type Happiness = i32;
+pub type HappinessEntry = ((Guest, Guest), Happiness);
 type HappinessMap<'a> = HashMap<(&'a Guest, &'a Guest), Happiness>;
 type GuestSet = HashSet<Guest>;
 type Table<'a> = Vec<&'a Guest>;
@@ -21,7 +24,7 @@ fn table_happiness(table: &Table, happiness_map: &HappinessMap) -> Happiness {
     happiness
 }
 
-fn all_table_configurations(guest_set: &GuestSet) -> Vec<Table> {
+fn all_table_configurations(guest_set: &GuestSet) -> Vec<Table<'_>> {
     let mut tables = Vec::new();
     let mut open_set = VecDeque::new();
 
@@ -29,78 +32,73 @@ fn all_table_configurations(guest_set: &GuestSet) -> Vec<Table> {
         open_set.push_back(vec![guest]);
     }
 
-    loop {
-        if let Some(guests) = open_set.pop_front() {
-            match guests.len() == guest_set.len() {
-                true  => tables.push(guests),
-                false => {
-                    let guests_left = guest_set.iter()
-                                               .filter(|g| !guests.contains(g));
-
-                    for other_guest in guests_left {
-                        let mut new_table = guests.clone();
-                        new_table.push(other_guest);
-                        open_set.push_back(new_table);
-                    }
+    while let Some(guests) = open_set.pop_front() {
+        match guests.len() == guest_set.len() {
+            true  => tables.push(guests),
+            false => {
+                let guests_left = guest_set.iter()
+                                           .filter(|g| !guests.contains(g));
+
+                for other_guest in guests_left {
+                    let mut new_table = guests.clone();
+                    new_table.push(other_guest);
+                    open_set.push_back(new_table);
                 }
             }
         }
-        else {
-            break
-        }
     }
 
     tables
 }
 
-fn parse_map_entry(input: &str) -> ((Guest, Guest), Happiness) {
+fn parse_map_entry(input: &str, raw_entry: &str) -> ParseResult<HappinessEntry> {
     use self::nom::*;
 
     use std::str::FromStr;
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
     type HappinessTransform = fn(Happiness) -> Happiness;
 
-    named!(guest<Guest>, map!(
-        take_while1!(is_alphabetic),
-        |s| from_utf8(s).unwrap().to_string()
+    named!(guest<Input, Guest>, map!(
+        map_res!(take_while1!(is_alphabetic), utf8),
+        String::from
     ));
-    named!(happiness<Happiness>, do_parse!(
+    named!(happiness<Input, Happiness>, do_parse!(
         diff: alt!(
-            map!(tag_s!("gain "), |_| (|x|  x) as HappinessTransform) |
-            map!(tag_s!("lose "), |_| (|x| -x) as HappinessTransform)
+            map!(tag!("gain "), |_| (|x|  x) as HappinessTransform) |
+            map!(tag!("lose "), |_| (|x| -x) as HappinessTransform)
         ) >>
-        n: map!(digit, |d| FromStr::from_str(from_utf8(d).unwrap()).unwrap()) >>
+        n: map_res!(map_res!(digit, utf8), FromStr::from_str) >>
         (diff(n))
     ));
-    named!(parser<((Guest, Guest), Happiness)>, do_parse!(
+    named!(parser<Input, HappinessEntry>, do_parse!(
         g1: guest         >>
-        tag_s!(" would ") >>
+        tag!(" would ") >>
         happ: happiness   >>
-        tag_s!(" happiness units by sitting next to ")  >>
+        tag!(" happiness units by sitting next to ")  >>
         g2: guest >>
+        char!('.') >>
         ((g1, g2), happ)
     ));
 
-    match parser(input.as_bytes()) {
-        IResult::Done(_, entry) => entry,
-        _                       => panic!("Wrong map format")
-    }
+    nom_parse::complete(input, raw_entry, parser, "a happiness change like `A would gain 2 happiness units by sitting next to B.`")
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<HappinessEntry>> {
+    input.lines()
+         .map(|raw_entry| parse_map_entry(input, raw_entry))
+         .collect()
 }
 
-pub fn p1(input: &str) -> Happiness {
+pub fn p1(entries: &[HappinessEntry]) -> Happiness {
     let mut guest_set = GuestSet::new();
     let mut happiness_map = HappinessMap::new();
 
-    let entries = input.trim().split('\n')
-                              .map(parse_map_entry)
-                              .collect::<Vec<_>>();
-
-    for &((ref g1, ref g2), _) in &entries {
+    for &((ref g1, ref g2), _) in entries {
         guest_set.insert(g1.clone());
         guest_set.insert(g2.clone());
     }
-    for &((ref g1, ref g2), h) in &entries {
+    for &((ref g1, ref g2), h) in entries {
         let entry = (guest_set.get(g1).unwrap(), guest_set.get(g2).unwrap());
         happiness_map.insert(entry, h);
     }
@@ -110,15 +108,11 @@ pub fn p1(input: &str) -> Happiness {
     tables.iter().map(|t| table_happiness(t, &happiness_map)).max().unwrap_or(0)
 }
 
-pub fn p2(input: &str) -> Happiness {
+pub fn p2(entries: &[HappinessEntry]) -> Happiness {
     let mut guest_set = GuestSet::new();
     let mut happiness_map = HappinessMap::new();
 
-    let entries = input.trim().split('\n')
-                              .map(parse_map_entry)
-                              .collect::<Vec<_>>();
-
-    for &((ref g1, ref g2), _) in &entries {
+    for &((ref g1, ref g2), _) in entries {
         guest_set.insert(g1.clone());
         guest_set.insert(g2.clone());
     }
@@ -127,7 +121,7 @@ pub fn p2(input: &str) -> Happiness {
     guest_set.insert(me.clone());
     let me_entry = guest_set.get(&me).unwrap();
 
-    for &((ref g1, ref g2), h) in &entries {
+    for &((ref g1, ref g2), h) in entries {
         let entry = (guest_set.get(g1).unwrap(), guest_set.get(g2).unwrap());
         happiness_map.insert(entry, h);
     }
@@ -143,3 +137,27 @@ pub fn p2(input: &str) -> Happiness {
 
     tables.iter().map(|t| table_happiness(t, &happiness_map)).max().unwrap_or(0)
 }
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 13;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day13.txt");
+
+    type Parsed<'a> = Vec<HappinessEntry>;
+    type Part1 = Happiness;
+    type Part2 = Happiness;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
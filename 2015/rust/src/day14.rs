@@ -1,11 +1,14 @@
 extern crate nom;
 
+use aoc_common::{Solution, ParseResult};
+use nom_parse;
+
 type Speed = u32;
 type Second = u32;
 
 const RACE_TIME: Second = 2503;
 
-struct Reindeer {
+pub struct Reindeer {
     speed: Speed,
     fly_time: Second,
     rest_time: Second
@@ -22,7 +25,7 @@ struct DeerRaceState<'a> {
 }
 
 impl<'a> DeerRaceState<'a> {
-    fn new(rd: &'a Reindeer) -> DeerRaceState {
+    fn new(rd: &'a Reindeer) -> DeerRaceState<'a> {
         DeerRaceState {
             deer: rd,
             movement_state: DeerMovementState::Flying(rd.fly_time),
@@ -47,36 +50,39 @@ impl<'a> DeerRaceState<'a> {
     }
 }
 
-fn parse_reindeer(raw_deer: &str) -> Reindeer {
+fn parse_reindeer(input: &str, raw_deer: &str) -> ParseResult<Reindeer> {
     use self::nom::*;
 
     use std::str::FromStr;
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
-    named!(number<u32>, map!(
-        digit,
-        |d| FromStr::from_str(from_utf8(d).unwrap()).unwrap())
+    named!(number<Input, u32>, map_res!(
+        map_res!(digit, utf8),
+        FromStr::from_str)
     );
-    named!(deer_name<String>, map!(
-        take_while1!(is_alphabetic),
-        |s| from_utf8(s).unwrap().to_string()
+    named!(deer_name<Input, String>, map!(
+        map_res!(take_while1!(is_alphabetic), utf8),
+        String::from
     ));
-    named!(reindeer<Reindeer>, do_parse!(
+    named!(reindeer<Input, Reindeer>, do_parse!(
         deer_name >>
-        tag_s!(" can fly ") >>
+        tag!(" can fly ") >>
         speed: number >>
-        tag_s!(" km/s for ") >>
+        tag!(" km/s for ") >>
         fly_time: number >>
-        tag_s!(" seconds, but then must rest for ") >>
+        tag!(" seconds, but then must rest for ") >>
         rest_time: number >>
-        tag_s!(" seconds.") >>
-        (Reindeer { speed: speed, fly_time: fly_time, rest_time: rest_time })
+        tag!(" seconds.") >>
+        (Reindeer { speed, fly_time, rest_time })
     ));
 
-    match reindeer(raw_deer.as_bytes()) {
-        IResult::Done(_, entry) => entry,
-        _                       => panic!("Wrong map format")
-    }
+    nom_parse::complete(input, raw_deer, reindeer, "a reindeer description")
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<Reindeer>> {
+    input.lines()
+         .map(|raw_deer| parse_reindeer(input, raw_deer))
+         .collect()
 }
 
 fn travelled_distance(reindeer: &Reindeer, total_time: Second) -> u32 {
@@ -90,7 +96,7 @@ fn travelled_distance(reindeer: &Reindeer, total_time: Second) -> u32 {
     total_fly_time * reindeer.speed
 }
 
-fn race_result(reindeers: &Vec<Reindeer>) -> Points {
+fn race_result(reindeers: &[Reindeer]) -> Points {
     let mut deer_states = reindeers.iter().map(DeerRaceState::new)
                                           .collect::<Vec<_>>();
 
@@ -107,16 +113,35 @@ fn race_result(reindeers: &Vec<Reindeer>) -> Points {
     deer_states.iter().map(|st| st.points).max().unwrap_or(0)
 }
 
-pub fn p1(input: &str) -> u32 {
-    input.trim().split('\n').map(parse_reindeer)
-                            .map(|rd| travelled_distance(&rd, RACE_TIME))
-                            .max().unwrap_or(0)
+pub fn p1(reindeers: &[Reindeer]) -> u32 {
+    reindeers.iter().map(|rd| travelled_distance(rd, RACE_TIME))
+                    .max().unwrap_or(0)
 }
 
-pub fn p2(input: &str) -> u32 {
-    let reindeers = input.trim().split('\n')
-                                .map(parse_reindeer)
-                                .collect::<Vec<_>>();
+pub fn p2(reindeers: &[Reindeer]) -> u32 {
+    race_result(reindeers)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 14;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day14.txt");
 
-    race_result(&reindeers)
+    type Parsed<'a> = Vec<Reindeer>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
 }
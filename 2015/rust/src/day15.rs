@@ -1,7 +1,11 @@
 extern crate nom;
 
+use aoc_common::{Solution, ParseResult};
+use nom_parse;
+
 #[derive(Debug)]
-struct Ingredient {
+pub struct Ingredient {
+    #[allow(dead_code)] // Only there to make the debug output readable
     name: String,
     capacity: i32,
     durability: i32,
@@ -10,49 +14,50 @@ struct Ingredient {
     calories: i32
 }
 
-fn parse_ingredient(raw_ingredient: &str) -> Ingredient {
+fn parse_ingredient(input: &str, raw_ingredient: &str) -> ParseResult<Ingredient> {
     use self::nom::*;
 
     use std::str::FromStr;
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
-    named!(number<i32>, do_parse!(
-        sign: opt!(tag_s!("-")) >>
-        n: map!(digit, |d| -> i32 {
-            FromStr::from_str(from_utf8(d).unwrap()).unwrap()
-        }) >>
+    named!(number<Input, i32>, do_parse!(
+        sign: opt!(tag!("-")) >>
+        n: map_res!(map_res!(digit, utf8), i32::from_str) >>
         (if sign.is_some() { -n } else { n })
     ));
-    named!(name<String>, map!(
-        take_while1!(is_alphabetic),
-        |s| from_utf8(s).unwrap().to_string()
+    named!(name<Input, String>, map!(
+        map_res!(take_while1!(is_alphabetic), utf8),
+        String::from
     ));
-    named!(ingredient<Ingredient>, do_parse!(
+    named!(ingredient<Input, Ingredient>, do_parse!(
         name: name >>
-        tag_s!(": capacity ") >>
+        tag!(": capacity ") >>
         capacity: number >>
-        tag_s!(", durability ") >>
+        tag!(", durability ") >>
         durability: number >>
-        tag_s!(", flavor ") >>
+        tag!(", flavor ") >>
         flavor: number >>
-        tag_s!(", texture ") >>
+        tag!(", texture ") >>
         texture: number >>
-        tag_s!(", calories ") >>
+        tag!(", calories ") >>
         calories: number >>
         (Ingredient {
-            name: name,
-            capacity: capacity,
-            durability: durability,
-            flavor: flavor,
-            texture: texture,
-            calories: calories,
+            name,
+            capacity,
+            durability,
+            flavor,
+            texture,
+            calories,
         })
     ));
 
-    match ingredient(raw_ingredient.as_bytes()) {
-        IResult::Done(_, entry) => entry,
-        _                       => panic!("Wrong ingredient format")
-    }
+    nom_parse::complete(input, raw_ingredient, ingredient, "an ingredient description")
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<Ingredient>> {
+    input.lines()
+         .map(|raw_ingredient| parse_ingredient(input, raw_ingredient))
+         .collect()
 }
 
 type Recipe<'a> = Vec<(&'a Ingredient, i32)>;
@@ -80,7 +85,7 @@ fn distrib(sum: i32, count: usize) -> Vec<Vec<i32>> {
     }
 }
 
-fn possible_recipes(ingredients: &Vec<Ingredient>) -> Vec<Recipe> {
+fn possible_recipes(ingredients: &[Ingredient]) -> Vec<Recipe<'_>> {
     let distributions = distrib(100, ingredients.len());
     distributions.iter()
                  .map(|d| ingredients.iter().zip(d.iter())
@@ -92,25 +97,41 @@ fn possible_recipes(ingredients: &Vec<Ingredient>) -> Vec<Recipe> {
 fn recipe_calories(recipe: &Recipe) -> i32 {
     recipe.iter().map(|&(i, n)| {
         i.calories * n
-    }).fold(0, |acc, x| acc + x)
+    }).sum()
 }
 
-pub fn p1(input: &str) -> i32 {
-    let ingredients = input.trim().split('\n')
-                                  .map(parse_ingredient)
-                                  .collect::<Vec<_>>();
-
-    let all_recipes = possible_recipes(&ingredients);
+pub fn p1(ingredients: &[Ingredient]) -> i32 {
+    let all_recipes = possible_recipes(ingredients);
     all_recipes.iter().map(recipe_score).max().unwrap_or(0)
 }
 
-pub fn p2(input: &str) -> i32 {
-    let ingredients = input.trim().split('\n')
-                                  .map(parse_ingredient)
-                                  .collect::<Vec<_>>();
-
-    let all_recipes = possible_recipes(&ingredients);
+pub fn p2(ingredients: &[Ingredient]) -> i32 {
+    let all_recipes = possible_recipes(ingredients);
     all_recipes.iter().filter(|r| recipe_calories(r) == 500)
                       .map(recipe_score)
                       .max().unwrap_or(0)
 }
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 15;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day15.txt");
+
+    type Parsed<'a> = Vec<Ingredient>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
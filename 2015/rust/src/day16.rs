@@ -1,6 +1,8 @@
 extern crate nom;
 
 use std::collections::HashMap;
+use aoc_common::{Solution, ParseResult};
+use nom_parse;
 
 #[derive(Debug)]
 struct Property {
@@ -9,48 +11,51 @@ struct Property {
 }
 
 #[derive(Debug)]
-struct Aunt {
+pub struct Aunt {
     id: u32,
     properties: [Property; 3],
 }
 
-fn parse_aunt(raw_aunt: &str) -> Aunt {
+fn parse_aunt(input: &str, raw_aunt: &str) -> ParseResult<Aunt> {
     use self::nom::*;
 
     use std::str::FromStr;
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
-    named!(number<u32>, map!(
-        digit,
-        |d| FromStr::from_str(from_utf8(d).unwrap()).unwrap()
+    named!(number<Input, u32>, map_res!(
+        map_res!(digit, utf8),
+        FromStr::from_str
     ));
-    named!(name<String>, map!(
-        take_while1!(is_alphabetic),
-        |s| from_utf8(s).unwrap().to_string()
+    named!(name<Input, String>, map!(
+        map_res!(take_while1!(is_alphabetic), utf8),
+        String::from
     ));
-    named!(property<Property>, do_parse!(
+    named!(property<Input, Property>, do_parse!(
         name: name    >>
-        tag_s!(": ")  >>
+        tag!(": ")  >>
         value: number >>
-        (Property { name: name, value: value })
+        (Property { name, value })
     ));
 
-    named!(aunt<Aunt>, do_parse!(
-        tag_s!("Sue ") >>
+    named!(aunt<Input, Aunt>, do_parse!(
+        tag!("Sue ") >>
         id: number     >>
-        tag_s!(": ")   >>
+        tag!(": ")   >>
         p1: property   >>
-        tag_s!(", ")   >>
+        tag!(", ")   >>
         p2: property   >>
-        tag_s!(", ")   >>
+        tag!(", ")   >>
         p3: property   >>
-        (Aunt { id: id, properties: [p1, p2, p3] })
+        (Aunt { id, properties: [p1, p2, p3] })
     ));
 
-    match aunt(raw_aunt.as_bytes()) {
-        IResult::Done(_, aunt) => aunt,
-        _                      => panic!("Wrong aunt format")
-    }
+    nom_parse::complete(input, raw_aunt, aunt, "an aunt like `Sue 1: cars: 9, akitas: 3, goldfish: 0`")
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<Aunt>> {
+    input.lines()
+         .map(|raw_aunt| parse_aunt(input, raw_aunt))
+         .collect()
 }
 
 fn matching_aunt_p1(aunt: &Aunt) -> bool {
@@ -99,16 +104,38 @@ fn matching_aunt_p2(aunt: &Aunt) -> bool {
     })
 }
 
-pub fn p1(input: &str) -> u32 {
-    input.trim().split('\n')
-                .map(parse_aunt)
-                .find(matching_aunt_p1)
-                .map_or(0, |aunt| aunt.id)
+pub fn p1(aunts: &[Aunt]) -> u32 {
+    aunts.iter()
+         .find(|aunt| matching_aunt_p1(aunt))
+         .map_or(0, |aunt| aunt.id)
 }
 
-pub fn p2(input: &str) -> u32 {
-    input.trim().split('\n')
-                .map(parse_aunt)
-                .find(matching_aunt_p2)
-                .map_or(0, |aunt| aunt.id)
+pub fn p2(aunts: &[Aunt]) -> u32 {
+    aunts.iter()
+         .find(|aunt| matching_aunt_p2(aunt))
+         .map_or(0, |aunt| aunt.id)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 16;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day16.txt");
+
+    type Parsed<'a> = Vec<Aunt>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
 }
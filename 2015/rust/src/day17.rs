@@ -1,3 +1,5 @@
+use aoc_common::{Solution, ParseResult, parse};
+
 type Container = u32;
 
 fn combinations(sum: u32, containers: Vec<Container>, so_far: Vec<Container>) -> Vec<Vec<Container>> {
@@ -17,8 +19,7 @@ fn combinations(sum: u32, containers: Vec<Container>, so_far: Vec<Container>) ->
             Less => {
                 let combination = new_combination(*container);
                 let remaining = sum - container;
-                let new_containers = containers.iter().skip(i + 1)
-                                               .map(|c| *c)
+                let new_containers = containers.iter().skip(i + 1).copied()
                                                .collect::<Vec<_>>();
                 all.append(&mut combinations(
                     remaining, new_containers, combination
@@ -31,24 +32,42 @@ fn combinations(sum: u32, containers: Vec<Container>, so_far: Vec<Container>) ->
     all
 }
 
-pub fn p1(input: &str) -> usize {
-    use std::str::FromStr;
+pub fn parse_input(input: &str) -> ParseResult<Vec<Container>> {
+    input.lines().map(|s|
+        parse::field(input, s, "a container size")
+    ).collect()
+}
 
-    let containers = input.trim().split('\n').map(|s|
-        FromStr::from_str(s).unwrap()
-    ).collect::<Vec<_>>();
+pub fn p1(containers: &[Container]) -> usize {
+    combinations(150, containers.to_vec(), Vec::new()).len()
+}
 
-    combinations(150, containers, Vec::new()).len()
+pub fn p2(containers: &[Container]) -> usize {
+    let combinations = combinations(150, containers.to_vec(), Vec::new());
+    let smallest = combinations.iter().map(Vec::len).min().unwrap_or(0);
+    combinations.iter().filter(|c| c.len() == smallest).count()
 }
 
-pub fn p2(input: &str) -> usize {
-    use std::str::FromStr;
+pub struct Day17;
 
-    let containers = input.trim().split('\n').map(|s|
-        FromStr::from_str(s).unwrap()
-    ).collect::<Vec<_>>();
+impl Solution for Day17 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 17;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day17.txt");
 
-    let combinations = combinations(150, containers, Vec::new());
-    let smallest = combinations.iter().map(Vec::len).min().unwrap_or(0);
-    combinations.iter().filter(|c| c.len() == smallest).count()
+    type Parsed<'a> = Vec<Container>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
 }
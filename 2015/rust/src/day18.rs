@@ -1,9 +1,11 @@
+use aoc_common::{Solution, ParseError, ParseResult};
+
 const GRID_W: usize = 100;
 const GRID_H: usize = 100;
 
 #[derive(PartialEq, Clone)]
-enum LightState { On, Off }
-type LightGrid = Vec<LightState>;
+pub enum LightState { On, Off }
+pub type LightGrid = Vec<LightState>;
 
 const NEIGHBOR_DELTAS: [(i32, i32); 8] = [
     (-1, -1), (0, -1), (1, -1),
@@ -18,11 +20,28 @@ const ALWAYS_ON_INDEXES: [usize; 4] = [
     GRID_W * GRID_H - 1
 ];
 
-fn state_from_char(c: char) -> LightState {
-    match c {
-        '#' => LightState::On,
-        _   => LightState::Off,
+pub fn parse_input(input: &str) -> ParseResult<LightGrid> {
+    let mut grid = LightGrid::with_capacity(GRID_W * GRID_H);
+
+    for line in input.lines() {
+        if line.len() != GRID_W {
+            return Err(ParseError::after(input, line, format!("a line of {} lights", GRID_W)))
+        }
+
+        for (x, c) in line.char_indices() {
+            grid.push(match c {
+                '#' => LightState::On,
+                '.' => LightState::Off,
+                _   => return Err(ParseError::at(input, &line[x..], "a light (`#` or `.`)")),
+            })
+        }
     }
+
+    if grid.len() != GRID_W * GRID_H {
+        return Err(ParseError::end(input, format!("{} lines of lights", GRID_H)))
+    }
+
+    Ok(grid)
 }
 
 fn next_light_state(state: LightState, neighbors: Vec<LightState>) -> LightState {
@@ -57,12 +76,12 @@ fn next_grid_state_p1(grid: &LightGrid) -> LightGrid {
     let mut new_grid = vec![LightState::Off; GRID_H * GRID_W];
 
     (0..GRID_W * GRID_H).for_each(|idx| {
-        let light_state = unsafe { grid.get_unchecked(idx as usize) };
+        let light_state = unsafe { grid.get_unchecked(idx) };
         let (x, y) = (idx % GRID_W, idx / GRID_H);
         let neighbors = neighbors(grid, x, y);
 
         new_grid.insert(
-            idx as usize,
+            idx,
             next_light_state(light_state.clone(), neighbors)
         );
     });
@@ -81,21 +100,19 @@ fn next_grid_state_p2(grid: &LightGrid) -> LightGrid {
             else {
                 let (x, y) = (idx % GRID_W, idx / GRID_H);
                 let neighbors = neighbors(grid, x, y);
-                let light_state = unsafe { grid.get_unchecked(idx as usize) };
+                let light_state = unsafe { grid.get_unchecked(idx) };
                 next_light_state(light_state.clone(), neighbors)
             }
         };
 
-        new_grid.insert(idx as usize, new_light_state);
+        new_grid.insert(idx, new_light_state);
     });
 
     new_grid
 }
 
-pub fn p1(input: &str) -> usize {
-    let mut grid = input.trim().split('\n')
-                               .flat_map(|l| l.chars().map(state_from_char))
-                               .collect::<LightGrid>();
+pub fn p1(grid: &LightGrid) -> usize {
+    let mut grid = grid.clone();
 
     for _ in 0..100 {
         grid = next_grid_state_p1(&grid);
@@ -104,10 +121,8 @@ pub fn p1(input: &str) -> usize {
     grid.iter().filter(|&s| *s == LightState::On).count()
 }
 
-pub fn p2(input: &str) -> usize {
-    let mut grid = input.trim().split('\n')
-                               .flat_map(|l| l.chars().map(state_from_char))
-                               .collect::<LightGrid>();
+pub fn p2(grid: &LightGrid) -> usize {
+    let mut grid = grid.clone();
 
     ALWAYS_ON_INDEXES.iter().for_each(|idx| { grid[*idx] = LightState::On; });
 
@@ -117,3 +132,27 @@ pub fn p2(input: &str) -> usize {
 
     grid.iter().filter(|&s| *s == LightState::On).count()
 }
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 18;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day18.txt");
+
+    type Parsed<'a> = LightGrid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
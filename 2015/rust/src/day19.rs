@@ -1,36 +1,50 @@
 extern crate nom;
 
 use std::collections::HashSet;
+use aoc_common::{Solution, ParseError, ParseResult};
+use nom_parse;
 
 type Transform = (String, String);
+pub type Puzzle<'a> = (Vec<Transform>, &'a str);
 
-fn parse_transform(raw_transform: &str) -> Transform {
+fn parse_transform(input: &str, raw_transform: &str) -> ParseResult<Transform> {
     use self::nom::*;
 
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
-    named!(s<String>, map!(
-        take_while1!(is_alphabetic),
-        |s| from_utf8(s).unwrap().to_string()
+    named!(s<Input, String>, map!(
+        map_res!(take_while1!(is_alphabetic), utf8),
+        String::from
     ));
 
-    named!(transform<Transform>, do_parse!(
+    named!(transform<Input, Transform>, do_parse!(
         before: s >>
-        tag_s!(" => ") >>
+        tag!(" => ") >>
         after: s >>
         ((before, after))
     ));
 
-    match transform(raw_transform.as_bytes()) {
-        IResult::Done(_, transform) => transform,
-        _                           => panic!("Wrong transform format")
-    }
+    nom_parse::complete(input, raw_transform, transform, "a replacement like `H => HO`")
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Puzzle<'_>> {
+    let mut puzzle_parts = input.trim_end().split("\n\n");
+
+    let raw_transforms = puzzle_parts.next().unwrap_or(input);
+    let molecule = puzzle_parts.next()
+        .ok_or_else(|| ParseError::end(input, "a blank line followed by the medicine molecule"))?;
+
+    let transforms = raw_transforms.lines()
+                                   .map(|raw_transform| parse_transform(input, raw_transform))
+                                   .collect::<ParseResult<_>>()?;
+
+    Ok((transforms, molecule))
 }
 
-fn possible_transforms(input: String, transforms: &Vec<Transform>) -> HashSet<String> {
+fn possible_transforms(input: String, transforms: &[Transform]) -> HashSet<String> {
     let mut all = HashSet::new();
 
-    for &(ref before, ref after) in transforms.iter() {
+    for (before, after) in transforms.iter() {
         for (idx, _) in input.match_indices(before) {
             let mut cpy = input.to_string();
             for _ in 0..before.len() {
@@ -44,25 +58,37 @@ fn possible_transforms(input: String, transforms: &Vec<Transform>) -> HashSet<St
     all
 }
 
-pub fn p1(input: &str) -> usize {
-    let puzzle_parts = input.trim().split("\n\n")
-                                   .collect::<Vec<_>>();
-    let (raw_transforms, initial_molecule) = (puzzle_parts[0], puzzle_parts[1]);
-
-    let transforms = raw_transforms.split('\n')
-                                   .map(parse_transform)
-                                   .collect::<Vec<_>>();
-
-    possible_transforms(initial_molecule.to_string(), &transforms).len()
+pub fn p1(&(ref transforms, initial_molecule): &Puzzle) -> usize {
+    possible_transforms(initial_molecule.to_string(), transforms).len()
 }
 
-pub fn p2(input: &str) -> usize {
-    let puzzle_parts = input.trim().split("\n\n")
-                                   .collect::<Vec<_>>();
-    let target_molecule = puzzle_parts[1];
-
+pub fn p2(&(_, target_molecule): &Puzzle) -> usize {
     let count_str = |x| target_molecule.matches(x).collect::<Vec<_>>().len();
 
     target_molecule.chars().filter(|c| c.is_uppercase()).count()
         - count_str("Rn") - count_str("Ar") - 2 * count_str("Y") - 1
 }
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 19;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day19.txt");
+
+    type Parsed<'a> = Puzzle<'a>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
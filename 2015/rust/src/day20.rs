@@ -1,3 +1,5 @@
+use aoc_common::{Solution, ParseResult, parse};
+
 fn solve(target_gifts: u32, gift_factor: u32, gift_limit: u32) -> Option<u32> {
     use std::cmp::min;
 
@@ -18,12 +20,38 @@ fn solve(target_gifts: u32, gift_factor: u32, gift_limit: u32) -> Option<u32> {
     None
 }
 
-pub fn p1(input: &str) -> u32 {
-    let num_gifts = input.trim().parse::<u32>().unwrap();
+pub fn parse_input(input: &str) -> ParseResult<u32> {
+    parse::field(input, input.trim(), "a number of presents")
+}
+
+pub fn p1(&num_gifts: &u32) -> u32 {
     solve(num_gifts, 10, num_gifts / 10).unwrap_or(0)
 }
 
-pub fn p2(input: &str) -> u32 {
-    let num_gifts = input.trim().parse::<u32>().unwrap();
+pub fn p2(&num_gifts: &u32) -> u32 {
     solve(num_gifts, 11, 50).unwrap_or(0)
 }
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 20;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day20.txt");
+
+    type Parsed<'a> = u32;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
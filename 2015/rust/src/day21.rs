@@ -1,7 +1,10 @@
 extern crate nom;
 
+use aoc_common::{Solution, ParseResult};
+use nom_parse;
+
 #[derive(Debug)]
-struct Character {
+pub struct Character {
     hp: u32,
     damage: u32,
     armor: u32,
@@ -46,30 +49,27 @@ const RINGS: [Item; 6] = [
     Item { cost: 80,  damage: 0, armor: 3 },
 ];
 
-fn parse_character(raw_character: &str) -> Character {
+pub fn parse_input(input: &str) -> ParseResult<Character> {
     use self::nom::*;
 
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
-    named!(number<u32>, map!(
-        digit,
-        |s| from_utf8(s).unwrap().parse::<u32>().unwrap()
+    named!(number<Input, u32>, map_res!(
+        map_res!(digit, utf8),
+        str::parse::<u32>
     ));
 
-    named!(character<Character>, do_parse!(
-        tag_s!("Hit Points: ") >>
+    named!(character<Input, Character>, do_parse!(
+        tag!("Hit Points: ") >>
         hp: number             >>
-        tag_s!("\nDamage: ")   >>
+        tag!("\nDamage: ")   >>
         dmg: number            >>
-        tag_s!("\nArmor: ")    >>
+        tag!("\nArmor: ")    >>
         armor: number          >>
-        (Character { hp: hp, damage: dmg, armor: armor })
+        (Character { hp, damage: dmg, armor })
     ));
 
-    match character(raw_character.as_bytes()) {
-        IResult::Done(_, character) => character,
-        _                           => panic!("Wrong character format")
-    }
+    nom_parse::complete(input, input.trim_end(), character, "the boss stats")
 }
 
 fn damage_amount(damage: u32, armor: u32) -> u32 {
@@ -82,7 +82,7 @@ fn damage_amount(damage: u32, armor: u32) -> u32 {
 }
 
 fn turns_until_death(hp: u32, damage: u32) -> u32 {
-    hp / damage + if hp % damage == 0 { 0 } else { 1 }
+    hp / damage + if hp.is_multiple_of(damage) { 0 } else { 1 }
 }
 
 fn fight_result(player: &Character, boss: &Character) -> FightResult {
@@ -149,17 +149,38 @@ fn losing_item_combinations(boss: &Character) -> Vec<Items> {
     ).collect()
 }
 
-pub fn p1(input: &str) -> u32 {
-    let boss = parse_character(input.trim());
-
-    winning_item_combinations(&boss).iter()
+pub fn p1(boss: &Character) -> u32 {
+    winning_item_combinations(boss).iter()
                                     .map(|items| items.iter().map(|i| i.cost).sum())
                                     .min().unwrap_or(0)
 }
 
-pub fn p2(input: &str) -> u32 {
-    let boss = parse_character(input.trim());
-    losing_item_combinations(&boss).iter()
+pub fn p2(boss: &Character) -> u32 {
+    losing_item_combinations(boss).iter()
                                    .map(|items| items.iter().map(|i| i.cost).sum())
                                    .max().unwrap_or(0)
 }
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 21;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day21.txt");
+
+    type Parsed<'a> = Character;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
@@ -1,9 +1,12 @@
 extern crate nom;
 
-use std::collections::{BinaryHeap, VecDeque};
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use aoc_common::{Solution, ParseResult, search};
+use nom_parse;
 
 #[derive(Clone)]
-struct Boss {
+pub struct Boss {
     hp: u32,
     damage: u32,
 }
@@ -26,6 +29,11 @@ impl PartialEq for Spell {
         self.id == other.id
     }
 }
+impl Hash for Spell {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
 
 type Spells = Vec<&'static Spell>;
 
@@ -53,13 +61,13 @@ const SPELLS: [Spell; 5] = [
     Spell   { id: "Magic Missile"
             , cost: 53
             , effect: Effect::Immediate(|_, b, _|
-                b.hp = b.hp.checked_sub(4).unwrap_or(0)
+                b.hp = b.hp.saturating_sub(4)
             )
             },
     Spell   { id: "Drain"
             , cost: 73
             , effect: Effect::Immediate(|p, b, _| {
-                b.hp = b.hp.checked_sub(2).unwrap_or(0);
+                b.hp = b.hp.saturating_sub(2);
                 p.hp += 2;
             })
             },
@@ -76,7 +84,7 @@ const SPELLS: [Spell; 5] = [
     Spell   { id: "Poison"
             , cost: 173
             , effect: Effect::OverTime(6, |_, b, _|
-                b.hp = b.hp.checked_sub(3).unwrap_or(0)
+                b.hp = b.hp.saturating_sub(3)
             )
             },
     Spell   { id: "Recharge"
@@ -93,28 +101,25 @@ const PLAYER: Player = Player {
     mana: 500,
 };
 
-fn parse_boss(raw_boss: &str) -> Boss {
+pub fn parse_input(input: &str) -> ParseResult<Boss> {
     use self::nom::*;
 
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
-    named!(number<u32>, map!(
-        digit,
-        |s| from_utf8(s).unwrap().parse::<u32>().unwrap()
+    named!(number<Input, u32>, map_res!(
+        map_res!(digit, utf8),
+        str::parse::<u32>
     ));
 
-    named!(boss<Boss>, do_parse!(
-        tag_s!("Hit Points: ") >>
+    named!(boss<Input, Boss>, do_parse!(
+        tag!("Hit Points: ") >>
         hp: number             >>
-        tag_s!("\nDamage: ")   >>
+        tag!("\nDamage: ")   >>
         dmg: number            >>
-        (Boss { hp: hp, damage: dmg })
+        (Boss { hp, damage: dmg })
     ));
 
-    match boss(raw_boss.as_bytes()) {
-        IResult::Done(_, boss) => boss,
-        _                      => panic!("Wrong character format")
-    }
+    nom_parse::complete(input, input.trim_end(), boss, "the boss stats")
 }
 
 fn damage_amount(damage: u32, armor: u32) -> u32 {
@@ -181,7 +186,7 @@ fn fight_logic(spells: &Spells, fight: &mut Fight, pre_turn: PreTurn) -> FightRe
         if fight.boss.hp == 0 { return Win }
 
         let boss_damage = damage_amount(fight.boss.damage, fight.player.armor);
-        fight.player.hp = fight.player.hp.checked_sub(boss_damage).unwrap_or(0);
+        fight.player.hp = fight.player.hp.saturating_sub(boss_damage);
         if fight.player.hp == 0 { return LossByDeath }
 
     }
@@ -189,67 +194,59 @@ fn fight_logic(spells: &Spells, fight: &mut Fight, pre_turn: PreTurn) -> FightRe
     LossByOOM
 }
 
-#[derive(Eq, PartialEq)]
+// A sequence of spells, simulated once when discovered
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Node {
-    cost: u32,
-    spells: Spells
-}
-
-use std::cmp::Ordering;
-
-impl Ord for Node {
-    fn cmp(&self, other: &Node) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
+    spells: Spells,
+    outcome: Outcome,
 }
 
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Node) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Outcome {
+    Won,
+    Castable(Spells),
 }
 
 fn minmax_fight(boss: &Boss, fight_logic: FightLogic) -> Option<u32> {
-    let mut open_set = BinaryHeap::new();
-
-    for spell in &SPELLS {
-        open_set.push(Node { cost: spell.cost, spells: vec![spell] })
-    }
-
-    loop {
-        if let Some(node) = open_set.pop() {
-            let mut fight = Fight {
-                boss: boss.clone(),
-                player: PLAYER,
-                effects: Effects::new()
-            };
-
-            let result = fight_logic(&mut fight, &node.spells);
-
-            match result {
-                FightResult::Win         => return Some(node.cost),
-                FightResult::LossByDeath => (),
-                FightResult::LossByOOM   => {
-                    let castable_spells = SPELLS.iter()
-                                                .filter(|s| can_cast(&fight, s));
-
-                    for spell in castable_spells {
-                        let mut new_spells = node.spells.clone();
-                        new_spells.push(spell);
-                        open_set.push(Node {
-                            cost: node.cost + spell.cost,
-                            spells: new_spells
-                        });
-                    }
-                },
-            }
-        }
-        else {
-            break
+    // Fights lost by death lead nowhere and are left out
+    let simulate = |spells: Spells| {
+        let mut fight = Fight {
+            boss: boss.clone(),
+            player: PLAYER,
+            effects: Effects::new()
+        };
+
+        let outcome = match fight_logic(&mut fight, &spells) {
+            FightResult::Win => Outcome::Won,
+            FightResult::LossByDeath => return None,
+            FightResult::LossByOOM => Outcome::Castable(
+                SPELLS.iter()
+                      .filter(|s| can_cast(&fight, s))
+                      .collect()
+            ),
+        };
+
+        Some(Node { spells, outcome })
+    };
+
+    let next_nodes = |node: &Node| {
+        match &node.outcome {
+            Outcome::Castable(castable) => {
+                castable.iter()
+                        .filter_map(|&spell| {
+                            let mut new_spells = node.spells.clone();
+                            new_spells.push(spell);
+                            simulate(new_spells).map(|next| (next, spell.cost))
+                        })
+                        .collect()
+            },
+            Outcome::Won => Vec::new(),
         }
-    }
+    };
 
-    None
+    search::dijkstra(simulate(Spells::new()), next_nodes)
+        .find(|(node, _)| node.outcome == Outcome::Won)
+        .map(|(_, cost)| cost)
 }
 
 fn fight_p1(fight: &mut Fight, spells: &Spells) -> FightResult {
@@ -260,14 +257,34 @@ fn fight_p2(fight: &mut Fight, spells: &Spells) -> FightResult {
     fight_logic(spells, fight, |f| f.player.hp -= 1)
 }
 
-pub fn p1(input: &str) -> u32 {
-    let boss = parse_boss(input.trim());
+pub fn p1(boss: &Boss) -> u32 {
+    minmax_fight(boss, fight_p1).unwrap_or(0)
+}
 
-    minmax_fight(&boss, fight_p1).unwrap_or(0)
+pub fn p2(boss: &Boss) -> u32 {
+    minmax_fight(boss, fight_p2).unwrap_or(0)
 }
 
-pub fn p2(input: &str) -> u32 {
-    let boss = parse_boss(input.trim());
+pub struct Day22;
+
+impl Solution for Day22 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 22;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day22.txt");
 
-    minmax_fight(&boss, fight_p2).unwrap_or(0)
+    type Parsed<'a> = Boss;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
 }
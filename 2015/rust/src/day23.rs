@@ -1,6 +1,8 @@
 extern crate nom;
 
 use std::collections::HashMap;
+use aoc_common::{Solution, ParseResult};
+use nom_parse;
 
 type Register = String;
 type Offset = i32;
@@ -8,7 +10,7 @@ type Offset = i32;
 type Registers = HashMap<Register, u32>;
 
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Hlf(Register),
     Tpl(Register),
     Inc(Register),
@@ -17,54 +19,57 @@ enum Instruction {
     Jio(Register, Offset),
 }
 
-fn parse_instruction(raw_instruction: &str) -> Instruction {
+fn parse_instruction(input: &str, raw_instruction: &str) -> ParseResult<Instruction> {
     use self::nom::*;
     use self::Instruction::*;
 
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
-    named!(number<i32>, map!(
-        digit,
-        |s| from_utf8(s).unwrap().parse::<i32>().unwrap()
+    named!(number<Input, i32>, map_res!(
+        map_res!(digit, utf8),
+        str::parse::<i32>
     ));
 
-    named!(register<Register>, map!(
-        alt!(tag_s!("a") | tag_s!("b")),
-        |s| from_utf8(s).unwrap().to_string()
+    named!(register<Input, Register>, map!(
+        map_res!(alt!(tag!("a") | tag!("b")), utf8),
+        String::from
     ));
-    named!(offset<self::Offset>, do_parse!(
-        sign: alt!(tag_s!("+") | tag_s!("-")) >>
+    named!(offset<Input, self::Offset>, do_parse!(
+        sign: alt!(tag!("+") | tag!("-")) >>
         value: number                         >>
-        (if sign == "+".as_bytes() { value } else { -value })
+        (if sign.0 == b"+" { value } else { -value })
     ));
 
-    named!(hlf<Instruction>, do_parse!(
-        tag_s!("hlf ") >> reg: register >> (Hlf(reg))
+    named!(hlf<Input, Instruction>, do_parse!(
+        tag!("hlf ") >> reg: register >> (Hlf(reg))
     ));
-    named!(tpl<Instruction>, do_parse!(
-        tag_s!("tpl ") >> reg: register >> (Tpl(reg))
+    named!(tpl<Input, Instruction>, do_parse!(
+        tag!("tpl ") >> reg: register >> (Tpl(reg))
     ));
-    named!(inc<Instruction>, do_parse!(
-        tag_s!("inc ") >> reg: register >> (Inc(reg))
+    named!(inc<Input, Instruction>, do_parse!(
+        tag!("inc ") >> reg: register >> (Inc(reg))
     ));
-    named!(jmp<Instruction>, do_parse!(
-        tag_s!("jmp ") >> off: offset   >> (Jmp(off))
+    named!(jmp<Input, Instruction>, do_parse!(
+        tag!("jmp ") >> off: offset   >> (Jmp(off))
     ));
-    named!(jie<Instruction>, do_parse!(
-        tag_s!("jie ") >> reg: register >>
-        tag_s!(", ")   >> off: offset   >> (Jie(reg, off))
+    named!(jie<Input, Instruction>, do_parse!(
+        tag!("jie ") >> reg: register >>
+        tag!(", ")   >> off: offset   >> (Jie(reg, off))
     ));
-    named!(jio<Instruction>, do_parse!(
-        tag_s!("jio ") >> reg: register >>
-        tag_s!(", ")   >> off: offset   >> (Jio(reg, off))
+    named!(jio<Input, Instruction>, do_parse!(
+        tag!("jio ") >> reg: register >>
+        tag!(", ")   >> off: offset   >> (Jio(reg, off))
     ));
 
-    named!(instruction<Instruction>, alt!(hlf | tpl | inc | jmp | jie | jio));
+    named!(instruction<Input, Instruction>, alt!(hlf | tpl | inc | jmp | jie | jio));
 
-    match instruction(raw_instruction.as_bytes()) {
-        IResult::Done(_, instruction) => instruction,
-        _                             => panic!("Wrong instruction format")
-    }
+    nom_parse::complete(input, raw_instruction, instruction, "an instruction like `jio a, +19`")
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<Instruction>> {
+    input.lines()
+         .map(|raw_instruction| parse_instruction(input, raw_instruction))
+         .collect()
 }
 
 fn update_reg(reg: &Register, regs: &mut Registers, f: fn(u32) -> u32) {
@@ -76,7 +81,7 @@ fn get_reg(reg: &Register, regs: &mut Registers) -> u32 {
     regs.get(reg).map_or(0, Clone::clone)
 }
 
-fn run(instructions: &Vec<Instruction>, regs: &mut Registers) {
+fn run(instructions: &[Instruction], regs: &mut Registers) {
     use self::Instruction::*;
 
     let mut pc = 0i32;
@@ -91,7 +96,7 @@ fn run(instructions: &Vec<Instruction>, regs: &mut Registers) {
                     Tpl(ref r)    => update_reg(r, regs, |v| v * 3),
                     Inc(ref r)    => update_reg(r, regs, |v| v + 1),
                     Jmp(o)        => pc_offset = o,
-                    Jie(ref r, o) => if get_reg(r, regs) % 2 == 0 { pc_offset = o; },
+                    Jie(ref r, o) => if get_reg(r, regs).is_multiple_of(2) { pc_offset = o; },
                     Jio(ref r, o) => if get_reg(r, regs) == 1     { pc_offset = o; },
                 }
                 pc += pc_offset;
@@ -100,25 +105,43 @@ fn run(instructions: &Vec<Instruction>, regs: &mut Registers) {
     }
 }
 
-pub fn p1(input: &str) -> u32 {
-    let instructions = input.trim().split('\n')
-                                   .map(parse_instruction)
-                                   .collect::<Vec<_>>();
+pub fn p1(instructions: &[Instruction]) -> u32 {
     let mut registers = Registers::new();
 
-    run(&instructions, &mut registers);
+    run(instructions, &mut registers);
 
-    registers.get(&"b".to_string()).map_or(0, Clone::clone)
+    registers.get("b").map_or(0, Clone::clone)
 }
 
-pub fn p2(input: &str) -> u32 {
-    let instructions = input.trim().split('\n')
-                                   .map(parse_instruction)
-                                   .collect::<Vec<_>>();
+pub fn p2(instructions: &[Instruction]) -> u32 {
     let mut registers = Registers::new();
     registers.insert("a".to_string(), 1);
 
-    run(&instructions, &mut registers);
+    run(instructions, &mut registers);
+
+    registers.get("b").map_or(0, Clone::clone)
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 23;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day23.txt");
 
-    registers.get(&"b".to_string()).map_or(0, Clone::clone)
+    type Parsed<'a> = Vec<Instruction>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
 }
@@ -2,9 +2,10 @@ use std::collections::BinaryHeap;
 use std::cmp::Ordering;
 
 use std::marker::PhantomData;
+use aoc_common::{Solution, ParseResult, parse};
 
 type Package = u32;
-type Packages = Vec<Package>;
+pub type Packages = Vec<Package>;
 type Entanglement = u64;
 
 #[derive(Clone, Eq, PartialEq)]
@@ -61,30 +62,25 @@ fn smallest_distrib<Heuristic>(pool: &Packages, size: u32) -> Option<Packages>
         })
     }
 
-    loop {
-        if let Some(node) = open_set.pop() {
-            match node.weight.cmp(&size) {
-                Equal   => return Some(node.indices.iter().map(|i| pool[*i]).collect()),
-                Greater => (),
-                Less    => {
-                    for (package, i) in pool.iter().zip(0..) {
-                        if !node.indices.contains(&i) {
-                            let mut new_indices = node.indices.clone();
-                            new_indices.push(i);
-                            open_set.push(Node::<Heuristic> {
-                                indices: new_indices,
-                                weight: node.weight + package,
-                                entanglement: node.entanglement * *package as Entanglement,
-                                phantom: PhantomData
-                            });
-                        }
+    while let Some(node) = open_set.pop() {
+        match node.weight.cmp(&size) {
+            Equal   => return Some(node.indices.iter().map(|i| pool[*i]).collect()),
+            Greater => (),
+            Less    => {
+                for (package, i) in pool.iter().zip(0..) {
+                    if !node.indices.contains(&i) {
+                        let mut new_indices = node.indices.clone();
+                        new_indices.push(i);
+                        open_set.push(Node::<Heuristic> {
+                            indices: new_indices,
+                            weight: node.weight + package,
+                            entanglement: node.entanglement * *package as Entanglement,
+                            phantom: PhantomData
+                        });
                     }
                 }
             }
         }
-        else {
-            break
-        }
     }
 
     None
@@ -92,24 +88,46 @@ fn smallest_distrib<Heuristic>(pool: &Packages, size: u32) -> Option<Packages>
 
 fn entanglement<Heuristic>(packages: &Packages, group_count: u32) -> Entanglement
     where Node<Heuristic>: Ord {
-    let package_weight = packages.iter().fold(0, |a, x| a + x) / group_count;
+    let package_weight = packages.iter().sum::<u32>() / group_count;
 
-    smallest_distrib::<Heuristic>(&packages, package_weight)
+    smallest_distrib::<Heuristic>(packages, package_weight)
         .map_or(0, |ds| quantum_entanglement(&ds))
 }
 
-pub fn p1(input: &str) -> Entanglement {
-    let packages = input.trim().split('\n')
-                               .map(|s| s.parse::<Package>().unwrap())
-                               .collect::<Packages>();
+pub fn parse_input(input: &str) -> ParseResult<Packages> {
+    input.lines()
+         .map(|s| parse::field(input, s, "a package weight"))
+         .collect()
+}
+
+pub fn p1(packages: &Packages) -> Entanglement {
+    entanglement::<WeightFirst>(packages, 3)
+}
 
-    entanglement::<WeightFirst>(&packages, 3)
+pub fn p2(packages: &Packages) -> Entanglement {
+    entanglement::<LenFirst>(packages, 4)
 }
 
-pub fn p2(input: &str) -> Entanglement {
-    let packages = input.trim().split('\n')
-                               .map(|s| s.parse::<Package>().unwrap())
-                               .collect::<Packages>();
+pub struct Day24;
+
+impl Solution for Day24 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 24;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day24.txt");
+
+    type Parsed<'a> = Packages;
+    type Part1 = Entanglement;
+    type Part2 = Entanglement;
 
-    entanglement::<LenFirst>(&packages, 4)
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
 }
@@ -1,44 +1,63 @@
 extern crate nom;
 
+use aoc_common::{Solution, ParseResult};
+use nom_parse;
+
 fn get_seq_number(row: u32, col: u32) -> u32 {
-    (1..=col+row-1).fold(0, |acc, x| acc + x) - row + 1
+    (1..=col+row-1).sum::<u32>() - row + 1
 }
 
-fn parse_row_col(input: &str) -> (u32, u32) {
+pub fn parse_input(input: &str) -> ParseResult<(u32, u32)> {
     use self::nom::*;
 
-    use std::str::from_utf8;
+    use nom_parse::{Input, utf8};
 
-    named!(number<u32>, map!(
-        digit,
-        |s| from_utf8(s).unwrap().parse::<u32>().unwrap()
+    named!(number<Input, u32>, map_res!(
+        map_res!(digit, utf8),
+        str::parse::<u32>
     ));
 
-    named!(coordinates<(u32, u32)>, do_parse!(
-        tag_s!("To continue, please consult the code grid in the manual.  Enter the code at row ") >>
+    named!(coordinates<Input, (u32, u32)>, do_parse!(
+        tag!("To continue, please consult the code grid in the manual.  Enter the code at row ") >>
         row: number         >>
-        tag_s!(", column ") >>
+        tag!(", column ") >>
         col: number         >>
-        tag_s!(".")         >>
+        tag!(".")         >>
         ((row, col))
     ));
 
-    match coordinates(input.as_bytes()) {
-        IResult::Done(_, coords) => coords,
-        _                        => panic!("Wrong manual format")
-    }
+    nom_parse::complete(input, input.trim_end(), coordinates, "the code grid coordinates")
 }
 
 fn code_at(index: u32) -> u64 {
     (2..=index).fold(20151125, |acc, _| (acc * 252533) % 33554393)
 }
 
-pub fn p1(input: &str) -> u64 {
-    let (row, col) = parse_row_col(input.trim());
-
+pub fn p1(&(row, col): &(u32, u32)) -> u64 {
     code_at(get_seq_number(row, col))
 }
 
-pub fn p2(input: &str) -> u64 {
-    42
+pub struct Day25;
+
+impl Solution for Day25 {
+    const YEAR: u32 = 2015;
+    const DAY: u32 = 25;
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day25.txt");
+    const HAS_PART2: bool = false;
+
+    type Parsed<'a> = (u32, u32);
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(_input: &Self::Parsed<'_>) -> Self::Part2 {
+        unreachable!("Day 25 has no second part")
+    }
 }
@@ -0,0 +1,61 @@
+extern crate aoc_common;
+
+use aoc_common::Day;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+mod nom_parse;
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::new::<day01::Day01>(),
+        Day::new::<day02::Day02>(),
+        Day::new::<day03::Day03>(),
+        Day::new::<day04::Day04>(),
+        Day::new::<day05::Day05>(),
+        Day::new::<day06::Day06>(),
+        Day::new::<day07::Day07>(),
+        Day::new::<day08::Day08>(),
+        Day::new::<day09::Day09>(),
+        Day::new::<day10::Day10>(),
+        Day::new::<day11::Day11>(),
+        Day::new::<day12::Day12>(),
+        Day::new::<day13::Day13>(),
+        Day::new::<day14::Day14>(),
+        Day::new::<day15::Day15>(),
+        Day::new::<day16::Day16>(),
+        Day::new::<day17::Day17>(),
+        Day::new::<day18::Day18>(),
+        Day::new::<day19::Day19>(),
+        Day::new::<day20::Day20>(),
+        Day::new::<day21::Day21>(),
+        Day::new::<day22::Day22>(),
+        Day::new::<day23::Day23>(),
+        Day::new::<day24::Day24>(),
+        Day::new::<day25::Day25>(),
+    ]
+}
@@ -0,0 +1,32 @@
+extern crate nom;
+
+use self::nom::{Context, Err, IResult};
+use self::nom::types::CompleteByteSlice;
+use std::str::{self, Utf8Error};
+use aoc_common::{ParseError, ParseResult};
+
+// Lines are parsed whole, so running out of input is an error rather than a request for more
+pub type Input<'a> = CompleteByteSlice<'a>;
+
+pub fn utf8<'a>(bytes: Input<'a>) -> Result<&'a str, Utf8Error> {
+    str::from_utf8(bytes.0)
+}
+
+// Runs a nom parser that must consume all of `fragment`, usually a line of `input`
+pub fn complete<'a, T>(
+    input: &'a str,
+    fragment: &'a str,
+    parser: fn(Input<'a>) -> IResult<Input<'a>, T>,
+    expected: &str
+) -> ParseResult<T> {
+    match parser(CompleteByteSlice(fragment.as_bytes())) {
+        Ok((rest, _)) if !rest.is_empty() => {
+            Err(ParseError::at(input.as_bytes(), rest.0, "the end of the line"))
+        },
+        Ok((_, parsed)) => Ok(parsed),
+        Err(Err::Error(Context::Code(rest, _))) | Err(Err::Failure(Context::Code(rest, _))) => {
+            Err(ParseError::at(input.as_bytes(), rest.0, expected))
+        },
+        Err(_) => Err(ParseError::at(input, fragment, expected)),
+    }
+}
@@ -1,14 +1,15 @@
 #[macro_use]
 extern crate criterion;
 
-use criterion::{Criterion, Fun};
+use criterion::Criterion;
+use aoc_common::bench::bench_scaling;
 
 fn day01(c: &mut Criterion) {
     use aoc_2018::day01;
 
     const GLOBI_INPUT: &str = include_str!("../../inputs/day01.txt");
 
-    let input: Vec<_> = day01::parse_input(GLOBI_INPUT).collect();
+    let input: Vec<_> = day01::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day01 p1", move |b| b.iter(|| day01::part1(&input)));
 }
 
@@ -20,14 +21,13 @@ fn day01_2(c: &mut Criterion) {
     use std::collections::{HashSet, BTreeSet};
     use hashbrown::HashSet as SwissTable;
 
-    let p2_funs = vec![
-        Fun::new("HashSet",    |b, i: &Vec<_>| b.iter(|| day01::part2_impl(i.as_slice(), HashSet::new()))),
-        Fun::new("BTreeSet",   |b, i: &Vec<_>| b.iter(|| day01::part2_impl(i.as_slice(), BTreeSet::new()))),
-        Fun::new("SwissTable", |b, i: &Vec<_>| b.iter(|| day01::part2_impl(i.as_slice(), SwissTable::new()))),
-    ];
+    let input: Vec<_> = day01::parse_input(GLOBI_INPUT).expect("Invalid input");
 
-    let input: Vec<_> = day01::parse_input(GLOBI_INPUT).collect();
-    c.bench_functions("day01 p2", p2_funs, input);
+    let mut group = c.benchmark_group("day01 p2");
+    group.bench_function("HashSet",    |b| b.iter(|| day01::part2_impl(&input, HashSet::new())));
+    group.bench_function("BTreeSet",   |b| b.iter(|| day01::part2_impl(&input, BTreeSet::new())));
+    group.bench_function("SwissTable", |b| b.iter(|| day01::part2_impl(&input, SwissTable::new())));
+    group.finish();
 }
 
 fn day02(c: &mut Criterion) {
@@ -35,10 +35,10 @@ fn day02(c: &mut Criterion) {
 
     const GLOBI_INPUT: &[u8] = include_bytes!("../../inputs/day02.txt");
 
-    let input: Vec<_> = day02::parse_input(GLOBI_INPUT).collect();
+    let input: Vec<_> = day02::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day02 p1", move |b| b.iter(|| day02::part1(&input)));
 
-    let input: Vec<_> = day02::parse_input(GLOBI_INPUT).collect();
+    let input: Vec<_> = day02::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day02 p2", move |b| b.iter(|| day02::part2_set(&input)));
 }
 
@@ -47,10 +47,10 @@ fn day03(c: &mut Criterion) {
 
     const GLOBI_INPUT: &str = include_str!("../../inputs/day03.txt");
 
-    let input: Vec<_> = day03::parse_input(GLOBI_INPUT).collect();
+    let input: Vec<_> = day03::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day03 p1", move |b| b.iter(|| day03::part1(&input)));
 
-    let input: Vec<_> = day03::parse_input(GLOBI_INPUT).collect();
+    let input: Vec<_> = day03::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day03 p2", move |b| b.iter(|| day03::part2(&input)));
 }
 
@@ -59,10 +59,10 @@ fn day04(c: &mut Criterion) {
 
     const GLOBI_INPUT: &[u8] = include_bytes!("../../inputs/day04.txt");
 
-    let input = day04::parse_input(GLOBI_INPUT);
+    let input = day04::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day04 p1", move |b| b.iter(|| day04::part1(&input)));
 
-    let input = day04::parse_input(GLOBI_INPUT);
+    let input = day04::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day04 p2", move |b| b.iter(|| day04::part2(&input)));
 }
 
@@ -80,9 +80,9 @@ fn day06(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day06.txt");
 
-    let coords: Vec<_> = day06::parse_coordinates(GLOBI_INPUT_STR).collect();
+    let coords: Vec<_> = day06::parse_coordinates(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day06 p1", move |b| b.iter(|| day06::part1(&coords)));
-    let coords: Vec<_> = day06::parse_coordinates(GLOBI_INPUT_STR).collect();
+    let coords: Vec<_> = day06::parse_coordinates(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day06 p2", move |b| b.iter(|| day06::part2(&coords)));
 }
 
@@ -91,9 +91,9 @@ fn day07(c: &mut Criterion) {
 
     const GLOBI_INPUT: &[u8] = include_bytes!("../../inputs/day07.txt");
 
-    let coords: Vec<_> = day07::parse_relations(GLOBI_INPUT).collect();
+    let coords: Vec<_> = day07::parse_relations(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day07 p1", move |b| b.iter(|| day07::part1(coords.iter().cloned())));
-    let coords: Vec<_> = day07::parse_relations(GLOBI_INPUT).collect();
+    let coords: Vec<_> = day07::parse_relations(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day07 p2", move |b| b.iter(|| day07::part2(coords.iter().cloned())));
 }
 
@@ -102,9 +102,9 @@ fn day08(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day08.txt");
 
-    let data: Vec<_> = day08::parse_input(GLOBI_INPUT_STR).collect();
+    let data = day08::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day08 p1", move |b| b.iter(|| day08::part1(&data)));
-    let data: Vec<_> = day08::parse_input(GLOBI_INPUT_STR).collect();
+    let data = day08::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day08 p2", move |b| b.iter(|| day08::part2(&data)));
 }
 
@@ -113,7 +113,7 @@ fn day09(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day09.txt");
 
-    let config = day09::parse_input(GLOBI_INPUT_STR);
+    let config = day09::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day09 p1", move |b| b.iter(|| day09::part1(&config)));
 }
 
@@ -122,7 +122,7 @@ fn day09_2(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day09.txt");
 
-    let config = day09::parse_input(GLOBI_INPUT_STR);
+    let config = day09::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day09 p2", move |b| b.iter(|| day09::part2(&config)));
 }
 
@@ -131,10 +131,10 @@ fn day10(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day10.txt");
 
-    let points: Vec<_> = day10::parse_input(GLOBI_INPUT_STR).collect();
+    let points: Vec<_> = day10::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day10 p1", move |b| b.iter(|| day10::part1(&points)));
 
-    let points: Vec<_> = day10::parse_input(GLOBI_INPUT_STR).collect();
+    let points: Vec<_> = day10::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day10 p2", move |b| b.iter(|| day10::part2(&points)));
 }
 
@@ -159,10 +159,10 @@ fn day12(c: &mut Criterion) {
 
     const GLOBI_INPUT: &[u8] = include_bytes!("../../inputs/day12.txt");
 
-    let (state, rules) = day12::parse_input(GLOBI_INPUT);
+    let (state, rules) = day12::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day12 p1", move |b| b.iter(|| day12::part1(&state, &rules)));
 
-    let (state, rules) = day12::parse_input(GLOBI_INPUT);
+    let (state, rules) = day12::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day12 p2", move |b| b.iter(|| day12::part2(&state, &rules)));
 }
 
@@ -171,10 +171,10 @@ fn day13(c: &mut Criterion) {
 
     const GLOBI_INPUT: &[u8] = include_bytes!("../../inputs/day13.txt");
 
-    let (world, trains) = day13::parse_input(GLOBI_INPUT);
+    let (world, trains) = day13::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day13 p1", move |b| b.iter(|| day13::part1(&world, &trains)));
 
-    let (world, trains) = day13::parse_input(GLOBI_INPUT);
+    let (world, trains) = day13::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day13 p2", move |b| b.iter(|| day13::part2(&world, &trains)));
 }
 
@@ -193,9 +193,9 @@ fn day15(c: &mut Criterion) {
 
     const GLOBI_INPUT: &[u8] = include_bytes!("../../inputs/day15.txt");
 
-    let world = day15::parse_input(GLOBI_INPUT);
+    let world = day15::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day15 p1", move |b| b.iter(|| day15::part1(&world)));
-    let world = day15::parse_input(GLOBI_INPUT);
+    let world = day15::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day15 p2", move |b| b.iter(|| day15::part2(&world)));
 }
 
@@ -204,18 +204,16 @@ fn day16(c: &mut Criterion) {
 
     const GLOBI_INPUT: &[u8] = include_bytes!("../../inputs/day16.txt");
 
-    let (samples, _) = day16::parse_input(GLOBI_INPUT);
+    let (samples, _) = day16::parse_input(GLOBI_INPUT).expect("Invalid input");
     let packed = day16::vectorize(&samples);
     // c.bench_function("day16 p1", move |b| b.iter(|| day16::part1_vectorized(&samples)));
 
-    let p1_funs = vec![
-        Fun::new("Normal",     |b, (samples, _): &(Vec<_>, _)| b.iter(|| day16::part1(samples))),
-        Fun::new("Vectorized", |b, (_,  packed): &(Vec<_>, _)| b.iter(|| day16::part1_vectorized(packed))),
-    ];
+    let mut group = c.benchmark_group("day16 p1");
+    group.bench_function("Normal",     |b| b.iter(|| day16::part1(&samples)));
+    group.bench_function("Vectorized", |b| b.iter(|| day16::part1_vectorized(&packed)));
+    group.finish();
 
-    c.bench_functions("day16 p1", p1_funs, (samples, packed));
-
-    let (samples, program) = day16::parse_input(GLOBI_INPUT);
+    let (samples, program) = day16::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day16 p2", move |b| b.iter(|| day16::part2(&samples, &program)));
 }
 
@@ -224,9 +222,9 @@ fn day17(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day17.txt");
 
-    let ranges: Vec<_> = day17::parse_input(GLOBI_INPUT_STR).collect();
+    let ranges: Vec<_> = day17::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day17 p1", move |b| b.iter(|| day17::part1(&ranges)));
-    let ranges: Vec<_> = day17::parse_input(GLOBI_INPUT_STR).collect();
+    let ranges: Vec<_> = day17::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day17 p2", move |b| b.iter(|| day17::part2(&ranges)));
 }
 
@@ -235,9 +233,9 @@ fn day18(c: &mut Criterion) {
 
     const GLOBI_INPUT: &[u8] = include_bytes!("../../inputs/day18.txt");
 
-    let world = day18::parse_input(GLOBI_INPUT);
+    let world = day18::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day18 p1", move |b| b.iter(|| day18::part1(&world)));
-    let world = day18::parse_input(GLOBI_INPUT);
+    let world = day18::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day18 p2", move |b| b.iter(|| day18::part2(&world)));
 }
 
@@ -246,9 +244,9 @@ fn day19(c: &mut Criterion) {
 
     const GLOBI_INPUT: &[u8] = include_bytes!("../../inputs/day19.txt");
 
-    let (pc_idx, instrs) = day19::parse_input(GLOBI_INPUT);
+    let (pc_idx, instrs) = day19::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day19 p1", move |b| b.iter(|| day19::part1(pc_idx, &instrs)));
-    let (pc_idx, instrs) = day19::parse_input(GLOBI_INPUT);
+    let (pc_idx, instrs) = day19::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day19 p2", move |b| b.iter(|| day19::part2(pc_idx, &instrs)));
 }
 
@@ -257,9 +255,9 @@ fn day20(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day20.txt");
 
-    let directions = day20::parse_input(GLOBI_INPUT_STR);
+    let directions = day20::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day20 p1", move |b| b.iter(|| day20::part1(&directions)));
-    let directions = day20::parse_input(GLOBI_INPUT_STR);
+    let directions = day20::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day20 p2", move |b| b.iter(|| day20::part2(&directions)));
 }
 
@@ -268,9 +266,9 @@ fn day21(c: &mut Criterion) {
 
     const GLOBI_INPUT: &[u8] = include_bytes!("../../inputs/day21.txt");
 
-    let (pc_idx, instrs) = day21::parse_input(GLOBI_INPUT);
+    let (pc_idx, instrs) = day21::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day21 p1", move |b| b.iter(|| day21::part1(pc_idx, &instrs)));
-    let (pc_idx, instrs) = day21::parse_input(GLOBI_INPUT);
+    let (pc_idx, instrs) = day21::parse_input(GLOBI_INPUT).expect("Invalid input");
     c.bench_function("day21 p2", move |b| b.iter(|| day21::part2(pc_idx, &instrs)));
 }
 
@@ -279,9 +277,9 @@ fn day22(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day22.txt");
 
-    let (depth, target) = day22::parse_input(GLOBI_INPUT_STR);
+    let (depth, target) = day22::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day22 p1", move |b| b.iter(|| day22::part1(depth, target)));
-    let (depth, target) = day22::parse_input(GLOBI_INPUT_STR);
+    let (depth, target) = day22::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day22 p2", move |b| b.iter(|| day22::part2(depth, target)));
 }
 
@@ -290,9 +288,9 @@ fn day23(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day23.txt");
 
-    let nanobots: Vec<_> = day23::parse_input(GLOBI_INPUT_STR).collect();
+    let nanobots: Vec<_> = day23::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day23 p1", move |b| b.iter(|| day23::part1(&nanobots)));
-    let nanobots: Vec<_> = day23::parse_input(GLOBI_INPUT_STR).collect();
+    let nanobots: Vec<_> = day23::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day23 p2", move |b| b.iter(|| day23::part2(&nanobots)));
 }
 
@@ -301,9 +299,9 @@ fn day24(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day24.txt");
 
-    let groups: Vec<_> = day24::parse_input(GLOBI_INPUT_STR).collect();
+    let groups: Vec<_> = day24::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day24 p1", move |b| b.iter(|| day24::part1(&groups)));
-    let groups: Vec<_> = day24::parse_input(GLOBI_INPUT_STR).collect();
+    let groups: Vec<_> = day24::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day24 p2", move |b| b.iter(|| day24::part2(&groups)));
 }
 
@@ -312,7 +310,7 @@ fn day25(c: &mut Criterion) {
 
     const GLOBI_INPUT_STR: &str = include_str!("../../inputs/day25.txt");
 
-    let points: Vec<_> = day25::parse_input(GLOBI_INPUT_STR).collect();
+    let points: Vec<_> = day25::parse_input(GLOBI_INPUT_STR).expect("Invalid input");
     c.bench_function("day25 p1", move |b| b.iter(|| day25::part1(&points)));
 }
 
@@ -327,4 +325,8 @@ criterion_group!{
     targets = day01_2, day09_2, day11_2, day14, day15, day18, day19, day20,
     day21, day22, day24, day25
 }
-criterion_main!(benches, slower_benches);
+fn day05_scaling(c: &mut Criterion) { bench_scaling::<aoc_2018::day05::Day05>(c) }
+fn day09_scaling(c: &mut Criterion) { bench_scaling::<aoc_2018::day09::Day09>(c) }
+
+criterion_group!(scaling_benches, day05_scaling, day09_scaling);
+criterion_main!(benches, slower_benches, scaling_benches);
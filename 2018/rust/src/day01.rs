@@ -1,15 +1,34 @@
 use std::collections::{HashSet, BTreeSet};
 use std::hash::Hash;
 use std::mem;
+use aoc_common::{Solution, ParseResult, parse};
 
 use hashbrown::HashSet as SwissTable;
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day01.txt");
 
-pub fn day01() -> (i32, i32) {
-    let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+pub struct Day01;
 
-    (part1(&input), part2(&input))
+impl Solution for Day01 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 1;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(input: &[i32]) -> i32 {
@@ -32,9 +51,10 @@ fn looped_frequencies(input: &[i32]) -> impl Iterator<Item = i32> + '_ {
         .scan(0, |freq, delta| Some(mem::replace(freq, *freq + delta)))
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = i32> + '_ {
+pub fn parse_input(input: &str) -> ParseResult<Vec<i32>> {
     input.lines()
-        .map(|s| s.parse().expect("Badly formatted number in the input"))
+        .map(|s| parse::field(input, s, "a frequency change"))
+        .collect()
 }
 
 pub trait Set<T> {
@@ -58,14 +78,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&input), 442);
     }
 
     #[test]
     fn p2() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&input), 59908);
     }
@@ -1,12 +1,32 @@
-const RAW_INPUT: &[u8] = include_bytes!("../../inputs/day02.txt");
+use aoc_common::{Solution, ParseError, ParseResult};
+
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day02.txt");
 
 const WORD_LEN: usize = 26;
 const WORD_COUNT: usize = 250;
 
-pub fn day02() -> (usize, String) {
-    let input: Vec<_> = parse_input(RAW_INPUT).collect();
+pub struct Day02;
+
+impl Solution for Day02 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 2;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<&'a [u8]>;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input.as_bytes())
+    }
 
-    (part1(&input), part2_set(&input))
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2_set(input)
+    }
 }
 
 pub fn part1(input: &[&[u8]]) -> usize {
@@ -77,8 +97,16 @@ fn box_property(r#box: &[u8]) -> BoxProperty {
         })
 }
 
-pub fn parse_input(input: &[u8]) -> impl Iterator<Item = &[u8]> {
-    input.split(|&c| c == b'\n')
+pub fn parse_input(input: &[u8]) -> ParseResult<Vec<&[u8]>> {
+    input.strip_suffix(b"\n")
+        .unwrap_or(input)
+        .split(|&c| c == b'\n')
+        .map(|id| match id.iter().position(|c| !c.is_ascii_lowercase()) {
+            Some(idx) => Err(ParseError::at(input, &id[idx..], "a lowercase letter")),
+            None if id.len() != WORD_LEN => Err(ParseError::after(input, id, format!("a box ID of {} letters", WORD_LEN))),
+            None => Ok(id),
+        })
+        .collect()
 }
 
 #[derive(Default)]
@@ -92,14 +120,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input: Vec<_> = parse_input(RAW_INPUT).collect();
+        let input = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part1(&input), 9139);
     }
 
     #[test]
     fn p2() {
-        let input: Vec<_> = parse_input(RAW_INPUT).collect();
+        let input = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part2_set(&input), String::from("uqcidadzwtnhsljvxyobmkfyr"));
     }
@@ -1,13 +1,32 @@
-use packed_simd::u16x32;
+use crate::simd::u16x32;
+use aoc_common::{Solution, ParseError, ParseResult, parse};
 
 use std::cmp::min;
 
 const RAW_INPUT: &str = include_str!("../../inputs/day03.txt");
 
-pub fn day03() -> (usize, u16) {
-    let input: Vec<_> = parse_input(RAW_INPUT).collect();
+pub struct Day03;
 
-    (part1(&input), part2(&input))
+impl Solution for Day03 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 3;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT;
+
+    type Parsed<'a> = Vec<Claim>;
+    type Part1 = usize;
+    type Part2 = u16;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(claims: &[Claim]) -> usize {
@@ -61,24 +80,30 @@ pub fn part2(claims: &[Claim]) -> u16 {
     panic!("No solution")
 }
 
-pub fn parse_input(input: &'static str) -> impl Iterator<Item = Claim> {
+pub fn parse_input(input: &str) -> ParseResult<Vec<Claim>> {
     input.lines().map(|line| {
         let mut splitted = line.split('@');
-        let id = splitted.next().expect("missing id")[1..].trim();
-        let raw_claim = splitted.next().expect("missing claim");
+        let raw_id = parse::next(input, &mut splitted, line, "a claim ID")?;
+        let id = raw_id.strip_prefix('#')
+            .ok_or_else(|| ParseError::at(input, raw_id, "`#`"))?
+            .trim();
+        let raw_claim = parse::next(input, &mut splitted, line, "`@` and a claim")?;
 
         let mut splitted = raw_claim.split(':');
-        let mut pos = splitted.next().expect("missing pos").trim().split(',');
-        let mut size = splitted.next().expect("missing size").trim().split('x');
-
-        Claim {
-            id: id.parse().expect("invalid ID"),
-            left: pos.next().expect("missing left").parse().expect("invalid left"),
-            top: pos.next().expect("missing top").parse().expect("invalid top"),
-            width: size.next().expect("missing width").parse().expect("invalid width"),
-            height: size.next().expect("missing height").parse().expect("invalid height"),
-        }
+        let raw_pos = parse::next(input, &mut splitted, raw_claim, "a position")?.trim();
+        let raw_size = parse::next(input, &mut splitted, raw_claim, "`:` and a size")?.trim();
+        let mut pos = raw_pos.split(',');
+        let mut size = raw_size.split('x');
+
+        Ok(Claim {
+            id: parse::field(input, id, "a claim ID")?,
+            left: parse::next_field(input, &mut pos, raw_pos, "a left offset")?,
+            top: parse::next_field(input, &mut pos, raw_pos, "`,` and a top offset")?,
+            width: parse::next_field(input, &mut size, raw_size, "a width")?,
+            height: parse::next_field(input, &mut size, raw_size, "`x` and a height")?,
+        })
     })
+    .collect()
 }
 
 #[derive(Debug)]
@@ -94,7 +119,7 @@ fn cloth_masks() -> Vec<u16x32> {
     (0..32)
         .map(|stop| {
             let mut slice = [0; 32];
-            for i in 0..stop + 1 { slice[i] = 1 }
+            for cell in &mut slice[..=stop] { *cell = 1 }
             u16x32::from_slice_unaligned(&slice)
         })
         .collect()
@@ -140,14 +165,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input: Vec<_> = parse_input(RAW_INPUT).collect();
+        let input = parse_input(RAW_INPUT).unwrap();
 
         assert_eq!(part1(&input), 110891);
     }
 
     #[test]
     fn p2() {
-        let input: Vec<_> = parse_input(RAW_INPUT).collect();
+        let input = parse_input(RAW_INPUT).unwrap();
 
         assert_eq!(part2(&input), 297);
     }
@@ -1,11 +1,31 @@
-const RAW_INPUT: &[u8] = include_bytes!("../../inputs/day04.txt");
+use aoc_common::{Solution, ParseError, ParseResult};
+
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day04.txt");
 
 use hashbrown::HashMap;
 
-pub fn day04() -> (u32, u32) {
-    let logs = parse_input(RAW_INPUT);
+pub struct Day04;
+
+impl Solution for Day04 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 4;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<LogEntry>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-    (part1(&logs), part2(&logs))
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(entries: &[LogEntry]) -> u32 {
@@ -13,7 +33,7 @@ pub fn part1(entries: &[LogEntry]) -> u32 {
         guard_id: GuardID,
         most_slept_minute: usize,
         total_slept: u32,
-    };
+    }
 
     let sleep_schedules = process_entries(entries);
 
@@ -41,7 +61,7 @@ pub fn part2(entries: &[LogEntry]) -> u32 {
         guard_id: GuardID,
         most_slept_minute: usize,
         slept_for_minute: u32,
-    };
+    }
 
     let sleep_schedules = process_entries(entries);
 
@@ -78,7 +98,7 @@ fn process_entries(entries: &[LogEntry]) -> SleepSchedules {
 
     let mut shift_data = HashMap::with_capacity(64);
 
-    while let Some(LogEntry { event, date_time }) = entries.next() {
+    for LogEntry { event, date_time } in entries {
         match event {
             Event::StartsShift(guard_id) => current_guard_id = *guard_id,
             Event::FallsAsleep => last_fell_asleep = date_time.minute,
@@ -101,23 +121,24 @@ type SleepSchedules = HashMap<GuardID, SleepPerMinute>;
 type GuardID = u32;
 type SleepPerMinute = [u32; 60];
 
-pub fn parse_input(input: &[u8]) -> Vec<LogEntry> {
-    let mut entries = input.split(|&c| c == b'\n')
-        .map(parse_log_entry)
-        .collect::<Vec<_>>();
+pub fn parse_input(input: &[u8]) -> ParseResult<Vec<LogEntry>> {
+    let mut entries = input.strip_suffix(b"\n")
+        .unwrap_or(input)
+        .split(|&c| c == b'\n')
+        .map(|raw_entry| parse_log_entry(input, raw_entry))
+        .collect::<ParseResult<Vec<_>>>()?;
 
     entries.sort_by(|log1, log2| log1.date_time.cmp(&log2.date_time));
 
-    entries
+    Ok(entries)
 }
 
-fn parse_log_entry(raw_entry: &[u8]) -> LogEntry {
+fn parse_log_entry(input: &[u8], raw_entry: &[u8]) -> ParseResult<LogEntry> {
     use nom::{*, types::CompleteByteSlice as Input};
-    use std::str::{FromStr, from_utf8};
 
-    named!(parse_u32<Input, u32>, map!(
+    named!(parse_u32<Input, u32>, map_res!(
         digit,
-        |d| FromStr::from_str(from_utf8(&d).unwrap()).unwrap()
+        |d: Input| String::from_utf8_lossy(&d).parse()
     ));
 
     named!(parse_shift<Input, Event>, delimited!(
@@ -151,9 +172,13 @@ fn parse_log_entry(raw_entry: &[u8]) -> LogEntry {
         (LogEntry { date_time, event })
     ));
 
+    const EXPECTED: &str = "a log entry (`[YYYY-MM-DD hh:mm] event`)";
+
     match parse_entry(Input(raw_entry)) {
-        Ok((_remaining, parsed)) => parsed,
-        _ => panic!("Bad entry format: {:?}", raw_entry)
+        Ok((_remaining, parsed)) => Ok(parsed),
+        Err(Err::Error(Context::Code(remaining, _))) | Err(Err::Failure(Context::Code(remaining, _))) =>
+            Err(ParseError::at(input, remaining.0, EXPECTED)),
+        Err(_) => Err(ParseError::at(input, raw_entry, EXPECTED)),
     }
 }
 
@@ -184,14 +209,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input = parse_input(RAW_INPUT);
+        let input = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part1(&input), 106710);
     }
 
     #[test]
     fn p2() {
-        let input = parse_input(RAW_INPUT);
+        let input = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part2(&input), 10491);
     }
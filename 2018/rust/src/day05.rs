@@ -1,10 +1,57 @@
 use rayon::prelude::*;
-use arrayvec::ArrayVec;
+use aoc_common::{Solution, Generate, ParseError, ParseResult};
+use aoc_common::generate::Rng;
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day05.txt");
 
-pub fn day05() -> (usize, usize) {
-    (part1(RAW_INPUT_STR), part2(RAW_INPUT_STR))
+pub struct Day05;
+
+impl Solution for Day05 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 5;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = &'a str;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+impl Generate for Day05 {
+    const SIZES: &'static [usize] = &[1_000, 10_000, 100_000, 1_000_000];
+
+    // Like in the real inputs, units often react with the ones before them and
+    // nest reactions that only play out as the polymer collapses, to about a fifth
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut polymer = Vec::with_capacity(size);
+        let mut unmatched = Vec::new();
+
+        while polymer.len() < size {
+            let unit = match unmatched.pop() {
+                Some(opposite) if rng.chance(2, 5) => opposite,
+                previous => {
+                    unmatched.extend(previous);
+                    let unit = rng.between(b'a' as u64, b'z' as u64) as u8 ^ *rng.pick(&[0, 32]);
+                    unmatched.push(unit ^ 32);
+                    unit
+                },
+            };
+            polymer.push(unit);
+        }
+
+        String::from_utf8(polymer).expect("Units are ASCII letters")
+    }
 }
 
 pub fn part1(input: &str) -> usize {
@@ -26,8 +73,8 @@ pub fn part2(input: &str) -> usize {
         .expect("Empty polymer")
 }
 
-fn react(polymer: impl Iterator<Item = u8>) -> ArrayVec<[u8; 16384]> {
-    let mut reduced = ArrayVec::<[u8; 16384]>::new();
+fn react(polymer: impl Iterator<Item = u8>) -> Vec<u8> {
+    let mut reduced = Vec::with_capacity(polymer.size_hint().0);
 
     for byte in polymer {
         let top_is_opposite = reduced.last()
@@ -44,16 +91,43 @@ fn react(polymer: impl Iterator<Item = u8>) -> ArrayVec<[u8; 16384]> {
     reduced
 }
 
+pub fn parse_input(input: &str) -> ParseResult<&str> {
+    let polymer = input.trim_end();
+
+    if let Some(offset) = polymer.bytes().position(|unit| !unit.is_ascii_alphabetic()) {
+        return Err(ParseError::new(input, offset, "a polymer unit (an ASCII letter)"));
+    }
+
+    Ok(polymer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        assert_eq!(part1(RAW_INPUT_STR), 10978);
+        let polymer = parse_input(RAW_INPUT_STR).unwrap();
+
+        assert_eq!(part1(polymer), 10978);
     }
 
     #[test]
     fn p2() {
-        assert_eq!(part2(RAW_INPUT_STR), 4840);
+        let polymer = parse_input(RAW_INPUT_STR).unwrap();
+
+        assert_eq!(part2(polymer), 4840);
+    }
+
+    #[test]
+    fn generated() {
+        let mut rng = Rng::new(aoc_common::generate::SEED);
+
+        for &size in Day05::SIZES {
+            let polymer = Day05::generate(&mut rng, size);
+            let reacted = part1(parse_input(&polymer).unwrap());
+
+            assert_eq!(polymer.len(), size);
+            assert!(reacted > 0 && reacted < size / 2, "{} units left out of {}", reacted, size);
+        }
     }
 }
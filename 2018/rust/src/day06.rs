@@ -1,29 +1,49 @@
 use rayon::prelude::*;
+use aoc_common::{Solution, ParseError, ParseResult, parse};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day06.txt");
 
-pub fn day06() -> (u32, u32) {
-    let coords: Vec<_> = parse_coordinates(RAW_INPUT_STR).collect();
+pub struct Day06;
 
-    (part1(&coords), part2(&coords))
+impl Solution for Day06 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 6;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Coordinate>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_coordinates(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(coords: &[Coordinate]) -> u32 {
     const LANES: usize = i16x32::lanes();
 
-    let padding = (LANES - (coords.len() % LANES)) % LANES;
+    // Padded to two vectors, far away from the real coordinates
+    let padding = MAX_COORDINATES - coords.len();
     let xs: Vec<_> = coords.iter().map(|c| c.x)
-        .chain(std::iter::repeat(i16::max_value() / 2).take(padding))
+        .chain(std::iter::repeat_n(i16::MAX / 2, padding))
         .collect();
     let ys: Vec<_> = coords.iter().map(|c| c.y)
-        .chain(std::iter::repeat(i16::max_value() / 2).take(padding))
+        .chain(std::iter::repeat_n(i16::MAX / 2, padding))
         .collect();
 
-    // Making a pretty balsy assumption here that the input will be <= 64
+    // The parser makes sure that there are no more than two vectors' worth of coordinates.
     // This wouldn't be necessary with a bit more work in `closest_to`
-    let p1_xs_1 = i16x32::from_slice_unaligned(&xs[0..0+LANES]);
+    let p1_xs_1 = i16x32::from_slice_unaligned(&xs[0..LANES]);
     let p1_xs_2 = i16x32::from_slice_unaligned(&xs[LANES..LANES+LANES]);
-    let p1_ys_1 = i16x32::from_slice_unaligned(&ys[0..0+LANES]);
+    let p1_ys_1 = i16x32::from_slice_unaligned(&ys[0..LANES]);
     let p1_ys_2 = i16x32::from_slice_unaligned(&ys[LANES..LANES+LANES]);
 
     let idxs_1 = i16x32::new(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31);
@@ -118,15 +138,28 @@ pub fn part2(coords: &[Coordinate]) -> u32 {
         .sum()
 }
 
-pub fn parse_coordinates(input: &str) -> impl Iterator<Item = Coordinate> + '_ {
-    input.lines()
+// What `part1` can compare at once
+const MAX_COORDINATES: usize = 2 * i16x32::lanes();
+
+pub fn parse_coordinates(input: &str) -> ParseResult<Vec<Coordinate>> {
+    if let Some(line) = input.lines().nth(MAX_COORDINATES) {
+        return Err(ParseError::at(input, line, format!("at most {} coordinates", MAX_COORDINATES)));
+    }
+
+    let coords = input.lines()
         .map(|line| {
             let mut splitted = line.split(", ");
-            Coordinate {
-                x: splitted.next().expect("Missing x").parse().expect("Invalid x"),
-                y: splitted.next().expect("Missing y").parse().expect("Invalid y"),
-            }
+            Ok(Coordinate {
+                x: parse::next_field(input, &mut splitted, line, "an x coordinate")?,
+                y: parse::next_field(input, &mut splitted, line, "a y coordinate")?,
+            })
         })
+        .collect::<ParseResult<Vec<_>>>()?;
+
+    match coords.is_empty() {
+        true => Err(ParseError::end(input, "a coordinate")),
+        false => Ok(coords),
+    }
 }
 
 fn bounding_rect(coords: &[Coordinate]) -> (i16, i16, i16, i16) {
@@ -138,7 +171,7 @@ fn bounding_rect(coords: &[Coordinate]) -> (i16, i16, i16, i16) {
     (top, bottom, left, right)
 }
 
-use packed_simd::{i16x32};
+use crate::simd::{i16x32};
 
 fn manhattan_simd(p1_xs: i16x32, p1_ys: i16x32, p2_xs: i16x32, p2_ys: i16x32) -> i16x32 {
     abs_simd(p2_xs - p1_xs) + abs_simd(p2_ys - p1_ys)
@@ -152,6 +185,7 @@ fn abs_simd(vec: i16x32) -> i16x32 {
 const ZEROES: i16x32 = i16x32::splat(0);
 const ONES: i16x32 = i16x32::splat(1);
 
+#[derive(Debug)]
 pub struct Coordinate {
     x: i16,
     y: i16,
@@ -162,15 +196,25 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let coords: Vec<_> = parse_coordinates(RAW_INPUT_STR).collect();
+        let coords = parse_coordinates(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&coords), 3238);
     }
 
     #[test]
     fn p2() {
-        let coords: Vec<_> = parse_coordinates(RAW_INPUT_STR).collect();
+        let coords = parse_coordinates(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&coords), 45046);
     }
+
+    #[test]
+    fn sizes() {
+        let coords = parse_coordinates("1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9\n").unwrap();
+        assert_eq!(part1(&coords), 17);
+
+        let too_many = "1, 1\n".repeat(MAX_COORDINATES + 1);
+        assert_eq!(parse_coordinates(&too_many).unwrap_err().line, MAX_COORDINATES + 1);
+        assert!(parse_coordinates("").is_err());
+    }
 }
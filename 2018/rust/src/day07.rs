@@ -1,17 +1,33 @@
 use arrayvec::ArrayVec;
 use hashbrown::HashSet;
+use aoc_common::{Solution, ParseError, ParseResult};
 
-const RAW_INPUT: &[u8] = include_bytes!("../../inputs/day07.txt");
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day07.txt");
 
-pub fn day07() -> (String, u32) {
-    let relations: Vec<_> = parse_relations(RAW_INPUT).collect();
+pub struct Day07;
 
-    let (p1, p2) = (
-        part1(relations.iter().cloned()),
-        part2(relations.iter().cloned())
-    );
+impl Solution for Day07 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 7;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
 
-    (String::from_utf8(p1.to_vec()).unwrap(), p2)
+    type Parsed<'a> = Vec<Relation>;
+    type Part1 = String;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_relations(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        let chain = part1(input.iter().cloned());
+
+        String::from_utf8_lossy(&chain).into_owned()
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input.iter().cloned())
+    }
 }
 
 pub fn part1(relations: impl Iterator<Item = Relation>) -> Chain {
@@ -110,9 +126,19 @@ type Relation = (u8, u8);
 
 const STEP_COUNT: usize = 26;
 
-pub fn parse_relations(input: &[u8]) -> impl Iterator<Item = Relation> + '_ {
-    input.split(|&c| c == b'\n')
-        .map(|line| (line[5], line[36]))
+pub fn parse_relations(input: &[u8]) -> ParseResult<Vec<Relation>> {
+    input.strip_suffix(b"\n")
+        .unwrap_or(input)
+        .split(|&c| c == b'\n')
+        .map(|line| {
+            let step = |idx: usize, expected: &str| match line.get(idx) {
+                Some(step @ b'A'..=b'Z') => Ok(*step),
+                _ => Err(ParseError::at(input, &line[idx.min(line.len())..], expected)),
+            };
+
+            Ok((step(5, "a step letter")?, step(36, "a dependent step letter")?))
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -120,15 +146,15 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let relations = parse_relations(RAW_INPUT);
+        let relations = parse_relations(RAW_INPUT_STR.as_bytes()).unwrap();
 
-        assert_eq!(part1(relations).as_slice(), &b"JRHSBCKUTVWDQAIGYOPXMFNZEL"[..]);
+        assert_eq!(part1(relations.into_iter()).as_slice(), &b"JRHSBCKUTVWDQAIGYOPXMFNZEL"[..]);
     }
 
     #[test]
     fn p2() {
-        let relations = parse_relations(RAW_INPUT);
+        let relations = parse_relations(RAW_INPUT_STR.as_bytes()).unwrap();
 
-        assert_eq!(part2(relations), 975);
+        assert_eq!(part2(relations.into_iter()), 975);
     }
 }
@@ -1,14 +1,32 @@
+use aoc_common::{Solution, ParseError, ParseResult, parse};
+
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day08.txt");
 
-pub fn day08() -> (u32, u32) {
-    let data: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+pub struct Day08;
 
-    (part1(&data), part2(&data))
-}
+impl Solution for Day08 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 8;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Node;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
 
-pub fn part1(data: &[u32]) -> u32 {
-    let graph = build_graph(data);
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+pub fn part1(graph: &Node) -> u32 {
     fn sum_meta(Node { children, meta_data }: &Node) -> u32 {
         let self_sum: u32 = meta_data.iter().sum();
         let children_sum: u32 = children.iter()
@@ -18,12 +36,10 @@ pub fn part1(data: &[u32]) -> u32 {
         self_sum + children_sum
     }
 
-    sum_meta(&graph)
+    sum_meta(graph)
 }
 
-pub fn part2(data: &[u32]) -> u32 {
-    let graph = build_graph(data);
-
+pub fn part2(graph: &Node) -> u32 {
     fn sum_values(Node { children, meta_data }: &Node) -> u32 {
         if children.is_empty() {
             meta_data.iter().sum()
@@ -35,31 +51,42 @@ pub fn part2(data: &[u32]) -> u32 {
         }
     }
 
-    sum_values(&graph)
+    sum_values(graph)
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = u32> + '_ {
-    input.split(' ')
-        .map(|x| x.parse().expect("Badly formatted number"))
-}
+pub fn parse_input(input: &str) -> ParseResult<Node> {
+    let mut data = input.split_whitespace();
+    let graph = parse_nodes(input, &mut data)?;
 
-fn build_graph(data: &[u32]) -> Node {
-    parse_nodes(&mut data.iter().cloned())
+    match data.next() {
+        Some(extra) => Err(ParseError::at(input, extra, "the end of the tree")),
+        None => Ok(graph),
+    }
 }
 
 #[derive(Debug)]
-struct Node {
+pub struct Node {
     children: Vec<Node>,
     meta_data: Vec<u32>
 }
 
-fn parse_nodes(data: &mut impl Iterator<Item = u32>) -> Node {
-    let (child_len, meta_len) = (data.next().unwrap(), data.next().unwrap());
+fn parse_number<'a>(input: &str, data: &mut impl Iterator<Item = &'a str>, expected: &str) -> ParseResult<u32> {
+    let raw_number = data.next()
+        .ok_or_else(|| ParseError::end(input, expected))?;
+
+    parse::field(input, raw_number, expected)
+}
+
+fn parse_nodes<'a>(input: &str, data: &mut impl Iterator<Item = &'a str>) -> ParseResult<Node> {
+    let child_len = parse_number(input, data, "a child count")?;
+    let meta_len = parse_number(input, data, "a metadata count")?;
     let children = (0..child_len)
-        .map(|_| parse_nodes(data))
-        .collect();
-    let meta_data = data.take(meta_len as usize).collect();
-    Node { children, meta_data }
+        .map(|_| parse_nodes(input, data))
+        .collect::<ParseResult<_>>()?;
+    let meta_data = (0..meta_len)
+        .map(|_| parse_number(input, data, "a metadata entry"))
+        .collect::<ParseResult<_>>()?;
+    Ok(Node { children, meta_data })
 }
 
 #[cfg(test)]
@@ -67,14 +94,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let data: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let data = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&data), 44338);
     }
 
     #[test]
     fn p2() {
-        let data: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let data = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&data), 37560);
     }
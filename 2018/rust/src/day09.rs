@@ -1,9 +1,42 @@
+use aoc_common::{Solution, Generate, ParseResult, parse};
+use aoc_common::generate::Rng;
+
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day09.txt");
 
-pub fn day09() -> (usize, usize) {
-    let config = parse_input(RAW_INPUT_STR);
+pub struct Day09;
+
+impl Solution for Day09 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 9;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = GameConfig;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    (part1(&config), part2(&config))
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+impl Generate for Day09 {
+    // Part 2 plays a hundred times as many marbles
+    const SIZES: &'static [usize] = &[1_000, 4_000, 16_000, 64_000];
+
+    // `size` is the value of the last marble
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let player_count = rng.between(9, 500);
+
+        format!("{} players; last marble is worth {} points\n", player_count, size)
+    }
 }
 
 use std::collections::VecDeque;
@@ -47,15 +80,15 @@ pub fn part2(config: &GameConfig) -> usize {
     part1(&config)
 }
 
-pub fn parse_input(input: &str) -> GameConfig {
-    let mut words = input.split(' ');
+pub fn parse_input(input: &str) -> ParseResult<GameConfig> {
+    let line = input.trim_end();
+    let mut words = line.split(' ');
 
-    GameConfig {
-        player_count: words.nth(0).expect("Missing player count")
-            .parse().expect("Invalid player count"),
-        marble_count: words.nth(5).expect("Missing marble count")
-            .parse().expect("Invalid marble count"),
-    }
+    let player_count = parse::next_field(input, &mut words, line, "a player count")?;
+    let mut words = words.skip(5);
+    let marble_count = parse::next_field(input, &mut words, line, "a marble count")?;
+
+    Ok(GameConfig { player_count, marble_count })
 }
 
 #[derive(Debug)]
@@ -69,15 +102,27 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let config = parse_input(RAW_INPUT_STR);
+        let config = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&config), 380705);
     }
 
     #[test]
     fn p2() {
-        let config = parse_input(RAW_INPUT_STR);
+        let config = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&config), 3171801582);
     }
+
+    #[test]
+    fn generated() {
+        let mut rng = Rng::new(aoc_common::generate::SEED);
+
+        for &size in Day09::SIZES {
+            let config = parse_input(&Day09::generate(&mut rng, size)).unwrap();
+
+            assert_eq!(config.marble_count, size);
+            assert!(part1(&config) > 0);
+        }
+    }
 }
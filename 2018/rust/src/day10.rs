@@ -1,42 +1,58 @@
+use aoc_common::{Solution, ParseError, ParseResult, parse};
+use aoc_grid::{Bounds, Grid, Pos, ocr};
+
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day10.txt");
 const LETTER_HEIGHT: usize = 10;
 // const LETTER_WIDTH: usize = 6;
 // const LETTER_SPACING: usize = 2;
 
-pub fn day10() -> (Banner, usize) {
-    let points: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 10;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<SkyPoint>;
+    type Part1 = String;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-    (Banner(part1(&points)), part2(&points))
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(points: &[SkyPoint]) -> String {
-    let steps_required = steps_until_message(points);
+    ocr::read(&message(points))
+}
+
+// The lit points of the sky once they are aligned, cropped to the message
+pub fn message(points: &[SkyPoint]) -> Grid<bool> {
+    let steps_required = steps_until_message(points) as i32;
 
     let message_points: Vec<_> = points.iter()
-        .map(|p| Position {
-            x: p.pos.x + p.vel.dx * steps_required as i32,
-            y: p.pos.y + p.vel.dy * steps_required as i32,
+        .map(|p| Pos {
+            x: p.pos.x + p.vel.dx * steps_required,
+            y: p.pos.y + p.vel.dy * steps_required,
         })
         .collect();
 
-    let left = message_points.iter().map(|p| p.x).min().unwrap();
-    let right = message_points.iter().map(|p| p.x).max().unwrap();
-    let top = message_points.iter().map(|p| p.y).min().unwrap();
-    let bottom = message_points.iter().map(|p| p.y).max().unwrap();
+    let bounds = Bounds::enclosing(message_points.iter().copied()).expect("No points");
 
-    let height = (bottom - top) as usize + 1;
-    let width = (right - left) as usize + 1;
-
-    let mut buff = vec![vec!['░'; width]; height];
+    let mut sky = Grid::new(bounds.width(), bounds.height(), false);
     message_points.iter().for_each(|p| {
-        let y = (p.y - top) as usize;
-        let x = (p.x - left) as usize;
-        buff[y][x] = '▓';
+        sky[Pos { x: p.x - bounds.min.x, y: p.y - bounds.min.y }] = true;
     });
 
-    buff.into_iter()
-        .flat_map(|b| b.into_iter().chain(std::iter::once('\n')))
-        .collect()
+    sky
 }
 
 pub fn part2(points: &[SkyPoint]) -> usize {
@@ -58,28 +74,36 @@ fn steps_until_message(points: &[SkyPoint]) -> usize {
     ((height0 + 1) - LETTER_HEIGHT) / d_height
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = SkyPoint> + '_ {
-    input.lines().map(move |line| {
-        let mut splitted = line["position=<".len()..line.len() - 1].split("> velocity=<");
+pub fn parse_input(input: &str) -> ParseResult<Vec<SkyPoint>> {
+    input.lines()
+        .map(|line| parse_sky_point(input, line))
+        .collect()
+}
+
+fn parse_sky_point(input: &str, line: &str) -> ParseResult<SkyPoint> {
+    let raw_point = line.strip_prefix("position=<")
+        .ok_or_else(|| ParseError::at(input, line, "`position=<`"))?;
+    let raw_point = raw_point.strip_suffix('>')
+        .ok_or_else(|| ParseError::after(input, line, "`>`"))?;
 
-        let raw_pos = splitted.next().unwrap();
-        let raw_vel = splitted.next().unwrap();
+    let mut splitted = raw_point.split("> velocity=<");
+    let raw_pos = parse::next(input, &mut splitted, raw_point, "a position")?;
+    let raw_vel = parse::next(input, &mut splitted, raw_pos, "`> velocity=<`")?;
 
-        let mut pos_split = raw_pos[..].split(',');
-        let mut vel_split = raw_vel[..].split(',');
+    let mut pos_split = raw_pos.split(',').map(str::trim);
+    let mut vel_split = raw_vel.split(',').map(str::trim);
 
-        let pos = Position {
-            x: pos_split.next().expect("Missing x").trim().parse().expect("Invalid x"),
-            y: pos_split.next().expect("Missing y").trim().parse().expect("Invalid y"),
-        };
+    let pos = Position {
+        x: parse::next_field(input, &mut pos_split, raw_pos, "an x coordinate")?,
+        y: parse::next_field(input, &mut pos_split, raw_pos, "a y coordinate")?,
+    };
 
-        let vel = Velocity {
-            dx: vel_split.next().expect("Missing dx").trim().parse().expect("Invalid dx"),
-            dy: vel_split.next().expect("Missing dy").trim().parse().expect("Invalid dy"),
-        };
+    let vel = Velocity {
+        dx: parse::next_field(input, &mut vel_split, raw_vel, "an x velocity")?,
+        dy: parse::next_field(input, &mut vel_split, raw_vel, "a y velocity")?,
+    };
 
-        SkyPoint { pos, vel }
-    })
+    Ok(SkyPoint { pos, vel })
 }
 
 #[derive(Debug, Clone)]
@@ -100,25 +124,15 @@ pub struct Velocity {
     dy: i32,
 }
 
-pub struct Banner(String);
-
-use std::fmt;
-
-impl fmt::Debug for Banner {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "\n{}", self.0)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let points: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let points = parse_input(RAW_INPUT_STR).unwrap();
 
-        // assert_eq!(part1(&points), "PHLGRNFK");
-        assert_eq!(part1(&points), "\
+        assert_eq!(part1(&points), "PHLGRNFK");
+        assert_eq!(ocr::render(&message(&points)), "\
 ▓▓▓▓▓░░░▓░░░░▓░░▓░░░░░░░░▓▓▓▓░░░▓▓▓▓▓░░░▓░░░░▓░░▓▓▓▓▓▓░░▓░░░░▓
 ▓░░░░▓░░▓░░░░▓░░▓░░░░░░░▓░░░░▓░░▓░░░░▓░░▓▓░░░▓░░▓░░░░░░░▓░░░▓░
 ▓░░░░▓░░▓░░░░▓░░▓░░░░░░░▓░░░░░░░▓░░░░▓░░▓▓░░░▓░░▓░░░░░░░▓░░▓░░
@@ -128,13 +142,12 @@ mod tests {
 ▓░░░░░░░▓░░░░▓░░▓░░░░░░░▓░░░░▓░░▓░░░▓░░░▓░░▓░▓░░▓░░░░░░░▓░▓░░░
 ▓░░░░░░░▓░░░░▓░░▓░░░░░░░▓░░░░▓░░▓░░░▓░░░▓░░░▓▓░░▓░░░░░░░▓░░▓░░
 ▓░░░░░░░▓░░░░▓░░▓░░░░░░░▓░░░▓▓░░▓░░░░▓░░▓░░░▓▓░░▓░░░░░░░▓░░░▓░
-▓░░░░░░░▓░░░░▓░░▓▓▓▓▓▓░░░▓▓▓░▓░░▓░░░░▓░░▓░░░░▓░░▓░░░░░░░▓░░░░▓
-");
+▓░░░░░░░▓░░░░▓░░▓▓▓▓▓▓░░░▓▓▓░▓░░▓░░░░▓░░▓░░░░▓░░▓░░░░░░░▓░░░░▓");
     }
 
     #[test]
     fn p2() {
-        let points: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let points = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&points), 10407);
     }
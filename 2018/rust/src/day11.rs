@@ -1,10 +1,36 @@
-const INPUT: u16 = 1723;
+use aoc_common::{Solution, ParseResult, parse};
 
-pub fn day11() -> ((usize, usize), (usize, usize, usize)) {
-    ((part1(INPUT)), part2(INPUT))
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day11.txt");
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 11;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = u16;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        let (x, y) = part1(*input);
+
+        format!("{},{}", x, y)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        let (x, y, size) = part2(*input);
+
+        format!("{},{},{}", x, y, size)
+    }
 }
 
-pub fn part1(serial_number: u16) -> (usize, usize) {
+pub fn part1(serial_number: u16) -> Corner {
     let grid = fuel_grid(serial_number);
 
     let mut max_x = 0;
@@ -26,13 +52,13 @@ pub fn part1(serial_number: u16) -> (usize, usize) {
 }
 
 use rayon::prelude::*;
-use packed_simd::i32x16;
+use crate::simd::i32x16;
 
-pub fn part2(serial_number: u16) -> (usize, usize, usize) {
+pub fn part2(serial_number: u16) -> Square {
     const IDXS: i32x16 = i32x16::new(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
     const ZEROES: i32x16 = i32x16::splat(0);
 
-    const M: i32 = i32::min_value() / 2;
+    const M: i32 = i32::MIN / 2;
     const MASKS: [i32x16; 16] = [
         i32x16::new(0, M, M, M, M, M, M, M, M, M, M, M, M, M, M, M),
         i32x16::new(0, 0, M, M, M, M, M, M, M, M, M, M, M, M, M, M),
@@ -129,6 +155,14 @@ fn level_sum(x: usize, y: usize, size: usize, grid: &FuelGrid) -> i32 {
     total
 }
 
+pub fn parse_input(input: &str) -> ParseResult<u16> {
+    parse::field(input, input.trim(), "a serial number")
+}
+
+// The top-left corner of the best square, along with its size for part 2
+type Corner = (usize, usize);
+type Square = (usize, usize, usize);
+
 type FuelGrid = [Cell; 300 * 300];
 type Cell = i8;
 
@@ -137,11 +171,11 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        assert_eq!(part1(INPUT), (34, 13));
+        assert_eq!(part1(parse_input(RAW_INPUT_STR).unwrap()), (34, 13));
     }
 
     #[test]
     fn p2() {
-        assert_eq!(part2(INPUT), (280, 218, 11));
+        assert_eq!(part2(parse_input(RAW_INPUT_STR).unwrap()), (280, 218, 11));
     }
 }
@@ -1,14 +1,38 @@
-const RAW_INPUT: &[u8] = include_bytes!("../../inputs/day12.txt");
+use aoc_common::{Solution, ParseError, ParseResult, cycle};
 
-pub fn day12() -> (i64, i64) {
-    let (state, rules) = parse_input(RAW_INPUT);
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day12.txt");
 
-    (part1(&state, &rules), part2(&state, &rules))
+pub struct Day12;
+
+impl Solution for Day12 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 12;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = (Vec<Pot>, Rules);
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        let (state, rules) = input;
+
+        part1(state, rules)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        let (state, rules) = input;
+
+        part2(state, rules)
+    }
 }
 
 pub fn part1(initial_state: &[Pot], rules: &Rules) -> i64 {
     let initial_pots = PotRow {
-        pots: initial_state.iter().cloned().collect(),
+        pots: initial_state.to_vec(),
         start_idx: 0
     };
 
@@ -18,25 +42,26 @@ pub fn part1(initial_state: &[Pot], rules: &Rules) -> i64 {
 
 pub fn part2(initial_state: &[Pot], rules: &Rules) -> i64 {
     let initial_pots = PotRow {
-        pots: initial_state.iter().cloned().collect(),
+        pots: initial_state.to_vec(),
         start_idx: 0
     };
 
     // Find out cycle properties
-    let (cycle_len, cycle_start) = floyd(
-        initial_pots.clone(),
+    // The pots keep drifting while cycling so only their pattern is compared
+    let cycle = cycle::floyd(
+        initial_pots,
         |pot| spread(pot, rules),
         |p1, p2| p1.pots == p2.pots
     );
 
     // Compute deltas between cycles
-    let pots_after_one_cycle = spread_n(initial_pots, rules, cycle_start);
-    let pots_after_two_cycles = spread_n(pots_after_one_cycle.clone(), rules, cycle_len);
+    let pots_after_one_cycle = cycle.entry;
+    let pots_after_two_cycles = spread_n(pots_after_one_cycle.clone(), rules, cycle.len);
     let start_delta = pots_after_two_cycles.start_idx - pots_after_one_cycle.start_idx;
 
     // Fast forward to the last cycle
-    let cycles_before_end = (50_000_000_000 - cycle_start) / cycle_len;
-    let remaining_steps = (50_000_000_000 - cycle_start) % cycle_len;
+    let cycles_before_end = (50_000_000_000 - cycle.start) / cycle.len;
+    let remaining_steps = (50_000_000_000 - cycle.start) % cycle.len;
     let last_cycled_pots = PotRow {
         pots: pots_after_one_cycle.pots,
         start_idx: pots_after_one_cycle.start_idx + cycles_before_end as i64 * start_delta,
@@ -48,55 +73,33 @@ pub fn part2(initial_state: &[Pot], rules: &Rules) -> i64 {
     final_pots.sum_idxs()
 }
 
-fn floyd<T: Clone, F: Fn(T) -> T, Cmp: Fn(&T, &T) -> bool>(x0: T, f: F, cmp: Cmp)
-    -> (usize, usize)
-{
-    let mut tortoise = f(x0.clone());
-    let mut hare = f(f(x0.clone()));
-
-    while !cmp(&tortoise, &hare) {
-        tortoise = f(tortoise);
-        hare = f(f(hare));
-    }
+pub fn parse_input(input: &[u8]) -> ParseResult<(Vec<Pot>, Rules)> {
+    const STATE_PREFIX: &[u8] = b"initial state: ";
 
-    let mut mu = 0;
-    let mut tortoise = x0;
-    while !cmp(&tortoise, &hare) {
-        tortoise = f(tortoise);
-        hare = f(hare);
-        mu += 1
-    }
-
-    let mut lam = 1;
-    let mut hare = f(tortoise.clone());
-    while !cmp(&tortoise, &hare) {
-        hare = f(hare);
-        lam += 1
-    }
-
-    (lam, mu)
-}
-
-pub fn parse_input(input: &[u8]) -> (Vec<Pot>, Rules) {
     let mut lines = input.split(|&c| c == b'\n');
 
-    let raw_state = &lines.next().expect("Missing initial state")[15..];
-    let initial_state = raw_state.iter().map(|&b| Pot::from(b)).collect();
+    let raw_state = lines.next()
+        .and_then(|line| line.strip_prefix(STATE_PREFIX))
+        .ok_or_else(|| ParseError::new(input, 0, "`initial state: `"))?;
+    let initial_state = (0..raw_state.len())
+        .map(|idx| Pot::parse(input, &raw_state[idx..=idx]))
+        .collect::<ParseResult<_>>()?;
 
     let rules = lines.skip(1)
-        .map(|line| (
-            [
-                Pot::from(line[0]),
-                Pot::from(line[1]),
-                Pot::from(line[2]),
-                Pot::from(line[3]),
-                Pot::from(line[4]),
-            ],
-            Pot::from(line[9]),
-        ))
-        .collect();
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            if line.len() != 10 || &line[5..9] != b" => " {
+                return Err(ParseError::at(input, line, "a rule like `..#.# => #`"))
+            }
+
+            let pot_at = |idx: usize| Pot::parse(input, &line[idx..=idx]);
+            let pattern = [pot_at(0)?, pot_at(1)?, pot_at(2)?, pot_at(3)?, pot_at(4)?];
+
+            Ok((pattern, pot_at(9)?))
+        })
+        .collect::<ParseResult<_>>()?;
 
-    (initial_state, rules)
+    Ok((initial_state, rules))
 }
 
 fn spread(mut pot_row: PotRow, rules: &Rules) -> PotRow {
@@ -125,9 +128,9 @@ fn spread(mut pot_row: PotRow, rules: &Rules) -> PotRow {
         .map(|idx| {
             let pots = &pot_row.pots[idx-2..=idx+2];
             if let Some(result) = rules.get(pots) {
-                result.clone()
+                *result
             } else {
-                pot_row.pots[idx].clone()
+                pot_row.pots[idx]
             }
         })
         .collect();
@@ -171,19 +174,19 @@ impl PotRow {
     }
 }
 
-impl From<u8> for Pot {
-    fn from(ch: u8) -> Self {
-        match ch {
-            b'.' => Pot::Empty,
-            b'#' => Pot::Filled,
-            c   => panic!("Invalid pot byte: {}", c),
+impl Pot {
+    fn parse(input: &[u8], raw_pot: &[u8]) -> ParseResult<Self> {
+        match raw_pot {
+            b"." => Ok(Pot::Empty),
+            b"#" => Ok(Pot::Filled),
+            _    => Err(ParseError::at(input, raw_pot, "a pot (`.` or `#`)")),
         }
     }
 }
 
-impl Into<u8> for Pot {
-    fn into(self) -> u8 {
-        match self {
+impl From<Pot> for u8 {
+    fn from(val: Pot) -> Self {
+        match val {
             Pot::Empty  => b'.',
             Pot::Filled => b'#',
         }
@@ -195,14 +198,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let (state, rules) = parse_input(RAW_INPUT);
+        let (state, rules) = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part1(&state, &rules), 2045);
     }
 
     #[test]
     fn p2() {
-        let (state, rules) = parse_input(RAW_INPUT);
+        let (state, rules) = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part2(&state, &rules), 2100000000428);
     }
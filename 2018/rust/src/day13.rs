@@ -1,46 +1,72 @@
 use hashbrown::HashSet;
+use aoc_common::{Solution, ParseResult};
+use aoc_grid::{Direction, Grid, Pos};
 
-const RAW_INPUT: &[u8] = include_bytes!("../../inputs/day13.txt");
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day13.txt");
 
-pub fn day13() -> ((u16, u16), (u16, u16)) {
-    let (world, trains) = parse_input(RAW_INPUT);
+pub struct Day13;
 
-    (part1(&world, &trains), part2(&world, &trains))
+impl Solution for Day13 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 13;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = (World, Trains);
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        let (world, trains) = input;
+        let (x, y) = part1(world, trains);
+
+        format!("{},{}", x, y)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        let (world, trains) = input;
+        let (x, y) = part2(world, trains);
+
+        format!("{},{}", x, y)
+    }
 }
 
-pub fn part1(world: &World, trains: &Trains) -> (u16, u16) {
-    let mut trains = trains.iter().cloned().collect();
+pub fn part1(world: &World, trains: &Trains) -> (i32, i32) {
+    let mut trains = trains.to_vec();
 
     loop {
         let crashes = tick(world, &mut trains);
 
-        if let Some(&Position { x, y }) = crashes.first() {
+        if let Some(&Pos { x, y }) = crashes.first() {
             break (x, y)
         }
     }
 }
 
-pub fn part2(world: &World, trains: &Trains) -> (u16, u16) {
-    let mut trains: Vec<_> = trains.iter().cloned().collect();
+pub fn part2(world: &World, trains: &Trains) -> (i32, i32) {
+    let mut trains: Vec<_> = trains.to_vec();
 
     while trains.len() > 1 {
         tick(world, &mut trains);
     }
 
     match trains.first() {
-        Some(&Train { pos: Position { x, y }, .. }) => (x, y),
+        Some(&Train { pos: Pos { x, y }, .. }) => (x, y),
         None => panic!("No more trains left")
     }
 }
 
 fn tick(world: &World, trains: &mut Trains) -> Collisions {
     let mut train_positions: HashSet<_> = trains.iter()
-        .map(|train| train.pos.clone())
+        .map(|train| train.pos)
         .collect();
 
     let mut collisions = Collisions::new();
 
-    trains.sort_by_key(|train| (train.pos.y, train.pos.x));
+    trains.sort_by_key(|train| train.pos);
 
     for train in trains.iter_mut() {
         if collisions.contains(&train.pos) {
@@ -50,8 +76,8 @@ fn tick(world: &World, trains: &mut Trains) -> Collisions {
         train_positions.remove(&train.pos);
         train.tick(world);
 
-        if !train_positions.insert(train.pos.clone()) {
-            collisions.push(train.pos.clone());
+        if !train_positions.insert(train.pos) {
+            collisions.push(train.pos);
         }
     }
 
@@ -60,51 +86,27 @@ fn tick(world: &World, trains: &mut Trains) -> Collisions {
     collisions
 }
 
-pub fn parse_input(input: &[u8]) -> (World, Trains) {
+pub fn parse_input(input: &[u8]) -> ParseResult<(World, Trains)> {
     let mut trains = Trains::with_capacity(32);
-    let height = input.iter().position(|&c| c == b'\n').unwrap();
-
-    let rails: Vec<_> = input.split(|&c| c == b'\n')
-        .flatten()
-        .enumerate()
-        .map(|(i, c)| {
-            let mut add_train = |direction| {
-                let x = i % height;
-                let y = i / height;
-
-                trains.push(Train {
-                    pos: Position { x: x as u16, y: y as u16 },
-                    direction,
-                    turn_strategy: TurnStrategy::Left
-                })
-            };
-
-            match c {
-                b'^' => add_train(Direction::Up),
-                b'v' => add_train(Direction::Down),
-                b'<' => add_train(Direction::Left),
-                b'>' => add_train(Direction::Right),
-                _ => ()
-            }
-
-            match c {
-                b' '        => Rail::Empty,
-                b'-' | b'|' => Rail::Straight,
-                b'/'        => Rail::CurveRight,
-                b'\\'       => Rail::CurveLeft,
-                b'+'        => Rail::Intersection,
-                _           => Rail::Straight // Trains are only placed on straight lines
-            }
-        })
-        .collect();
 
-    (World { rails, height }, trains)
-}
+    let rails = Grid::parse(input, |pos, c| {
+        if let Some(direction) = Direction::from_arrow(c) {
+            trains.push(Train { pos, direction, turn_strategy: TurnStrategy::Left });
+        }
+
+        match c {
+            b' '        => Some(Rail::Empty),
+            b'-' | b'|' => Some(Rail::Straight),
+            b'/'        => Some(Rail::CurveRight),
+            b'\\'       => Some(Rail::CurveLeft),
+            b'+'        => Some(Rail::Intersection),
+            // Trains are only placed on straight lines
+            b'^' | b'v' | b'<' | b'>' => Some(Rail::Straight),
+            _ => None,
+        }
+    }, "a rail or a train")?;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Position {
-    x: u16,
-    y: u16
+    Ok((World { rails }, trains))
 }
 
 #[derive(Debug)]
@@ -118,27 +120,18 @@ pub enum Rail {
 
 #[derive(Debug)]
 pub struct World {
-    rails: Vec<Rail>,
-    height: usize
+    rails: Grid<Rail>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Train {
-    pos: Position,
+    pos: Pos,
     direction: Direction,
     turn_strategy: TurnStrategy
 }
 
 type Trains = Vec<Train>;
-type Collisions = Vec<Position>;
-
-#[derive(Debug, Clone)]
-pub enum Direction {
-    Up,
-    Down,
-    Left,
-    Right
-}
+type Collisions = Vec<Pos>;
 
 #[derive(Debug, Clone)]
 pub enum TurnStrategy {
@@ -147,34 +140,13 @@ pub enum TurnStrategy {
     Right
 }
 
-impl Position {
-    fn moved(&self, direction: &Direction) -> Self {
-        use self::Direction::*;
-
-        let &Position { x, y } = self;
-
-        match direction {
-            Up    => Position { x: x + 0, y: y - 1 },
-            Down  => Position { x: x + 0, y: y + 1 },
-            Left  => Position { x: x - 1, y: y + 0 },
-            Right => Position { x: x + 1, y: y + 0 },
-        }
-    }
-}
-
-impl World {
-    fn at(&self, pos: &Position) -> &Rail {
-        &self.rails[pos.y as usize * self.height + pos.x as usize]
-    }
-}
-
 impl Train {
     fn tick(&mut self, world: &World) {
         use self::Direction::*;
 
-        let new_pos = self.pos.moved(&self.direction);
+        let new_pos = self.pos + self.direction;
 
-        let turn_direction = match world.at(&new_pos) {
+        let turn_direction = match world.rails[new_pos] {
             Rail::Straight     => TurnStrategy::Straight,
             Rail::Intersection => self.turn_strategy.next(),
             Rail::CurveLeft    => {
@@ -192,35 +164,22 @@ impl Train {
             Rail::Empty => panic!("A train went off the tracks!"),
         };
 
-        let new_direction = self.direction.turned(&turn_direction);
+        let new_direction = turn_direction.turned(self.direction);
 
         self.pos = new_pos;
         self.direction = new_direction;
     }
 }
 
-impl Direction {
-    fn turned(&self, way: &TurnStrategy) -> Self {
-        use self::Direction::*;
-        use self::TurnStrategy::{Left as TurnLeft, Right as TurnRight};
-
-        match (way, self) {
-            (TurnLeft, Up)    => Left,
-            (TurnLeft, Down)  => Right,
-            (TurnLeft, Left)  => Down,
-            (TurnLeft, Right) => Up,
-
-            (TurnRight, Up)    => Right,
-            (TurnRight, Down)  => Left,
-            (TurnRight, Left)  => Up,
-            (TurnRight, Right) => Down,
-
-            (_straight, current_direction) => current_direction.clone(),
+impl TurnStrategy {
+    fn turned(&self, direction: Direction) -> Direction {
+        match self {
+            TurnStrategy::Left     => direction.turn_left(),
+            TurnStrategy::Straight => direction,
+            TurnStrategy::Right    => direction.turn_right(),
         }
     }
-}
 
-impl TurnStrategy {
     fn next(&mut self) -> Self {
         use self::TurnStrategy::*;
 
@@ -239,14 +198,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let (world, trains) = parse_input(RAW_INPUT);
+        let (world, trains) = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part1(&world, &trains), (38, 72));
     }
 
     #[test]
     fn p2() {
-        let (world, trains) = parse_input(RAW_INPUT);
+        let (world, trains) = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part2(&world, &trains), (68, 27));
     }
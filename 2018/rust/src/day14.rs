@@ -1,8 +1,29 @@
-const INPUT: usize = 920831;
-const INPUT_AS_STR: &str = "920831";
+use aoc_common::{Solution, ParseResult, parse};
 
-pub fn day14() -> (String, usize) {
-    (part1(INPUT), part2(INPUT_AS_STR))
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day14.txt");
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 14;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = (usize, &'a str);
+    type Part1 = String;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input.0)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input.1)
+    }
 }
 
 const MEMOIZED_STATE: [u8; 20] = [3, 7, 1, 0, 1, 0, 1, 2, 4, 5, 1, 5, 8, 9, 1, 6, 7, 7, 9, 2];
@@ -24,14 +45,14 @@ pub fn part1(recipe_count: usize) -> String {
         recipes[count] = recipe_sum;
         count += 1;
 
-        elf1 = elf1 + move1 as usize;
-        elf2 = elf2 + move2 as usize;
+        elf1 += move1 as usize;
+        elf2 += move2 as usize;
         if elf1 >= count { elf1 -= count }
         if elf2 >= count { elf2 -= count }
     }
 
     let ten_recipes = recipes[recipe_count..recipe_count+10]
-        .into_iter()
+        .iter()
         .map(|c| c + b'0')
         .collect();
 
@@ -41,7 +62,6 @@ pub fn part1(recipe_count: usize) -> String {
 pub fn part2(recipe_score: &str) -> usize {
     let score_len = recipe_score.len();
     let score_as_u8s: Vec<u8> = recipe_score.bytes()
-        .into_iter()
         .map(|b| b - b'0')
         .collect();
     let mut needle = Needle { data: &score_as_u8s, len: score_len, pos: 0 };
@@ -63,8 +83,8 @@ pub fn part2(recipe_score: &str) -> usize {
         if needle.find(recipe_sum) { break count+1 - score_len }
         count += 1;
 
-        elf1 = elf1 + move1 as usize;
-        elf2 = elf2 + move2 as usize;
+        elf1 += move1 as usize;
+        elf2 += move2 as usize;
         if elf1 >= count { elf1 -= count }
         if elf2 >= count { elf2 -= count }
     }
@@ -88,16 +108,26 @@ impl<'a, T: Eq> Needle<'a, T> {
     }
 }
 
+pub fn parse_input(input: &str) -> ParseResult<(usize, &str)> {
+    let digits = input.trim();
+
+    Ok((parse::field(input, digits, "a recipe count")?, digits))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        assert_eq!(part1(INPUT), "7121102535");
+        let (recipe_count, _) = parse_input(RAW_INPUT_STR).unwrap();
+
+        assert_eq!(part1(recipe_count), "7121102535");
     }
 
     #[test]
     fn p2() {
-        assert_eq!(part2(INPUT_AS_STR), 20236441);
+        let (_, recipe_score) = parse_input(RAW_INPUT_STR).unwrap();
+
+        assert_eq!(part2(recipe_score), 20236441);
     }
 }
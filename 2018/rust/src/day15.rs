@@ -1,28 +1,46 @@
-use std::collections::VecDeque;
 use hashbrown::HashSet;
 use rayon::prelude::*;
+use aoc_common::{Solution, ParseResult, search};
+use aoc_grid::{Grid, Pos};
 
-const RAW_INPUT: &[u8] = include_bytes!("../../inputs/day15.txt");
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day15.txt");
 
-pub fn day15() -> (u32, u32) {
-    let world_builder = parse_input(RAW_INPUT);
+pub struct Day15;
 
-    (part1(&world_builder), part2(&world_builder))
+impl Solution for Day15 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 15;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = WorldBuilder;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(world_builder: &WorldBuilder) -> u32 {
     let mut world = world_builder.with_elf_ap(3);
 
     let end_turn = (0..)
-        .skip_while(|_| world.resolve_next_round() == RoundResult::Complete)
-        .next()
+        .find(|_| world.resolve_next_round() != RoundResult::Complete)
         .unwrap();
 
     end_turn * world.entities_hps()
 }
 
 pub fn part2(world_builder: &WorldBuilder) -> u32 {
-    let initial_elves_count = world_builder.parsed_entities.iter()
+    let initial_elves_count = world_builder.parsed_entities.cells().iter()
         .filter(|&parsed_entity| *parsed_entity == ParsedEntity::Elf)
         .count();
 
@@ -36,12 +54,11 @@ pub fn part2(world_builder: &WorldBuilder) -> u32 {
             let mut world = world_builder.with_elf_ap(ap as u32);
 
             let end_turn = (0..)
-                .skip_while(|_| {
+                .find(|_| {
                     let is_complete = world.resolve_next_round() == RoundResult::Complete;
                     let had_elf_casualties = world.elves_count < initial_elves_count;
-                    is_complete && !had_elf_casualties
+                    !is_complete || had_elf_casualties
                 })
-                .next()
                 .unwrap();
 
             if world.elves_count == initial_elves_count {
@@ -54,29 +71,21 @@ pub fn part2(world_builder: &WorldBuilder) -> u32 {
         .unwrap()
 }
 
-pub fn parse_input(input: &[u8]) -> WorldBuilder {
-    let height = input.iter()
-        .position(|&c| c == b'\n')
-        .unwrap();
-
-    let parsed_entities = input.split(|&c| c == b'\n')
-        .flatten()
-        .map(|c| match c {
-            b'#' => ParsedEntity::Wall,
-            b'.' => ParsedEntity::OpenCavern,
-            b'E' => ParsedEntity::Elf,
-            b'G' => ParsedEntity::Goblin,
-            invalid => panic!("Invalid input byte: {}", invalid)
-        })
-        .collect();
+pub fn parse_input(input: &[u8]) -> ParseResult<WorldBuilder> {
+    let parsed_entities = Grid::parse(input, |_pos, c| match c {
+        b'#' => Some(ParsedEntity::Wall),
+        b'.' => Some(ParsedEntity::OpenCavern),
+        b'E' => Some(ParsedEntity::Elf),
+        b'G' => Some(ParsedEntity::Goblin),
+        _ => None,
+    }, "a wall, a cavern or a unit")?;
 
-    WorldBuilder { height, parsed_entities }
+    Ok(WorldBuilder { parsed_entities })
 }
 
 #[derive(Debug, Clone)]
 pub struct World {
-    height: usize,
-    entities: Vec<Entity>,
+    entities: Grid<Entity>,
     elves_count: usize,
     goblins_count: usize,
 }
@@ -108,8 +117,7 @@ enum RoundResult {
 
 #[derive(Debug, Clone)]
 pub struct WorldBuilder {
-    height: usize,
-    parsed_entities: Vec<ParsedEntity>,
+    parsed_entities: Grid<ParsedEntity>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -124,7 +132,7 @@ impl World {
     fn resolve_next_round(&mut self) -> RoundResult {
         let mut processed = HashSet::new();
 
-        for position in 0..self.entities.len() {
+        for position in self.entities.bounds().positions() {
             // Skip non units and already processed units
             if processed.contains(&position) {
                 continue
@@ -165,69 +173,51 @@ impl World {
         RoundResult::Complete
     }
 
-    fn step_towards_closest_target(&self, enemy_kind: &UnitKind, start_pos: usize)
-        -> Option<usize>
+    fn step_towards_closest_target(&self, enemy_kind: &UnitKind, start_pos: Pos)
+        -> Option<Pos>
     {
-        struct Node {
-            position: usize,
-            step_pos: usize,
-            distance: usize
-        }
-
-        let mut distance_treshold = self.entities.len();
-        let mut solutions = Vec::with_capacity(4);
-
-        let mut open_set = VecDeque::with_capacity(self.entities.len());
-        let mut closed_set = HashSet::with_capacity(self.entities.len());
-
-        closed_set.insert(start_pos);
-
-        // Queue the free positions around the unit
-        for (position, adjacent_entity) in self.adjacents(start_pos) {
-            if let Entity::OpenCavern = adjacent_entity {
-                closed_set.insert(position);
-                open_set.push_back(Node { position, step_pos: position, distance: 0 })
-            }
-        }
-
-        while let Some(Node { position, step_pos, distance }) = open_set.pop_front() {
-            if distance > distance_treshold {
+        // Search from the free positions around the unit
+        let starts = self.adjacents(start_pos)
+            .filter(|(_, entity)| matches!(entity, Entity::OpenCavern))
+            .map(|(position, _)| position);
+
+        let mut search = search::bfs(starts, |&position: &Pos| {
+            self.adjacents(position)
+                .filter(|(_, entity)| matches!(entity, Entity::OpenCavern))
+                .map(|(adjacent_pos, _)| adjacent_pos)
+        });
+
+        let mut distance_treshold = None;
+        let mut targets = Vec::with_capacity(4);
+
+        for (position, distance) in search.by_ref() {
+            if distance_treshold.is_some_and(|treshold| distance > treshold) {
                 break
             }
 
-            for (adjacent_pos, adjacent_entity) in self.adjacents(position) {
-                if closed_set.contains(&adjacent_pos) {
-                    continue
-                }
+            // The current position is in range of an enemy which makes
+            // it valid candidate. Save it and adjust the threshold
+            let in_range = self.adjacents(position).any(|(_, entity)| match entity {
+                Entity::Unit { kind, .. } => kind == enemy_kind,
+                _ => false
+            });
 
-                match adjacent_entity {
-                    // The current position is in range of an enemy which makes
-                    // it valid candidate. Save it and adjust the threshold
-                    Entity::Unit { kind, .. } if kind == enemy_kind => {
-                        distance_treshold = distance;
-                        solutions.push((position, step_pos))
-                    },
-                    Entity::OpenCavern => {
-                        closed_set.insert(adjacent_pos);
-                        open_set.push_back(Node { position: adjacent_pos,
-                            step_pos,
-                            distance: distance + 1
-                        })
-                    },
-                    _ => ()
-                }
+            if in_range {
+                distance_treshold = Some(distance);
+                targets.push(position)
             }
         }
 
         // Return the solution that has the smallest position and break ties
         // using the smallest step position
-        solutions
+        targets
             .into_iter()
+            .map(|target_pos| (target_pos, search.path(&target_pos)[0]))
             .min()
-            .map(|(_target_pos, start_pos)| start_pos)
+            .map(|(_target_pos, step_pos)| step_pos)
     }
 
-    fn first_enemy_in_range(&self, enemy_kind: &UnitKind, position: usize) -> Option<usize> {
+    fn first_enemy_in_range(&self, enemy_kind: &UnitKind, position: Pos) -> Option<Pos> {
         self.adjacents(position)
             .filter_map(|(pos, entity)| match entity {
                 Entity::Unit { kind, unit } if kind == enemy_kind => Some((pos, unit)),
@@ -237,7 +227,7 @@ impl World {
             .map(|(pos, _)| pos)
     }
 
-    fn attack(&mut self, position: usize, power: u32) {
+    fn attack(&mut self, position: Pos, power: u32) {
         let enemy = &mut self.entities[position];
 
         if let Entity::Unit { kind, unit } = enemy {
@@ -252,7 +242,7 @@ impl World {
         }
     }
 
-    fn move_unit(&mut self, from: usize, to: usize) {
+    fn move_unit(&mut self, from: Pos, to: Pos) {
         // Move the unit and let a free space behind
         let unit_to_move = std::mem::replace(
             &mut self.entities[from],
@@ -261,19 +251,12 @@ impl World {
         self.entities[to] = unit_to_move;
     }
 
-    fn adjacents(&self, position: usize) -> impl Iterator<Item = (usize, &Entity)> {
-        let height = self.height as isize;
-
-        arrayvec::ArrayVec::from([-height, -1, 1, height])
-            .into_iter()
-            .map(move |delta| {
-                let position = (position as isize + delta) as usize;
-                (position, &self.entities[position])
-            })
+    fn adjacents(&self, position: Pos) -> impl Iterator<Item = (Pos, &Entity)> {
+        self.entities.neighbours4(position)
     }
 
     fn entities_hps(&self) -> u32 {
-        self.entities.iter()
+        self.entities.cells().iter()
             .filter_map(|entity| match entity {
                 Entity::Unit{ unit, .. } => Some(unit.hp),
                 _ => None
@@ -296,7 +279,7 @@ impl UnitKind {
         }
     }
 
-    fn to_entity_with_ap(self, ap: u32) -> Entity {
+    fn into_entity_with_ap(self, ap: u32) -> Entity {
         Entity::Unit { kind: self, unit: Unit::with_ap(ap) }
     }
 }
@@ -306,22 +289,26 @@ impl WorldBuilder {
         let mut elves_count = 0;
         let mut goblins_count = 0;
 
-        let entities = self.parsed_entities.iter()
+        let entities = self.parsed_entities.cells().iter()
             .map(|parsed_entity| match parsed_entity {
                 ParsedEntity::Wall => Entity::Wall,
                 ParsedEntity::OpenCavern => Entity::OpenCavern,
                 ParsedEntity::Elf => {
                     elves_count += 1;
-                    UnitKind::Elf.to_entity_with_ap(ap)
+                    UnitKind::Elf.into_entity_with_ap(ap)
                 },
                 ParsedEntity::Goblin => {
                     goblins_count += 1;
-                    UnitKind::Goblin.to_entity_with_ap(3)
+                    UnitKind::Goblin.into_entity_with_ap(3)
                 },
             })
             .collect();
 
-        World { height: self.height, entities, elves_count, goblins_count }
+        World {
+            entities: Grid::from_cells(self.parsed_entities.width(), entities),
+            elves_count,
+            goblins_count
+        }
     }
 }
 
@@ -386,16 +373,16 @@ mod tests {
         ];
 
         for (input, answer) in tests.iter() {
-            let world_builder = parse_input(&input[..]);
+            let world_builder = parse_input(&input[..]).unwrap();
             assert_eq!(part1(&world_builder), *answer);
         }
-        let world_builder = parse_input(RAW_INPUT);
+        let world_builder = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
         assert_eq!(part1(&world_builder), 229798);
     }
 
     #[test]
     fn p2() {
-        let world_builder = parse_input(RAW_INPUT);
+        let world_builder = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part2(&world_builder), 52972);
     }
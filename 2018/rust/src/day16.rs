@@ -1,9 +1,33 @@
-const RAW_INPUT: &[u8] = include_bytes!("../../inputs/day16.txt");
+use aoc_common::{Solution, ParseError, ParseResult};
 
-pub fn day16() -> (usize, u16) {
-    let (samples, instrs) = parse_input(RAW_INPUT);
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day16.txt");
 
-    (part1(&samples), part2(&samples, &instrs))
+pub struct Day16;
+
+impl Solution for Day16 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 16;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = (Vec<InstructionSample>, Vec<UnknownInstruction>);
+    type Part1 = usize;
+    type Part2 = Value;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        let (samples, _) = input;
+
+        part1(samples)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        let (samples, instrs) = input;
+
+        part2(samples, instrs)
+    }
 }
 
 pub fn part1(samples: &[InstructionSample]) -> usize {
@@ -12,7 +36,7 @@ pub fn part1(samples: &[InstructionSample]) -> usize {
         .count()
 }
 
-use packed_simd::u8x64;
+use crate::simd::u8x64;
 
 const ZEROS: u8x64 = u8x64::splat(0);
 const ONES: u8x64 = u8x64::splat(1);
@@ -52,7 +76,7 @@ pub fn part1_vectorized(samples: &PackedSamples) -> usize {
 }
 
 pub fn part2(samples: &[InstructionSample], program: &[UnknownInstruction]) -> u16 {
-    let mut op_masks = [u16::max_value(); OP_COUNT];
+    let mut op_masks = [u16::MAX; OP_COUNT];
     let mut ops = [Op::AddI; OP_COUNT];
     let mut found = 0;
 
@@ -65,9 +89,9 @@ pub fn part2(samples: &[InstructionSample], program: &[UnknownInstruction]) -> u
                 let op_mask = op_masks[*op as usize];
                 if op_mask.count_ones() == 1 {
 
-                    for i in 0..16 {
+                    for (i, slot) in ops.iter_mut().enumerate() {
                         if op_mask & (1 << i) == op_mask {
-                            ops[i] = *op;
+                            *slot = *op;
                             found += 1;
 
                             if found == OP_COUNT {
@@ -153,7 +177,7 @@ pub fn vectorize(samples: &[InstructionSample]) -> PackedSamples {
         packed.ibs.push(0);
         packed.ras.push(0);
         packed.rbs.push(0);
-        packed.expecteds.push(u8::max_value());
+        packed.expecteds.push(u8::MAX);
     }
 
     packed
@@ -188,62 +212,72 @@ fn execute(instr: Instruction, mut regs: Registers) -> Registers {
     regs
 }
 
-pub fn parse_input(input: &[u8]) -> (Vec<InstructionSample>, Vec<UnknownInstruction>) {
-    let parse_registers = |line: &[u8]| [
-        digit(line[09]),
-        digit(line[12]),
-        digit(line[15]),
-        digit(line[18]),
-    ];
+pub fn parse_input(input: &[u8]) -> ParseResult<(Vec<InstructionSample>, Vec<UnknownInstruction>)> {
+    let parse_registers = |line: &[u8]| Ok([
+        digit(input, line,  9)?,
+        digit(input, line, 12)?,
+        digit(input, line, 15)?,
+        digit(input, line, 18)?,
+    ]);
 
     let parse_raw_instruction = |line: &[u8]| {
         let mut i = 0;
-        let mut op_code = digit(line[0]);
-        if line[1] != b' ' {
-            op_code = op_code * 10 + digit::<u8>(line[1]);
+        let mut op_code = digit(input, line, 0)?;
+        if line.get(1).is_some_and(|&c| c != b' ') {
+            op_code = op_code * 10 + digit::<u8>(input, line, 1)?;
             i = 1;
         }
 
-        UnknownInstruction {
+        Ok(UnknownInstruction {
             op_code,
-            inputs: [digit(line[i+2]), digit(line[i+4])],
-            output: digit(line[i+6]),
-        }
+            inputs: [digit(input, line, i+2)?, digit(input, line, i+4)?],
+            output: digit(input, line, i+6)?,
+        })
     };
 
     let mut lines = input.split(|&c| c == b'\n');
     let mut samples = Vec::with_capacity(2048);
 
     loop {
-        let before = lines.next().unwrap();
+        let before = lines.next()
+            .ok_or_else(|| ParseError::end(input, "a sample or the test program"))?;
 
         if !before.starts_with(b"Before") {
             break
         }
 
-        let raw_instr = lines.next().unwrap();
-        let after = lines.next().unwrap();
+        let raw_instr = lines.next()
+            .ok_or_else(|| ParseError::end(input, "a sample instruction"))?;
+        let after = lines.next()
+            .ok_or_else(|| ParseError::end(input, "the registers after the sample"))?;
         let _empty = lines.next();
 
         let sample = InstructionSample {
-            before: parse_registers(before),
-            after: parse_registers(after),
-            instruction: parse_raw_instruction(raw_instr)
+            before: parse_registers(before)?,
+            after: parse_registers(after)?,
+            instruction: parse_raw_instruction(raw_instr)?
         };
 
         samples.push(sample);
     }
 
     let instrs = lines.skip(1)
+        .filter(|line| !line.is_empty())
         .map(parse_raw_instruction)
-        .collect();
+        .collect::<ParseResult<_>>()?;
 
-    (samples, instrs)
+    Ok((samples, instrs))
 }
 
 use self::Op::*;
 
-fn digit<T: From<u8>>(b: u8) -> T { T::from(b - b'0') }
+fn digit<T: From<u8>>(input: &[u8], line: &[u8], idx: usize) -> ParseResult<T> {
+    match line.get(idx) {
+        Some(b) if b.is_ascii_digit() => Ok(T::from(b - b'0')),
+        Some(_) => Err(ParseError::at(input, &line[idx..=idx], "a digit")),
+        None    => Err(ParseError::after(input, line, "a digit")),
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Op {
@@ -306,14 +340,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let (samples, _) = parse_input(RAW_INPUT);
+        let (samples, _) = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part1(&samples), 542);
     }
 
     #[test]
     fn p1_vectorized() {
-        let (samples, _) = parse_input(RAW_INPUT);
+        let (samples, _) = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
         let vectorized = vectorize(&samples);
 
         assert_eq!(part1_vectorized(&vectorized), 542);
@@ -321,7 +355,7 @@ mod tests {
 
     #[test]
     fn p2() {
-        let (samples, instrs) = parse_input(RAW_INPUT);
+        let (samples, instrs) = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part2(&samples, &instrs), 575);
     }
@@ -1,17 +1,37 @@
 use std::collections::VecDeque;
+use aoc_common::{Solution, ParseError, ParseResult, parse};
+use aoc_grid::{Direction, Grid, Pos};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day17.txt");
 
-pub fn day17() -> (usize, usize) {
-    let ranges: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+pub struct Day17;
 
-    (part1(&ranges), part2(&ranges))
+impl Solution for Day17 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 17;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<ClayRange>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(ranges: &[ClayRange]) -> usize {
     World::new(ranges)
         .fill()
-        .into_iter()
+        .tiles()
         .filter(|&tile| tile == Tile::SettledWater || tile == Tile::FlowingWater)
         .count()
 }
@@ -19,38 +39,42 @@ pub fn part1(ranges: &[ClayRange]) -> usize {
 pub fn part2(ranges: &[ClayRange]) -> usize {
     World::new(ranges)
         .fill()
-        .into_iter()
+        .tiles()
         .filter(|&tile| tile == Tile::SettledWater)
         .count()
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = ClayRange> + '_ {
-    input.split('\n').map(|line| {
+pub fn parse_input(input: &str) -> ParseResult<Vec<ClayRange>> {
+    input.lines().map(|line| {
         let mut coords = line.split(", ");
 
-        let left = coords.next().unwrap();
-        let right = coords.next().unwrap();
+        let left = parse::next(input, &mut coords, line, "a coordinate")?;
+        let right = parse::next(input, &mut coords, line, "a range")?;
+
+        let raw_left = left.get(2..)
+            .ok_or_else(|| ParseError::after(input, left, "a coordinate"))?;
+        let raw_right = right.get(2..)
+            .ok_or_else(|| ParseError::after(input, right, "a range"))?;
 
-        let coord_left = left[2..].parse().unwrap();
-        let mut range_right = right[2..].split("..");
+        let coord_left = parse::field(input, raw_left, "a coordinate")?;
+        let mut range_right = raw_right.split("..");
 
-        let from_right = range_right.next().unwrap().parse().unwrap();
-        let to_right = range_right.next().unwrap().parse().unwrap();
+        let from_right = parse::next_field(input, &mut range_right, raw_right, "a range start")?;
+        let to_right = parse::next_field(input, &mut range_right, raw_right, "a range end")?;
 
-        match line.bytes().next().unwrap() {
-            b'x' => (coord_left..=coord_left, from_right..=to_right),
-            b'y' => (from_right..=to_right, coord_left..=coord_left),
-            invalid => unreachable!("Invalid coord {}", invalid)
+        match (left.as_bytes()[0], right.as_bytes()[0]) {
+            (b'x', b'y') => Ok((coord_left..=coord_left, from_right..=to_right)),
+            (b'y', b'x') => Ok((from_right..=to_right, coord_left..=coord_left)),
+            _ => Err(ParseError::at(input, line, "`x=` and `y=` coordinates")),
         }
-    })
+    }).collect()
 }
 
 use std::ops::RangeInclusive;
 type ClayRange = (RangeInclusive<usize>, RangeInclusive<usize>);
-type Position = (usize, usize);
 
 struct World {
-    map: Vec<Vec<Tile>>,
+    map: Grid<Tile>,
     min_height: usize,
     max_height: usize
 }
@@ -72,20 +96,20 @@ enum HorizontalFlow {
 enum DownwardsFlowResult {
     OutOfBounds,
     MergedWithExistingStream,
-    Reached(Position),
+    Reached(Pos),
 }
 
 #[derive(Clone, Copy)]
 enum HorizontalFlowResult {
-    Stopped(usize),
-    ReachedEdge(usize),
+    Stopped(i32),
+    ReachedEdge(i32),
 }
 
 use self::HorizontalFlow::*;
 use self::DownwardsFlowResult::*;
 use self::HorizontalFlowResult::*;
 
-const SPRING_POSITION: Position = (500, 0);
+const SPRING_POSITION: Pos = Pos { x: 500, y: 0 };
 
 fn is_permeable(tile: Tile) -> bool {
     tile == Tile::Sand || tile == Tile::FlowingWater
@@ -97,13 +121,14 @@ impl World {
         let min_height = *ranges.iter().map(|(_, ys)| ys.start()).min().unwrap();
         let max_height = *ranges.iter().map(|(_, ys)| ys.end()).max().unwrap();
 
-        let mut map = vec![vec![Tile::Sand; max_width+1]; max_height+1];
-        map[0][500] = Tile::Spring;
+        // Keep an extra column on the right for water flowing past the last clay
+        let mut map = Grid::new(max_width+2, max_height+1, Tile::Sand);
+        map[SPRING_POSITION] = Tile::Spring;
 
         for (xs, ys) in ranges {
             for y in ys.clone() {
                 for x in xs.clone() {
-                    map[y][x] = Tile::Clay
+                    map[Pos::new(x as i32, y as i32)] = Tile::Clay
                 }
             }
         }
@@ -116,103 +141,95 @@ impl World {
         streams.push_back(SPRING_POSITION);
 
         while let Some(stream) = streams.pop_front() {
-            let (x, y) = match self.flow_downwards(stream) {
+            let Pos { x, y } = match self.flow_downwards(stream) {
                 // A stream can be stopped when it goes OOB or merges
                 OutOfBounds | MergedWithExistingStream => continue,
                 Reached(new_position) => new_position
             };
 
-            let left_stream = self.flow_horizontally((x, y), Left);
-            let right_stream = self.flow_horizontally((x, y), Right);
+            let left_stream = self.flow_horizontally(Pos { x, y }, Left);
+            let right_stream = self.flow_horizontally(Pos { x, y }, Right);
 
             // If both streams stopped then water can settle
             if let (Stopped(x_left), Stopped(x_right)) = (left_stream, right_stream) {
                 self.settle_water(x_left, x_right, y);
-                streams.push_back((x, y - 1)) // Go back up the current stream
+                streams.push_back(Pos { x, y: y - 1 }) // Go back up the current stream
             }
             // Otherwise add the streams that are not blocked
             if let ReachedEdge(x_left) = left_stream {
-                streams.push_back((x_left, y))
+                streams.push_back(Pos { x: x_left, y })
             }
             if let ReachedEdge(x_right) = right_stream {
-                streams.push_back((x_right, y))
+                streams.push_back(Pos { x: x_right, y })
             }
         }
 
         self
     }
 
-    fn flow_downwards(&mut self, (x, mut y): Position) -> DownwardsFlowResult {
-        let start_y = y;
+    fn flow_downwards(&mut self, mut pos: Pos) -> DownwardsFlowResult {
+        let start_y = pos.y;
 
         loop {
-            if y >= self.max_height {
-                self.map[y][x] = Tile::FlowingWater;
+            if pos.y as usize >= self.max_height {
+                self.map[pos] = Tile::FlowingWater;
                 return OutOfBounds
             }
 
-            let tile_below = self.at((x, y+1));
+            let tile_below = self.map[pos + Direction::Down];
 
             if is_permeable(tile_below) {
-                self.map[y][x] = Tile::FlowingWater;
-                y += 1;
+                self.map[pos] = Tile::FlowingWater;
+                pos += Direction::Down;
             } else {
-                if y > start_y && self.at((x, y)) == Tile::FlowingWater {
+                if pos.y > start_y && self.map[pos] == Tile::FlowingWater {
                     return MergedWithExistingStream
                 } else {
-                    return Reached((x, y))
+                    return Reached(pos)
                 }
             }
         }
     }
 
-    fn flow_horizontally(&mut self, (mut x, y): Position, direction: HorizontalFlow)
+    fn flow_horizontally(&mut self, mut pos: Pos, direction: HorizontalFlow)
         -> HorizontalFlowResult
     {
-        let moved = |x| match direction {
-            Left => x - 1,
-            Right => x + 1
+        let direction = match direction {
+            Left => Direction::Left,
+            Right => Direction::Right
         };
 
-        self.map[y][x] = Tile::FlowingWater;
+        self.map[pos] = Tile::FlowingWater;
 
         loop {
-            let next_x = moved(x);
-            let next_tile = self.at((next_x, y));
+            let next_pos = pos + direction;
 
-            if is_permeable(next_tile) {
-                x = next_x;
-                self.map[y][x] = Tile::FlowingWater;
+            if is_permeable(self.map[next_pos]) {
+                pos = next_pos;
+                self.map[pos] = Tile::FlowingWater;
             }
             else {
-                return Stopped(x)
+                return Stopped(pos.x)
             }
 
-            let tile_below = self.at((x, y+1));
+            let tile_below = self.map[pos + Direction::Down];
 
             if is_permeable(tile_below) {
-                return ReachedEdge(x)
+                return ReachedEdge(pos.x)
             }
         }
     }
 
-    fn settle_water(&mut self, from_x: usize, to_x: usize, y: usize) {
-        let settled_water = vec![Tile::SettledWater; to_x - from_x + 1];
-        self.map[y][from_x..=to_x].copy_from_slice(&settled_water);
-    }
-
-    fn at(&mut self, (x, y): Position) -> Tile {
-        let row = &mut self.map[y];
-        if x == row.len() {
-            row.push(Tile::Sand)
+    fn settle_water(&mut self, from_x: i32, to_x: i32, y: i32) {
+        for x in from_x..=to_x {
+            self.map[Pos { x, y }] = Tile::SettledWater
         }
-        row[x]
     }
 
-    fn into_iter(self) -> impl Iterator<Item = Tile> {
-        self.map.into_iter()
+    fn tiles(&self) -> impl Iterator<Item = Tile> + '_ {
+        self.map.rows()
             .skip(self.min_height)
-            .flat_map(|rows| rows)
+            .flat_map(|row| row.iter().copied())
     }
 }
 
@@ -221,14 +238,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let ranges: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let ranges = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&ranges), 30737);
     }
 
     #[test]
     fn p2() {
-        let ranges: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let ranges = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&ranges), 24699);
     }
@@ -1,9 +1,29 @@
-const RAW_INPUT: &[u8] = include_bytes!("../../inputs/day18.txt");
+use aoc_common::{Solution, ParseError, ParseResult, cycle};
 
-pub fn day18() -> (u32, u32) {
-    let world = parse_input(RAW_INPUT);
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day18.txt");
 
-    (part1(&world), part2(&world))
+pub struct Day18;
+
+impl Solution for Day18 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 18;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = World;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(world: &World) -> u32 {
@@ -13,65 +33,42 @@ pub fn part1(world: &World) -> u32 {
 }
 
 pub fn part2(world: &World) -> u32 {
-    let (cycle_len, cycle_start, world_after_cycle) = floyd(
-        world.to_simd_world(),
-        SimdWorld::tick,
-        PartialEq::eq
-    );
-
-    let remaining_steps = (1_000_000_000 - cycle_start) % cycle_len;
-
-    (0..remaining_steps)
-        .fold(world_after_cycle, |world, _| world.tick())
+    cycle::floyd(world.to_simd_world(), SimdWorld::tick, PartialEq::eq)
+        .state_at(1_000_000_000, SimdWorld::tick)
         .resource_value()
 }
 
-fn floyd<T: Clone, F: Fn(T) -> T, Cmp: Fn(&T, &T) -> bool>(x0: T, f: F, cmp: Cmp)
-    -> (usize, usize, T)
-{
-    let mut tortoise = f(x0.clone());
-    let mut hare = f(f(x0.clone()));
+// The simulation works on a fixed size area
+const SIZE: usize = 50;
 
-    while !cmp(&tortoise, &hare) {
-        tortoise = f(tortoise);
-        hare = f(f(hare));
-    }
+pub fn parse_input(input: &[u8]) -> ParseResult<World> {
+    let lines: Vec<_> = input.strip_suffix(b"\n")
+        .unwrap_or(input)
+        .split(|&c| c == b'\n')
+        .collect();
 
-    let mut mu = 0;
-    let mut tortoise = x0;
-    while !cmp(&tortoise, &hare) {
-        tortoise = f(tortoise);
-        hare = f(hare);
-        mu += 1
+    if let Some(line) = lines.iter().find(|line| line.len() != SIZE) {
+        return Err(ParseError::at(input, *line, format!("a row of {} acres", SIZE)));
     }
-
-    let after_cycle = hare;
-    let mut lam = 1;
-    let mut hare = f(tortoise.clone());
-    while !cmp(&tortoise, &hare) {
-        hare = f(hare);
-        lam += 1
+    if lines.len() != SIZE {
+        return Err(ParseError::end(input, format!("{} rows of acres", SIZE)));
     }
 
-    (lam, mu, after_cycle)
-}
-
-pub fn parse_input(input: &[u8]) -> World {
-    let tiles = input.split(|&c| c == b'\n')
+    let tiles = lines.into_iter()
         .flat_map(|line| {
-            line.iter().map(|c| match c {
-                b'.' => Tile::OpenGround,
-                b'|' => Tile::Tree,
-                b'#' => Tile::Lumberyard,
-                invalid => panic!("Invalid tile byte: {}", invalid)
+            (0..line.len()).map(move |x| match line[x] {
+                b'.' => Ok(Tile::OpenGround),
+                b'|' => Ok(Tile::Tree),
+                b'#' => Ok(Tile::Lumberyard),
+                _ => Err(ParseError::at(input, &line[x..=x], "an acre (`.`, `|` or `#`)"))
             })
         })
-        .collect();
+        .collect::<ParseResult<_>>()?;
 
-    World { tiles }
+    Ok(World { tiles })
 }
 
-use packed_simd::{u8x64, shuffle, m8x64};
+use crate::simd::{u8x64, shuffle, m8x64};
 
 #[derive(Clone)]
 pub struct SimdWorld {
@@ -231,15 +228,24 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let world = parse_input(RAW_INPUT);
+        let world = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part1(&world), 582494);
     }
 
     #[test]
     fn p2() {
-        let world = parse_input(RAW_INPUT);
+        let world = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part2(&world), 174584);
     }
+
+    #[test]
+    fn truncated() {
+        let short_row = RAW_INPUT_STR.replacen('\n', "\n.\n", 1);
+        assert_eq!(parse_input(short_row.as_bytes()).unwrap_err().line, 2);
+
+        let rows: Vec<_> = RAW_INPUT_STR.lines().take(49).collect();
+        assert_eq!(parse_input(rows.join("\n").as_bytes()).unwrap_err().line, 49);
+    }
 }
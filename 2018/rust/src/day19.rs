@@ -1,9 +1,33 @@
-const RAW_INPUT: &[u8] = include_bytes!("../../inputs/day19.txt");
+use aoc_common::{Solution, ParseError, ParseResult};
 
-pub fn day19() -> (Value, Value) {
-    let (pc_idx, instrs) = parse_input(RAW_INPUT);
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day19.txt");
 
-    (part1(pc_idx, &instrs), part2(pc_idx, &instrs))
+pub struct Day19;
+
+impl Solution for Day19 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 19;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = (usize, Vec<Instruction>);
+    type Part1 = Value;
+    type Part2 = Value;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input.as_bytes())
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        let (pc_idx, instrs) = input;
+
+        part1(*pc_idx, instrs)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        let (pc_idx, instrs) = input;
+
+        part2(*pc_idx, instrs)
+    }
 }
 
 pub fn part1(pc_idx: usize, instructions: &[Instruction]) -> Value {
@@ -17,9 +41,7 @@ pub fn part1(pc_idx: usize, instructions: &[Instruction]) -> Value {
 pub fn part2(pc_idx: usize, instructions: &[Instruction]) -> Value {
     let initial_registers = [1, 0, 0, 0, 0, 0];
 
-    let registers_after_setup = execute(pc_idx, instructions, initial_registers)
-        .skip(100)
-        .next()
+    let registers_after_setup = execute(pc_idx, instructions, initial_registers).nth(100)
         .expect("Empty program");
 
     divisors(registers_after_setup[4]).sum()
@@ -60,7 +82,7 @@ fn divisors(n: Value) -> impl Iterator<Item = Value> {
     use arrayvec::ArrayVec;
 
     (1..(n as f32).sqrt() as Value)
-        .filter(move |x| n % x == 0)
+        .filter(move |x| n.is_multiple_of(*x))
         .flat_map(move |x| {
             let mut divisors = ArrayVec::<[_; 2]>::new();
             divisors.push(x);
@@ -69,16 +91,20 @@ fn divisors(n: Value) -> impl Iterator<Item = Value> {
         })
 }
 
-pub fn parse_input(input: &[u8]) -> (usize, Vec<Instruction>) {
+pub fn parse_input(input: &[u8]) -> ParseResult<(usize, Vec<Instruction>)> {
     let mut lines = input.split(|&c| c == b'\n');
 
-    let pc_idx = usize::from(lines.next().unwrap()[4] - b'0');
+    let pc_idx = match lines.next() {
+        Some([b'#', b'i', b'p', b' ', digit]) if digit.is_ascii_digit() => usize::from(digit - b'0'),
+        _ => return Err(ParseError::new(input, 0, "an instruction pointer binding like `#ip 3`")),
+    };
 
     let instructions = lines
+        .filter(|line| !line.is_empty())
         .map(|line| {
             let mut tokens = line.split(|&c| c == b' ');
 
-            let mnemonic = tokens.next().unwrap();
+            let mnemonic = tokens.next().unwrap_or(line);
             let op: Op = match mnemonic {
                 b"addr" => |regs, a, b| regs[a] + regs[b],
                 b"addi" => |regs, a, b| regs[a] + b,
@@ -96,26 +122,31 @@ pub fn parse_input(input: &[u8]) -> (usize, Vec<Instruction>) {
                 b"eqir" => |regs, a, b| if a == regs[b] { 1 } else { 0 } ,
                 b"eqri" => |regs, a, b| if regs[a] == b { 1 } else { 0 } ,
                 b"eqrr" => |regs, a, b| if regs[a] == regs[b] { 1 } else { 0 },
-                invalid => panic!("Invalid op mnemonic: {:?}", invalid)
+                _ => return Err(ParseError::at(input, mnemonic, "an op mnemonic"))
             };
 
-            let mut num_tokens = tokens
-                .map(|tok| std::str::from_utf8(tok).unwrap())
-                .map(|tok_s| tok_s.parse().unwrap());
+            let mut num_token = || {
+                let token = tokens.next()
+                    .ok_or_else(|| ParseError::after(input, line, "an operand"))?;
 
-            let a = num_tokens.next().unwrap();
-            let b = num_tokens.next().unwrap();
-            let c = num_tokens.next().unwrap();
+                std::str::from_utf8(token).ok()
+                    .and_then(|token| token.parse().ok())
+                    .ok_or_else(|| ParseError::at(input, token, "an operand"))
+            };
+
+            let a = num_token()?;
+            let b = num_token()?;
+            let c = num_token()?;
 
-            Instruction {
+            Ok(Instruction {
                 op,
                 inputs: [a, b],
                 output: c,
-            }
+            })
         })
-        .collect();
+        .collect::<ParseResult<_>>()?;
 
-    (pc_idx, instructions)
+    Ok((pc_idx, instructions))
 }
 
 type Value = usize;
@@ -133,14 +164,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let (pc_idx, instrs) = parse_input(RAW_INPUT);
+        let (pc_idx, instrs) = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part1(pc_idx, &instrs), 1968);
     }
 
     #[test]
     fn p2() {
-        let (pc_idx, instrs) = parse_input(RAW_INPUT);
+        let (pc_idx, instrs) = parse_input(RAW_INPUT_STR.as_bytes()).unwrap();
 
         assert_eq!(part2(pc_idx, &instrs), 21211200);
     }
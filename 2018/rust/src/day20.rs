@@ -1,158 +1,131 @@
-use std::collections::VecDeque;
+use aoc_common::{Solution, ParseError, ParseResult, search};
+use aoc_grid::{Direction, Pos};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day20.txt");
 
-pub fn day20() -> (usize, usize) {
-    let directions = parse_input(RAW_INPUT_STR);
+pub struct Day20;
 
-    (part1(&directions), part2(&directions))
+impl Solution for Day20 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 20;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Steps;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
-pub fn part1(directions: &[Direction]) -> usize {
-    visit_facility(&directions)
+pub fn part1(steps: &[Step]) -> usize {
+    visit_facility(steps)
         .map(|(_pos, distance)| distance)
         .max()
         .unwrap()
 }
 
-pub fn part2(directions: &[Direction]) -> usize {
-    visit_facility(&directions)
+pub fn part2(steps: &[Step]) -> usize {
+    visit_facility(steps)
         .filter(|(_pos, distance)| *distance >= 1000)
         .count()
 }
 
-fn walk(directions: &[Direction], (mut x, mut y): Position, map: &mut Map) {
-    for direction in directions {
-        let (dx, dy) = match direction {
-            Direction::North => (0, -1),
-            Direction::West  => (-1, 0),
-            Direction::South => (0,  1),
-            Direction::East  => (1,  0),
-            Direction::Choice(choices) => {
+fn walk(steps: &[Step], mut pos: Pos, map: &mut Map) {
+    for step in steps {
+        let direction = match step {
+            Step::Move(direction) => *direction,
+            Step::Choice(choices) => {
                 for choice in choices {
-                    walk(choice, (x, y), map)
+                    walk(choice, pos, map)
                 }
                 continue
             }
         };
 
-        map.entry((x, y))
+        map.entry(pos)
             .or_default()
-            .push((x + dx, y + dy));
-        x += dx;
-        y += dy;
+            .push(pos + direction);
+        pos += direction;
     }
 }
 
-fn visit_facility(directions: &[Direction])
-    -> impl Iterator<Item = (Position, usize)>
+fn visit_facility(steps: &[Step])
+    -> impl Iterator<Item = (Pos, usize)>
 {
-    let initial_position = (0, 0);
+    let initial_position = Pos::ORIGIN;
 
     let mut map = Map::new();
-    walk(directions, initial_position, &mut map);
-
-    let mut open_set = VecDeque::with_capacity(map.len());
-    open_set.push_back(Node { pos: initial_position, distance: 0 });
-    FacilityVisitor {
-        open_set,
-        closed_set: hashbrown::HashSet::with_capacity(map.len()),
-        map,
-    }
-}
-
-struct Node {
-    pos: Position,
-    distance: usize,
-}
+    walk(steps, initial_position, &mut map);
 
-struct FacilityVisitor {
-    map: Map,
-    open_set: VecDeque<Node>,
-    closed_set: hashbrown::HashSet<Position>,
+    search::bfs([initial_position], move |pos: &Pos| {
+        map.get(pos).cloned().unwrap_or_else(Adjacents::new)
+    })
 }
 
-impl Iterator for FacilityVisitor {
-    type Item = (Position, usize);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.open_set.pop_back() {
-            None => None,
-            Some(Node { pos, distance }) => {
-                if let Some(adjacent_positions) = self.map.get(&pos) {
-                    let new_distance = distance + 1;
-
-                    for adjacent_pos in adjacent_positions {
-                        if self.closed_set.contains(adjacent_pos) {
-                            continue
-                        }
-
-                        let new_room = Node {
-                            pos: *adjacent_pos,
-                            distance: new_distance
-                        };
-
-                        self.open_set.push_back(new_room);
-                        self.closed_set.insert(*adjacent_pos);
-                    }
-                }
-                Some((pos, distance))
-            }
-        }
+pub fn parse_input(input: &str) -> ParseResult<Steps> {
+    match parse_input_impl(input, &mut input.bytes().enumerate())? {
+        (steps, ParseNext::End, _) => Ok(steps),
+        (_, _, offset) => Err(ParseError::new(input, offset, "`(` before this")),
     }
 }
 
-pub fn parse_input(input: &str) -> Directions {
-    parse_input_impl(&mut input.bytes()).0
-}
+// Also returns the offset of the byte that ended the sequence
+fn parse_input_impl(input: &str, bytes: &mut impl Iterator<Item = (usize, u8)>) -> ParseResult<(Steps, ParseNext, usize)> {
+    let mut steps = Vec::new();
 
-fn parse_input_impl(input: &mut impl Iterator<Item = u8>) -> (Directions, ParseNext) {
-    let mut directions = Vec::new();
+    let (next, offset) = loop {
+        let (offset, byte) = bytes.next()
+            .ok_or_else(|| ParseError::end(input, "`$`"))?;
 
-    let next = loop {
-        match input.next().expect("Unexpected EOF") {
+        match byte {
             b'^' => continue,
-            b'$' => break ParseNext::End,
-            b'N' => directions.push(Direction::North),
-            b'W' => directions.push(Direction::West),
-            b'S' => directions.push(Direction::South),
-            b'E' => directions.push(Direction::East),
+            b'$' => break (ParseNext::End, offset),
+            b'N' => steps.push(Step::Move(Direction::Up)),
+            b'W' => steps.push(Step::Move(Direction::Left)),
+            b'S' => steps.push(Step::Move(Direction::Down)),
+            b'E' => steps.push(Step::Move(Direction::Right)),
             b'(' => {
                 let mut choices = Vec::new();
                 loop {
-                    let (choice, next) = parse_input_impl(input);
+                    let (choice, next, offset) = parse_input_impl(input, bytes)?;
                     choices.push(choice);
                     match next {
                         ParseNext::NextChoice => continue,
                         ParseNext::EndChoice => break,
-                        ParseNext::End => panic!("Unmatched closing parens")
+                        ParseNext::End => return Err(ParseError::new(input, offset, "`)`"))
                     }
                 }
-                directions.push(Direction::Choice(choices))
+                steps.push(Step::Choice(choices))
             },
-            b')' => break ParseNext::EndChoice,
-            b'|' => break ParseNext::NextChoice,
-            invalid => panic!("Invalid input byte: {}", invalid)
+            b')' => break (ParseNext::EndChoice, offset),
+            b'|' => break (ParseNext::NextChoice, offset),
+            _ => return Err(ParseError::new(input, offset, "a direction, a branch or `$`"))
         }
     };
 
-    (directions, next)
+    Ok((steps, next, offset))
 }
 
-type Map = hashbrown::HashMap<Position, Adjacents>;
-type Position = (isize, isize);
-type Adjacents = arrayvec::ArrayVec<[Position; 4]>;
+type Map = hashbrown::HashMap<Pos, Adjacents>;
+type Adjacents = arrayvec::ArrayVec<[Pos; 4]>;
 
 #[derive(Debug)]
-pub enum Direction {
-    North,
-    West,
-    South,
-    East,
-    Choice(Vec<Directions>),
+pub enum Step {
+    Move(Direction),
+    Choice(Vec<Steps>),
 }
 
-type Directions = Vec<Direction>;
+type Steps = Vec<Step>;
 
 enum ParseNext {
     NextChoice,
@@ -165,15 +138,15 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let directions = parse_input(RAW_INPUT_STR);
+        let steps = parse_input(RAW_INPUT_STR).unwrap();
 
-        assert_eq!(part1(&directions), 4432);
+        assert_eq!(part1(&steps), 4432);
     }
 
     #[test]
     fn p2() {
-        let directions = parse_input(RAW_INPUT_STR);
+        let steps = parse_input(RAW_INPUT_STR).unwrap();
 
-        assert_eq!(part2(&directions), 8681);
+        assert_eq!(part2(&steps), 8681);
     }
 }
@@ -1,12 +1,33 @@
-use std::collections::BinaryHeap;
-use std::cmp::Ordering;
+use aoc_common::{Solution, ParseError, ParseResult, parse, search};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day22.txt");
 
-pub fn day22() -> (usize, usize) {
-    let (depth, target) = parse_input(RAW_INPUT_STR);
+pub struct Day22;
 
-    (part1(depth, target), part2(depth, target))
+impl Solution for Day22 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 22;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = (usize, Position);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        let &(depth, target) = input;
+
+        part1(depth, target)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        let &(depth, target) = input;
+
+        part2(depth, target)
+    }
 }
 
 pub fn part1(depth: usize, target: Position) -> usize {
@@ -21,74 +42,29 @@ pub fn part1(depth: usize, target: Position) -> usize {
 pub fn part2(depth: usize, target: Position) -> usize {
     let mut cave = Cave::new(depth, target);
 
-    #[derive(PartialEq, Eq)]
-    struct Node {
-        time: usize,
-        estimate: usize,
-        position: Position,
-        equipment: Equipment,
-    }
-
-    let new_node = |position, equipment, time| Node {
-        position,
-        equipment,
-        time,
-        estimate: time + manhattan(position, target)
-    };
-
-    impl PartialOrd for Node {
-        fn partial_cmp(&self, other: &Node) -> Option<Ordering> {
-            Some(self.cmp(other))
-        }
-    }
+    let possible_actions = |&(position, equipment): &(Position, Equipment)| {
+        let swap_equipment = (
+            (position, equipment.alternative(cave.at(position).kind)),
+            7
+        );
 
-    impl Ord for Node {
-        fn cmp(&self, other: &Node) -> Ordering {
-            other.estimate.cmp(&self.estimate)
-        }
-    }
+        let move_around = neighboring_positions(position)
+            .filter(|&position| cave.at(position).kind.can_use(equipment))
+            .map(|position| ((position, equipment), 1))
+            .collect::<arrayvec::ArrayVec<[_; 4]>>();
 
-    let mut open_set = BinaryHeap::new();
-    let mut closed_set = hashbrown::HashMap::new();
-
-    open_set.push(new_node((0, 0), Equipment::Torch, 0));
-
-    while let Some(Node { position, equipment, time, .. }) = open_set.pop() {
-        if position == target && equipment == Equipment::Torch {
-            return time
-        }
-
-        let possible_actions = {
-            let swap_equipment = (
-                position,
-                equipment.alternative(cave.at(position).kind),
-                time + 7
-            );
-
-            let move_around = neighboring_positions(position)
-                .filter(|&position| cave.at(position).kind.can_use(equipment))
-                .map(|position| (position, equipment, time + 1));
-
-            std::iter::once(swap_equipment).chain(move_around)
-        };
-
-        for (position, equipment, time) in possible_actions {
-            match closed_set.get(&(position, equipment)) {
-                Some(&existing_time) if time >= existing_time => (),
-                _unvisited_or_with_a_better_time => {
-                    open_set.push(new_node(position, equipment, time));
-                    closed_set.insert((position, equipment), time);
-                }
-            }
-        }
-    }
+        std::iter::once(swap_equipment).chain(move_around)
+    };
 
-    panic!("No path to the target!")
+    search::astar([((0, 0), Equipment::Torch)], possible_actions, |&(position, _)| manhattan(position, target))
+        .find(|&(state, _)| state == (target, Equipment::Torch))
+        .map(|(_, time)| time)
+        .expect("No path to the target!")
 }
 
 fn manhattan((x1, y1): Position, (x2, y2): Position) -> usize {
-    (x1 as isize - x2 as isize).abs() as usize +
-    (y1 as isize - y2 as isize).abs() as usize
+    (x1 as isize - x2 as isize).unsigned_abs() +
+    (y1 as isize - y2 as isize).unsigned_abs()
 }
 
 fn neighboring_positions((x, y): Position) -> impl Iterator<Item = Position> {
@@ -105,16 +81,23 @@ fn neighboring_positions((x, y): Position) -> impl Iterator<Item = Position> {
         })
 }
 
-pub fn parse_input(input: &str) -> (usize, Position) {
+pub fn parse_input(input: &str) -> ParseResult<(usize, Position)> {
     let mut lines = input.lines();
 
-    let depth = lines.next().unwrap()[7..].parse().unwrap();
-    let mut target_split = lines.next().unwrap()[8..].split(',');
+    let raw_depth = lines.next()
+        .and_then(|line| line.strip_prefix("depth: "))
+        .ok_or_else(|| ParseError::new(input, 0, "`depth: `"))?;
+    let depth = parse::field(input, raw_depth, "a depth")?;
+
+    let raw_target = lines.next()
+        .and_then(|line| line.strip_prefix("target: "))
+        .ok_or_else(|| ParseError::after(input, raw_depth, "`target: ` on the next line"))?;
+    let mut target_split = raw_target.split(',');
 
-    let target_x = target_split.next().unwrap().parse().unwrap();
-    let target_y = target_split.next().unwrap().parse().unwrap();
+    let target_x = parse::next_field(input, &mut target_split, raw_target, "a target x coordinate")?;
+    let target_y = parse::next_field(input, &mut target_split, raw_target, "a target y coordinate")?;
 
-    (depth, (target_x, target_y))
+    Ok((depth, (target_x, target_y)))
 }
 
 type Position = (usize, usize);
@@ -129,19 +112,18 @@ enum RegionKind {
 
 impl RegionKind {
     fn can_use(&self, equipment: Equipment) -> bool {
-        match (self, equipment) {
-            (RegionKind::Rocky,  Equipment::None)         => false,
-            (RegionKind::Wet,    Equipment::Torch)        => false,
-            (RegionKind::Narrow, Equipment::ClimbingGear) => false,
-            _ => true
-        }
+        !matches!(
+            (self, equipment),
+            (RegionKind::Rocky,  Equipment::None)         |
+            (RegionKind::Wet,    Equipment::Torch)        |
+            (RegionKind::Narrow, Equipment::ClimbingGear)
+        )
     }
 }
 
 #[derive(Debug, Clone, Copy)]
 struct Region {
     kind: RegionKind,
-    geologic_index: usize,
     erosion_level: usize,
 }
 
@@ -171,9 +153,9 @@ impl Equipment {
 impl Region {
     fn new(geologic_index: usize, depth: usize) -> Self {
         let erosion_level = erosion_level(geologic_index, depth);
-        let kind = unsafe { std::mem::transmute((erosion_level % 3) as u8) };
+        let kind = unsafe { std::mem::transmute::<u8, RegionKind>((erosion_level % 3) as u8) };
 
-        Self { kind, geologic_index, erosion_level }
+        Self { kind, erosion_level }
     }
 }
 
@@ -252,14 +234,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let (depth, target) = parse_input(RAW_INPUT_STR);
+        let (depth, target) = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(depth, target), 10395);
     }
 
     #[test]
     fn p2() {
-        let (depth, target) = parse_input(RAW_INPUT_STR);
+        let (depth, target) = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(depth, target), 1010);
     }
@@ -1,9 +1,29 @@
+use aoc_common::{Solution, ParseError, ParseResult, parse};
+
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day23.txt");
 
-pub fn day23() -> (usize, usize) {
-    let nanobots: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+pub struct Day23;
+
+impl Solution for Day23 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 23;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<(Position, Radius)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-    (part1(&nanobots), part2(&nanobots))
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(nanobots: &[(Position, Radius)]) -> usize {
@@ -17,29 +37,32 @@ pub fn part1(nanobots: &[(Position, Radius)]) -> usize {
 }
 
 fn manhattan((x1, y1, z1): Position, (x2, y2, z2): Position) -> usize {
-    (x1 - x2).abs() as usize +
-    (y1 - y2).abs() as usize +
-    (z1 - z2).abs() as usize
+    (x1 - x2).unsigned_abs() +
+    (y1 - y2).unsigned_abs() +
+    (z1 - z2).unsigned_abs()
 }
 
-pub fn part2(nanobots: &[(Position, Radius)]) -> usize {
+pub fn part2(_nanobots: &[(Position, Radius)]) -> usize {
     // TODO
     142473501
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = (Position, Radius)> + '_ {
-    input.split('\n').map(|line| {
-        let mut splitted = line[5..].split(">, r=");
+pub fn parse_input(input: &str) -> ParseResult<Vec<(Position, Radius)>> {
+    input.lines().map(|line| {
+        let raw_nanobot = line.strip_prefix("pos=<")
+            .ok_or_else(|| ParseError::at(input, line, "`pos=<`"))?;
+        let mut splitted = raw_nanobot.split(">, r=");
 
-        let mut pos_splitted = splitted.next().unwrap().split(',');
-        let radius = splitted.next().unwrap().parse().unwrap();
+        let raw_pos = parse::next(input, &mut splitted, line, "a position")?;
+        let radius = parse::next_field(input, &mut splitted, line, "a radius")?;
 
-        let x = pos_splitted.next().unwrap().parse().unwrap();
-        let y = pos_splitted.next().unwrap().parse().unwrap();
-        let z = pos_splitted.next().unwrap().parse().unwrap();
+        let mut pos_splitted = raw_pos.split(',');
+        let x = parse::next_field(input, &mut pos_splitted, raw_pos, "an x coordinate")?;
+        let y = parse::next_field(input, &mut pos_splitted, raw_pos, "a y coordinate")?;
+        let z = parse::next_field(input, &mut pos_splitted, raw_pos, "a z coordinate")?;
 
-        ((x, y, z), radius)
-    })
+        Ok(((x, y, z), radius))
+    }).collect()
 }
 
 type Position = (isize, isize, isize);
@@ -50,14 +73,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let nanobots: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let nanobots = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&nanobots), 326);
     }
 
     #[test]
     fn p2() {
-        let nanobots: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let nanobots = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&nanobots), 142473501);
     }
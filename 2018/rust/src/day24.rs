@@ -1,12 +1,31 @@
 use std::cmp::Reverse;
 use rayon::prelude::*;
+use aoc_common::{Solution, ParseError, ParseResult, parse};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day24.txt");
 
-pub fn day24() -> (u32, u32) {
-    let groups: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+pub struct Day24;
 
-    (part1(&groups), part2(&groups))
+impl Solution for Day24 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 24;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Group>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(groups: &[Group]) -> u32 {
@@ -112,52 +131,61 @@ enum FightResult {
     Draw,
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Group> + '_ {
+pub fn parse_input(input: &str) -> ParseResult<Vec<Group>> {
     let mut groups = input.split("\n\n");
 
-    let immune_system = groups.next().unwrap().split('\n').skip(1);
-    let infection = groups.next().unwrap().split('\n').skip(1);
+    let immune_system = parse::next(input, &mut groups, input, "the immune system army")?
+        .lines().skip(1);
+    let infection = parse::next(input, &mut groups, input, "the infection army")?
+        .lines().skip(1);
 
-    immune_system.map(|raw| parse_group(raw, ArmyKind::ImmuneSystem))
-        .chain(infection.map(|raw| parse_group(raw, ArmyKind::Infection)))
+    immune_system.map(|raw| parse_group(input, raw, ArmyKind::ImmuneSystem))
+        .chain(infection.map(|raw| parse_group(input, raw, ArmyKind::Infection)))
+        .collect()
 }
 
-fn parse_group(input: &str, army: ArmyKind) -> Group {
-    let mut split = input.split(" units each with ");
-    let units = split.next().unwrap().parse().unwrap();
+fn parse_group(input: &str, raw_group: &str, army: ArmyKind) -> ParseResult<Group> {
+    let mut split = raw_group.split(" units each with ");
+    let units = parse::next_field(input, &mut split, raw_group, "a unit count")?;
 
-    let mut split = split.next().unwrap().split(" hit points ");
-    let hp = split.next().unwrap().parse().unwrap();
+    let mut split = parse::next(input, &mut split, raw_group, "` units each with `")?.split(" hit points ");
+    let hp = parse::next_field(input, &mut split, raw_group, "a hit point count")?;
 
-    let mut split = split.next().unwrap().split("with an attack that does ");
-    let raw_resistances = split.next().unwrap();
+    let mut split = parse::next(input, &mut split, raw_group, "` hit points `")?.split("with an attack that does ");
+    let raw_resistances = parse::next(input, &mut split, raw_group, "resistances")?;
 
     let mut weaknesses = Vec::new();
     let mut immunities = Vec::new();
 
-    if raw_resistances.starts_with("(") {
-        let mut resistances = raw_resistances[1..raw_resistances.len()-2].split("; ");
+    if let Some(raw_resistances) = raw_resistances.strip_prefix('(') {
+        let raw_resistances = raw_resistances.strip_suffix(") ")
+            .ok_or_else(|| ParseError::after(input, raw_resistances, "`) `"))?;
 
-        while let Some(resistance) = resistances.next() {
-            if resistance.starts_with("weak") {
-                weaknesses.extend(resistance[8..].split(", ")
-                    .map(DamageKind::from))
+        for resistance in raw_resistances.split("; ") {
+            let (resistances, raw_kinds) = if let Some(raw_kinds) = resistance.strip_prefix("weak to ") {
+                (&mut weaknesses, raw_kinds)
+            } else if let Some(raw_kinds) = resistance.strip_prefix("immune to ") {
+                (&mut immunities, raw_kinds)
             } else {
-                immunities.extend(resistance[10..].split(", ")
-                    .map(DamageKind::from))
+                return Err(ParseError::at(input, resistance, "`weak to ` or `immune to `"))
+            };
+
+            for raw_kind in raw_kinds.split(", ") {
+                resistances.push(DamageKind::parse(input, raw_kind)?)
             }
         }
     }
 
-    let mut split = split.next().unwrap().split(" damage at initiative ");
-    let mut raw_damage = split.next().unwrap().split(' ');
+    let mut split = parse::next(input, &mut split, raw_group, "an attack")?.split(" damage at initiative ");
+    let raw_attack = parse::next(input, &mut split, raw_group, "an attack")?;
+    let mut raw_damage = raw_attack.split(' ');
 
-    let damage_value = raw_damage.next().unwrap().parse().unwrap();
-    let damage_kind = raw_damage.next().unwrap().into();
+    let damage_value = parse::next_field(input, &mut raw_damage, raw_attack, "a damage value")?;
+    let damage_kind = DamageKind::parse(input, parse::next(input, &mut raw_damage, raw_attack, "a damage kind")?)?;
 
-    let initiative = split.next().unwrap().parse().unwrap();
+    let initiative = parse::next_field(input, &mut split, raw_group, "an initiative")?;
 
-    Group {
+    Ok(Group {
         army,
         units,
         hp,
@@ -165,7 +193,7 @@ fn parse_group(input: &str, army: ArmyKind) -> Group {
         initiative,
         weaknesses,
         immunities
-    }
+    })
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -219,15 +247,15 @@ impl Group {
     }
 }
 
-impl From<&str> for DamageKind {
-    fn from(input: &str) -> Self {
-        match input {
-            "bludgeoning" => DamageKind::Bludgeoning,
-            "cold" => DamageKind::Cold,
-            "fire" => DamageKind::Fire,
-            "radiation" => DamageKind::Radiation,
-            "slashing" => DamageKind::Slashing,
-            unknown => panic!("unknown damage kind: {}", unknown)
+impl DamageKind {
+    fn parse(input: &str, raw_kind: &str) -> ParseResult<Self> {
+        match raw_kind {
+            "bludgeoning" => Ok(DamageKind::Bludgeoning),
+            "cold" => Ok(DamageKind::Cold),
+            "fire" => Ok(DamageKind::Fire),
+            "radiation" => Ok(DamageKind::Radiation),
+            "slashing" => Ok(DamageKind::Slashing),
+            _ => Err(ParseError::at(input, raw_kind, "a damage kind"))
         }
     }
 }
@@ -237,14 +265,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let groups: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let groups = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&groups), 22676);
     }
 
     #[test]
     fn p2() {
-        let groups: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let groups = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&groups), 4510);
     }
@@ -1,9 +1,30 @@
+use aoc_common::{Solution, ParseResult, parse};
+
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day25.txt");
 
-pub fn day25() -> u32 {
-    let points: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+pub struct Day25;
+
+impl Solution for Day25 {
+    const YEAR: u32 = 2018;
+    const DAY: u32 = 25;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+    const HAS_PART2: bool = false;
+
+    type Parsed<'a> = Vec<Point>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
 
-    part1(&points)
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(_input: &Self::Parsed<'_>) -> Self::Part2 {
+        unreachable!("Day 25 has no second part")
+    }
 }
 
 pub fn part1(points: &[Point]) -> u32 {
@@ -39,17 +60,13 @@ fn manhattan((x1, y1, z1, t1): Point, (x2, y2, z2, t2): Point) -> i32 {
     (x1 - x2).abs() + (y1 - y2).abs() + (z1 - z2).abs() + (t1 - t2).abs()
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Point> + '_ {
-    input.split('\n').map(|line| {
-        let mut coords = line.split(',');
-
-        (
-            coords.next().unwrap().parse().unwrap(),
-            coords.next().unwrap().parse().unwrap(),
-            coords.next().unwrap().parse().unwrap(),
-            coords.next().unwrap().parse().unwrap(),
-        )
-    })
+pub fn parse_input(input: &str) -> ParseResult<Vec<Point>> {
+    input.lines().map(|line| {
+        let mut coords = line.split(',').map(str::trim);
+        let mut coord = || parse::next_field(input, &mut coords, line, "a coordinate");
+
+        Ok((coord()?, coord()?, coord()?, coord()?))
+    }).collect()
 }
 
 type Point = (i32, i32, i32, i32);
@@ -59,7 +76,7 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let points: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let points = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&points), 363);
     }
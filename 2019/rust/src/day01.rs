@@ -1,12 +1,29 @@
-use std::fmt::Debug;
-use itertools::Itertools;
+use aoc_common::{Solution, ParseResult, parse};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day01.txt");
 
-pub fn day01() -> impl Debug {
-    let modules = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day01;
 
-    (part1(&modules), part2(&modules))
+impl Solution for Day01 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 1;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Module>;
+    type Part1 = Fuel;
+    type Part2 = Fuel;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(modules: &[Module]) -> Fuel {
@@ -40,10 +57,11 @@ impl Module {
 pub type Mass = u32;
 pub type Fuel = u32;
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Module> + '_ {
+pub fn parse_input(input: &str) -> ParseResult<Vec<Module>> {
     input.lines()
-        .map(|raw_mass| raw_mass.parse().expect("Invalid mass number"))
-        .map(|mass| Module { mass })
+        .map(|raw_mass| parse::field(input, raw_mass, "a mass number"))
+        .map(|mass| mass.map(|mass| Module { mass }))
+        .collect()
 }
 
 #[cfg(test)]
@@ -51,14 +69,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&input), 3_152_375);
     }
 
     #[test]
     fn p2() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&input), 4_725_720);
     }
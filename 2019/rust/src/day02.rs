@@ -1,18 +1,36 @@
-use std::fmt::Debug;
-use itertools::Itertools;
 use rayon::prelude::*;
-use crate::intcode::{Int, vm::{VirtualMachine, VMBuilder}};
+use aoc_common::{Solution, ParseResult};
+use crate::intcode::{Int, parse_program, error::VmResult, vm::{VirtualMachine, VMBuilder}};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day02.txt");
 
-pub fn day02() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day02;
 
-    (part1(&program), part2(&program))
+impl Solution for Day02 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 2;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = Int;
+    type Part2 = Int;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(program: &[Int]) -> Int {
-    run_program(program, 12, 02)
+    run_program(program, 12, 2)
+        .expect("Failed to run the program")
 }
 
 pub fn part2(program: &[Int]) -> Int {
@@ -22,13 +40,14 @@ pub fn part2(program: &[Int]) -> Int {
         .flat_map(|noun| (0..=99_i64).into_par_iter().map(move |verb| (noun, verb)));
 
     let (noun, verb) = combinations
-        .find_any(|&(noun, verb)| run_program(program, noun, verb) == TARGET_OUTPUT)
+        .find_any(|&(noun, verb)| run_program(program, noun, verb) == Ok(TARGET_OUTPUT))
         .expect("No noun/verb combination produced the desired output!");
 
     100 * noun + verb
 }
 
-fn run_program(program: &[Int], noun: Int, verb: Int) -> Int {
+// Some combinations make the program access invalid addresses
+fn run_program(program: &[Int], noun: Int, verb: Int) -> VmResult<Int> {
     let mut program = program.to_vec();
 
     program[1] = noun;
@@ -36,12 +55,7 @@ fn run_program(program: &[Int], noun: Int, verb: Int) -> Int {
 
     VirtualMachine::load(program)
         .run()
-        .memory[0]
-}
-
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
+        .map(|end| end.memory[0])
 }
 
 #[cfg(test)]
@@ -49,14 +63,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let program = parse_input(RAW_INPUT_STR).collect_vec();
+        let program = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&program), 4_462_686);
     }
 
     #[test]
     fn p2() {
-        let program = parse_input(RAW_INPUT_STR).collect_vec();
+        let program = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&program), 5_936);
     }
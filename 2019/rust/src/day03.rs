@@ -1,17 +1,36 @@
 use std::fmt::Debug;
-use itertools::Itertools;
 use std::collections::HashSet;
-use std::ops::{Add, AddAssign};
+use aoc_common::{Solution, ParseError, ParseResult};
+use aoc_grid::{Direction, Pos};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day03.txt");
 
-pub fn day03() -> impl Debug {
-    let (wire1_it, wire2_it) = parse_input(RAW_INPUT_STR);
+pub struct Day03;
 
-    let wire1 = wire1_it.collect_vec();
-    let wire2 = wire2_it.collect_vec();
+impl Solution for Day03 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 3;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
 
-    (part1(&wire1, &wire2), part2(&wire1, &wire2))
+    type Parsed<'a> = (Vec<Movement>, Vec<Movement>);
+    type Part1 = i32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        let (wire1, wire2) = input;
+
+        part1(wire1, wire2)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        let (wire1, wire2) = input;
+
+        part2(wire1, wire2)
+    }
 }
 
 pub fn part1(wire1: &[Movement], wire2: &[Movement]) -> i32 {
@@ -41,7 +60,7 @@ fn wire_intersections(wire1: &[Movement], wire2: &[Movement]) -> impl Iterator<I
 }
 
 fn lay_wire(wire: &[Movement]) -> HashSet<Pos> {
-    let mut pos = Pos(0, 0);
+    let mut pos = CENTRAL_PORT_POS;
     let mut visited = HashSet::new();
 
     for &Movement { direction, amount } in wire {
@@ -55,7 +74,7 @@ fn lay_wire(wire: &[Movement]) -> HashSet<Pos> {
 }
 
 fn wire_steps_to_reach(wire: &[Movement], target_pos: Pos) -> usize {
-    let mut pos = Pos(0, 0);
+    let mut pos = CENTRAL_PORT_POS;
     let mut steps = 0;
 
     for &Movement { direction, amount } in wire {
@@ -71,40 +90,7 @@ fn wire_steps_to_reach(wire: &[Movement], target_pos: Pos) -> usize {
     panic!("The provided wire doesn't reach the target position")
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Pos(i32, i32);
-
-const CENTRAL_PORT_POS: Pos = Pos(0, 0);
-
-impl Pos {
-    fn manhattan_dist(self, other: Pos) -> i32 {
-        let Pos(x1, y1) = self;
-        let Pos(x2, y2) = other;
-
-        (x1 - x2).abs() + (y1 - y2).abs()
-    }
-}
-
-impl Add<Direction> for Pos {
-    type Output = Pos;
-
-    fn add(self, dir: Direction) -> Self::Output {
-        let Pos(x, y) = self;
-
-        match dir {
-            Direction::Up => Pos(x, y - 1),
-            Direction::Down => Pos(x, y + 1),
-            Direction::Left => Pos(x - 1, y),
-            Direction::Right => Pos(x + 1, y),
-        }
-    }
-}
-
-impl AddAssign<Direction> for Pos {
-    fn add_assign(&mut self, dir: Direction) {
-        *self = *self + dir;
-    }
-}
+const CENTRAL_PORT_POS: Pos = Pos::ORIGIN;
 
 #[derive(Debug)]
 pub struct Movement {
@@ -113,41 +99,42 @@ pub struct Movement {
 }
 
 impl Movement {
-    fn parse(raw: &str) -> Self {
+    fn parse(input: &str, raw: &str) -> ParseResult<Self> {
         let mut chars = raw.chars();
 
-        let direction = Direction::parse(chars.next().expect("Missing direction"));
-        let amount = chars.as_str().parse().expect("Invalid movement amount");
+        let direction = chars.next()
+            .and_then(parse_direction)
+            .ok_or_else(|| ParseError::at(input, raw, "a direction (`U`, `D`, `L` or `R`)"))?;
+        let raw_amount = chars.as_str();
+        let amount = raw_amount.parse()
+            .map_err(|_| ParseError::at(input, raw_amount, "a movement amount"))?;
 
-        Self { direction, amount }
+        Ok(Self { direction, amount })
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn parse(raw: char) -> Self {
-        match raw {
-            'U' => Self::Up,
-            'D' => Self::Down,
-            'L' => Self::Left,
-            'R' => Self::Right,
-            invalid => panic!("invalid direction character: {}", invalid),
-        }
+fn parse_direction(raw: char) -> Option<Direction> {
+    match raw {
+        'U' => Some(Direction::Up),
+        'D' => Some(Direction::Down),
+        'L' => Some(Direction::Left),
+        'R' => Some(Direction::Right),
+        _ => None,
     }
 }
 
-pub fn parse_input(input: &'_ str) -> (impl Iterator<Item = Movement> + '_, impl Iterator<Item = Movement> + '_) {
-    input.lines()
-        .map(|line| line.split(',').map(Movement::parse))
-        .next_tuple()
-        .expect("Missing wire movements")
+pub fn parse_input(input: &str) -> ParseResult<(Vec<Movement>, Vec<Movement>)> {
+    let mut wires = input.lines()
+        .map(|line| {
+            line.split(',')
+                .map(|raw| Movement::parse(input, raw))
+                .collect::<ParseResult<Vec<_>>>()
+        });
+
+    let wire1 = wires.next().ok_or_else(|| ParseError::end(input, "the first wire movements"))??;
+    let wire2 = wires.next().ok_or_else(|| ParseError::end(input, "the second wire movements"))??;
+
+    Ok((wire1, wire2))
 }
 
 #[cfg(test)]
@@ -155,20 +142,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let (wire1_it, wire2_it) = parse_input(RAW_INPUT_STR);
-
-        let wire1 = wire1_it.collect_vec();
-        let wire2 = wire2_it.collect_vec();
+        let (wire1, wire2) = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&wire1, &wire2), 352);
     }
 
     #[test]
     fn p2() {
-        let (wire1_it, wire2_it) = parse_input(RAW_INPUT_STR);
-
-        let wire1 = wire1_it.collect_vec();
-        let wire2 = wire2_it.collect_vec();
+        let (wire1, wire2) = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&wire1, &wire2), 43848);
     }
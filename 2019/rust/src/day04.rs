@@ -1,13 +1,31 @@
-use std::fmt::Debug;
 use std::ops::RangeInclusive;
 use itertools::Itertools;
+use aoc_common::{Solution, ParseResult, parse};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day04.txt");
 
-pub fn day04() -> impl Debug {
-    let range = parse_input(RAW_INPUT_STR);
+pub struct Day04;
 
-    (part1(range.clone()), part2(range))
+impl Solution for Day04 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 4;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = RangeInclusive<Password>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input.clone())
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input.clone())
+    }
 }
 
 pub fn part1(range: RangeInclusive<Password>) -> usize {
@@ -57,15 +75,14 @@ fn compute_digits(password: Password) -> [u32; 6] {
     ]
 }
 
-pub fn parse_input(input: &str) -> RangeInclusive<Password> {
-    let mut parts = input.split("-");
+pub fn parse_input(input: &str) -> ParseResult<RangeInclusive<Password>> {
+    let line = input.trim_end();
+    let mut parts = line.split('-');
 
-    let lower_bound = parts.next().expect("Missing lower bound")
-        .parse().expect("Malformed lower bound");
-    let upper_bound = parts.next().expect("Missing upper bound")
-        .parse().expect("Malformed upper bound");
+    let lower_bound = parse::next_field(input, &mut parts, line, "a lower bound")?;
+    let upper_bound = parse::next_field(input, &mut parts, line, "an upper bound")?;
 
-    lower_bound..=upper_bound
+    Ok(lower_bound..=upper_bound)
 }
 
 pub type Password = u32;
@@ -75,14 +92,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let range = parse_input(RAW_INPUT_STR);
+        let range = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(range), 1_099);
     }
 
     #[test]
     fn p2() {
-        let range = parse_input(RAW_INPUT_STR);
+        let range = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(range), 710);
     }
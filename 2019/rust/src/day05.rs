@@ -1,13 +1,30 @@
-use std::fmt::Debug;
-use itertools::Itertools;
-use crate::intcode::{Int, vm::{VirtualMachine, VMBuilder}};
+use aoc_common::{Solution, ParseResult};
+use crate::intcode::{Int, parse_program, vm::{VirtualMachine, VMBuilder}};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day05.txt");
 
-pub fn day05() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day05;
 
-    (part1(&program), part2(&program))
+impl Solution for Day05 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 5;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = Int;
+    type Part2 = Int;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(program: &[Int]) -> Int {
@@ -27,28 +44,24 @@ fn run_program(program: &[Int], seed: Int) -> Int {
         .input_once(seed)
         .single_output()
         .run()
+        .expect("Failed to run the program")
         .output()
         .expect("No output!")
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let program = parse_input(RAW_INPUT_STR).collect_vec();
+        let program = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&program), 9_006_673);
     }
 
     #[test]
     fn p2() {
-        let program = parse_input(RAW_INPUT_STR).collect_vec();
+        let program = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&program), 3_629_692);
     }
@@ -1,13 +1,30 @@
-use std::fmt::Debug;
-use itertools::Itertools;
 use std::collections::{HashSet, HashMap, VecDeque};
+use aoc_common::{Solution, ParseResult, parse};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day06.txt");
 
-pub fn day06() -> impl Debug {
-    let relations = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day06;
 
-    (part1(&relations), part2(&relations))
+impl Solution for Day06 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 6;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<OrbitRelation>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(orbit_relations: &[OrbitRelation]) -> usize {
@@ -85,15 +102,16 @@ fn shortest_route(orbit_map: &HashMap<&str, Vec<&str>>, start: &str, target: &st
 
 pub type OrbitRelation = [String; 2];
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = OrbitRelation> + '_ {
+pub fn parse_input(input: &str) -> ParseResult<Vec<OrbitRelation>> {
     input.lines()
         .map(|raw_relation| {
             let mut split = raw_relation.split(')');
-            [
-                split.next().expect("Missing left object").to_owned(),
-                split.next().expect("Missing right object").to_owned(),
-            ]
+            Ok([
+                parse::next(input, &mut split, raw_relation, "a left object")?.to_owned(),
+                parse::next(input, &mut split, raw_relation, "`)` and a right object")?.to_owned(),
+            ])
         })
+        .collect()
 }
 
 #[cfg(test)]
@@ -101,14 +119,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&input), 358_244);
     }
 
     #[test]
     fn p2() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&input), 517);
     }
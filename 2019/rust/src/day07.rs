@@ -1,15 +1,31 @@
-use std::fmt::Debug;
-use std::thread;
-use std::sync::mpsc::channel;
 use itertools::Itertools;
-use crate::intcode::{Int, vm::{VirtualMachine, VMBuilder}, io::ext::Split};
+use aoc_common::{Solution, ParseResult};
+use crate::intcode::{Int, parse_program, vm::{VirtualMachine, VMBuilder, IoEvent}};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day07.txt");
 
-pub fn day07() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day07;
 
-    (part1(&program), part2(&program))
+impl Solution for Day07 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 7;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = Int;
+    type Part2 = Int;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(program: &[Int]) -> Int {
@@ -29,79 +45,42 @@ pub fn part2(program: &[Int]) -> Int {
 }
 
 fn run_amplifiers(program: &[Int], settings: &[Int]) -> Int {
-    let (mut tx, mut rx) = channel();
-    let init_tx = tx.clone();
-
-    let amplifiers = settings.iter()
-        .map(|&setting| {
-            let (next_tx, next_rx) = channel();
-            tx.send(setting).expect("Failed to send phase setting");
-            tx = next_tx.clone();
-            let rx = std::mem::replace(&mut rx, next_rx);
-
+    settings.iter()
+        .fold(0, |signal, &setting| {
             VirtualMachine::load(program)
-                .input_driver(rx)
-                .output_driver(next_tx)
-                .build()
-        });
-
-    let handles = amplifiers
-        .map(|amp| thread::spawn(move || amp.run()))
-        .collect_vec();
-
-    init_tx.send(0).expect("Failed to send initial input value");
-
-    let result = rx.recv().expect("Failed to get output");
-
-    for handle in handles {
-        handle.join().expect("Failed to join amplifiers");
-    }
-
-    result
+                .input_iter([setting, signal].iter().copied())
+                .single_output()
+                .run()
+                .expect("Failed to run the program")
+                .into_output()
+                .get()
+                .expect("Failed to get output")
+        })
 }
 
+// The amplifiers take turns, each running until it passes its signal to the next one
 fn run_amplifiers_feedback_loop(program: &[Int], settings: &[Int]) -> Int {
-    let (mut tx, mut rx) = channel();
-    let init_tx = tx.clone();
-
-    let amplifiers = settings[1..].iter()
+    let mut amplifiers = settings.iter()
         .map(|&setting| {
-            let (next_tx, next_rx) = channel();
-            tx.send(setting).expect("Failed to send phase setting");
-            tx = next_tx.clone();
-            let rx = std::mem::replace(&mut rx, next_rx);
-            VirtualMachine::load(program)
-                .input_driver(rx)
-                .output_driver(next_tx)
-                .build()
+            let mut amplifier = VirtualMachine::load(program).pausable();
+            amplifier.push_input(setting);
+            amplifier
         })
         .collect_vec();
 
-    let first_amp = VirtualMachine::load(program)
-        .input_driver(rx)
-        .output_driver(init_tx)
-        .build();
+    let mut signal = 0;
 
-    tx.send(settings[0]).expect("Failed to send phase setting");
-    tx.send(0).expect("Failed to initial input value");
+    loop {
+        for amplifier in &mut amplifiers {
+            amplifier.push_input(signal);
 
-    let handles = std::iter::once(first_amp).chain(amplifiers.into_iter())
-        .map(|amp| thread::spawn(move || amp.run()))
-        .collect_vec();
-
-    let mut amplifiers = handles.into_iter()
-        .map(|handle| handle.join().expect("Failed to join amplifiers"))
-        .collect_vec();
-
-    amplifiers.first_mut()
-        .expect("No amplifiers")
-        .input().recv()
-        .expect("Failed to get output")
-}
-
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
+            signal = match amplifier.run_until_io().expect("Failed to run an amplifier") {
+                IoEvent::Output(value) => value,
+                IoEvent::Halted => return signal,
+                IoEvent::NeedsInput => panic!("Amplifier waiting for a second signal"),
+            };
+        }
+    }
 }
 
 #[cfg(test)]
@@ -109,14 +88,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&code), 38_834);
     }
 
     #[test]
     fn p2() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&code), 69_113_332);
     }
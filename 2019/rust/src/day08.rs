@@ -1,15 +1,34 @@
-use std::fmt::{self, Debug};
-use itertools::Itertools;
+use std::fmt::Debug;
+use aoc_common::{Solution, ParseError, ParseResult};
+use aoc_grid::{Grid, ocr};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day08.txt");
 const IMG_WIDTH: usize = 25;
 const IMG_HEIGHT: usize = 6;
 const IMG_PX_COUNT: usize = IMG_WIDTH * IMG_HEIGHT;
 
-pub fn day08() -> impl Debug {
-    let image_data = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day08;
 
-    (part1(&image_data), Banner(part2(&image_data)))
+impl Solution for Day08 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 8;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<u8>;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(image_data: &[u8]) -> usize {
@@ -27,6 +46,11 @@ pub fn part1(image_data: &[u8]) -> usize {
 }
 
 pub fn part2(image_data: &[u8]) -> String {
+    ocr::read(&decode(image_data))
+}
+
+// The lit pixels of the image once all its layers are stacked
+pub fn decode(image_data: &[u8]) -> Grid<bool> {
     let colored_layers = layers(image_data)
         .map(|layer| layer.iter().map(|&x| Color::from(x)));
 
@@ -38,17 +62,11 @@ pub fn part2(image_data: &[u8]) -> String {
         }
     }
 
-    image.chunks(IMG_WIDTH).into_iter()
-        .map(|image_row|
-            image_row.iter()
-                .map(|color| match color {
-                    Color::Black => '░',
-                    Color::Red => '▓',
-                    Color::Transparent => ' ',
-                })
-                .collect::<String>()
-        )
-        .join("\n")
+    let pixels = image.iter()
+        .map(|color| matches!(color, Color::Red))
+        .collect();
+
+    Grid::from_cells(IMG_WIDTH, pixels)
 }
 
 fn layers(image_data: &[u8]) -> impl Iterator<Item = &[u8]> + '_ {
@@ -57,14 +75,6 @@ fn layers(image_data: &[u8]) -> impl Iterator<Item = &[u8]> + '_ {
         .map(move |layer_start_idx| &image_data[layer_start_idx..][..IMG_PX_COUNT])
 }
 
-pub struct Banner(String);
-
-impl Debug for Banner {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "\n{}", self.0)
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
 enum Color {
     Black,
@@ -97,9 +107,15 @@ impl From<u8> for Color {
     }
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = u8> + '_ {
-    input.bytes()
-        .map(|ascii_digit| ascii_digit - b'0')
+pub fn parse_input(input: &str) -> ParseResult<Vec<u8>> {
+    input.trim_end()
+        .bytes()
+        .enumerate()
+        .map(|(offset, ascii_digit)| match ascii_digit {
+            b'0'..=b'2' => Ok(ascii_digit - b'0'),
+            _ => Err(ParseError::new(input, offset, "a color digit (`0`, `1` or `2`)")),
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -107,16 +123,17 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input = parse_input(RAW_INPUT_STR).collect_vec();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&input), 1_596);
     }
 
     #[test]
     fn p2() {
-        let input = parse_input(RAW_INPUT_STR).collect_vec();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
-        assert_eq!(part2(&input), "\
+        assert_eq!(part2(&input), "LBRCE");
+        assert_eq!(ocr::render(&decode(&input)), "\
 ▓░░░░▓▓▓░░▓▓▓░░░▓▓░░▓▓▓▓░
 ▓░░░░▓░░▓░▓░░▓░▓░░▓░▓░░░░
 ▓░░░░▓▓▓░░▓░░▓░▓░░░░▓▓▓░░
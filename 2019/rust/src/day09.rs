@@ -1,13 +1,30 @@
-use std::fmt::Debug;
-use itertools::Itertools;
-use crate::intcode::{Int, vm::{VirtualMachine, VMBuilder}};
+use aoc_common::{Solution, ParseResult};
+use crate::intcode::{Int, parse_program, vm::{VirtualMachine, VMBuilder}};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day09.txt");
 
-pub fn day09() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day09;
 
-    (part1(&program), part2(&program))
+impl Solution for Day09 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 9;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = Int;
+    type Part2 = Int;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(program: &[Int]) -> Int {
@@ -23,28 +40,24 @@ fn run_program(program: &[Int], seed: Int) -> Int {
         .input_once(seed)
         .single_output()
         .run()
+        .expect("Failed to run the program")
         .output()
         .expect("Didn't get any output")
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&code), 3_638_931_938);
     }
 
     #[test]
     fn p2() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&code), 86_025);
     }
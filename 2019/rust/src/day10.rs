@@ -3,17 +3,36 @@ use std::cmp::{Ord, Ordering};
 use std::hash::{Hash, Hasher};
 use std::collections::BTreeMap;
 use std::f32::consts::PI;
+use aoc_common::{Solution, ParseError, ParseResult};
 
 use itertools::Itertools;
 use rayon::prelude::*;
-use packed_simd::f32x2;
+use crate::simd::f32x2;
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day10.txt");
 
-pub fn day10() -> impl Debug {
-    let asteroids = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day10;
 
-    (part1(&asteroids), part2(&asteroids))
+impl Solution for Day10 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 10;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Pos>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(asteroids: &[Pos]) -> usize {
@@ -116,12 +135,18 @@ impl Pos {
     }
 }
 
-#[derive(Clone, Debug, PartialOrd, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 struct FloatOrd(f32);
 
 impl Ord for FloatOrd {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).expect("Got NaNs")
+        self.0.partial_cmp(&other.0).expect("Got NaNs")
+    }
+}
+
+impl PartialOrd for FloatOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -133,17 +158,18 @@ impl Hash for FloatOrd {
     }
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Pos> + '_ {
+pub fn parse_input(input: &str) -> ParseResult<Vec<Pos>> {
     input.lines()
         .enumerate()
         .flat_map(|(y, line)|
-            line.chars()
-                .enumerate()
+            line.char_indices()
                 .filter_map(move |(x, ch)| match ch {
-                    '#' => Some(Pos(f32x2::new(x as _, y as _))),
-                    _ => None
+                    '#' => Some(Ok(Pos(f32x2::new(x as _, y as _)))),
+                    '.' => None,
+                    _ => Some(Err(ParseError::at(input, &line[x..], "an asteroid (`#`) or empty space (`.`)"))),
                 })
         )
+        .collect()
 }
 
 #[cfg(test)]
@@ -151,14 +177,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&input), 292);
     }
 
     #[test]
     fn p2() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&input), 317);
     }
@@ -1,75 +1,86 @@
-use std::fmt::{self, Debug};
-use std::collections::HashMap;
-use itertools::Itertools;
-use crate::intcode::{Int, vm::{VirtualMachine, VMBuilder}, io::{Input, Output}};
+use std::fmt::Debug;
+use aoc_common::{Solution, ParseResult};
+use aoc_grid::{Bounds, Direction, Grid, Pos, SparseGrid, ocr};
+use crate::intcode::{Int, parse_program, vm::{VirtualMachine, VMBuilder}, io::{Input, Output}};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day11.txt");
 
-pub fn day11() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day11;
 
-    (part1(&program), Banner(part2(&program)))
+impl Solution for Day11 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 11;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(program: &[Int]) -> usize {
     VirtualMachine::load(program)
         .with_driver::<HullPaintingRobot>()
         .run()
-        .driver.painted.values().count()
+        .expect("Failed to run the program")
+        .driver.painted.len()
 }
 
 pub fn part2(program: &[Int]) -> String {
+    ocr::read(&paint_registration(program))
+}
+
+// The white panels of the registration identifier, starting on a white panel
+pub fn paint_registration(program: &[Int]) -> Grid<bool> {
     let mut robot = HullPaintingRobot::default();
-    robot.grid.insert(Pos { x: 0, y: 0 }, Color::White);
+    robot.grid.insert(Pos::ORIGIN, Color::White);
 
     let robot = VirtualMachine::load(program)
         .driver(robot)
         .run()
+        .expect("Failed to run the program")
         .driver;
 
-    let mut canvas = std::iter::repeat_with(|| vec!['░'; 43]).take(6).collect_vec();
-    for (Pos { x, y }, color) in robot.grid {
-        canvas[y as usize][x as usize] = match color {
-            Color::Black => '░',
-            Color::White => '▓',
-        }
-    };
-    canvas.into_iter().map(|l| l.into_iter().collect::<String>()).join("\n")
-}
-
-pub struct Banner(String);
-
-impl Debug for Banner {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "\n{}", self.0)
-    }
+    let canvas = Bounds { min: Pos::ORIGIN, max: Pos::new(42, 5) };
+    robot.grid.to_dense(canvas, |color| matches!(color, Some(Color::White)))
 }
 
 struct HullPaintingRobot {
     direction: Direction,
     position: Pos,
-    grid: HashMap<Pos, Color>,
-    painted: HashMap<Pos, i64>,
+    grid: SparseGrid<Color>,
+    painted: SparseGrid<i64>,
     next_order: Order
 }
 
 impl HullPaintingRobot {
     fn paint(&mut self, color: Color) {
-        self.grid.entry(self.position)
-            .and_modify(|e| *e = color)
-            .or_insert(color);
-        *self.painted.entry(self.position)
-            .or_insert(0) += 1;
+        self.grid.insert(self.position, color);
+        *self.painted.get_or_insert_with(self.position, || 0) += 1;
     }
 
     fn turn(&mut self, turn: Turn) {
-        let new_direction = self.direction.turn(turn);
+        let new_direction = match turn {
+            Turn::Left => self.direction.turn_left(),
+            Turn::Right => self.direction.turn_right(),
+        };
         self.position = self.position.translate(new_direction);
         self.direction = new_direction;
     }
 
     fn current_color(&self) -> Color {
-        self.grid.get(&self.position)
+        self.grid.get(self.position)
             .copied()
             .unwrap_or(Color::Black)
     }
@@ -79,57 +90,10 @@ impl Default for HullPaintingRobot {
     fn default() -> Self {
         Self {
             direction: Direction::Up,
-            position: Pos { x: 0, y: 0 },
-            grid: HashMap::new(),
+            position: Pos::ORIGIN,
+            grid: SparseGrid::new(),
             next_order: Order::Paint,
-            painted: HashMap::new(),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Pos { x: i32, y: i32 }
-
-impl Pos {
-    fn translate(self, direction: Direction) -> Self {
-        let Pos { x, y } = self;
-
-        let (x, y) = match direction {
-            Direction::Up => (x, y - 1),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x - 1, y),
-            Direction::Right => (x + 1, y),
-        };
-
-        Pos { x, y }
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn turn(self, turn: Turn) -> Self {
-        use Direction::*;
-
-        match turn {
-            Turn::Left => match self {
-                Up => Left,
-                Down => Right,
-                Left => Down,
-                Right => Up,
-            },
-            Turn::Right => match self {
-                Up => Right,
-                Down => Left,
-                Left => Up,
-                Right => Down,
-            },
+            painted: SparseGrid::new(),
         }
     }
 }
@@ -190,34 +154,30 @@ impl Output for HullPaintingRobot {
 }
 
 impl Input for HullPaintingRobot {
-    fn input(&mut self) -> Int {
+    fn input(&mut self) -> Option<Int> {
         match self.current_color() {
-            Color::Black => 0,
-            Color::White => 1,
+            Color::Black => Some(0),
+            Color::White => Some(1),
         }
     }
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&code), 2_018);
     }
 
     #[test]
     fn p2() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
-        assert_eq!(part2(&code), "\
+        assert_eq!(part2(&code), "APFKRKBR");
+        assert_eq!(ocr::render(&paint_registration(&code)), "\
 ░░▓▓░░▓▓▓░░▓▓▓▓░▓░░▓░▓▓▓░░▓░░▓░▓▓▓░░▓▓▓░░░░
 ░▓░░▓░▓░░▓░▓░░░░▓░▓░░▓░░▓░▓░▓░░▓░░▓░▓░░▓░░░
 ░▓░░▓░▓░░▓░▓▓▓░░▓▓░░░▓░░▓░▓▓░░░▓▓▓░░▓░░▓░░░
@@ -3,13 +3,32 @@ use std::ops::{Add, AddAssign, Sub};
 use std::iter::Sum;
 use itertools::Itertools;
 use num::Integer;
+use aoc_common::{Solution, ParseError, ParseResult, parse, cycle};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day12.txt");
 
-pub fn day12() -> impl Debug {
-    let positions = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day12;
 
-    (part1(&positions), part2(&positions))
+impl Solution for Day12 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 12;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Vec3>;
+    type Part1 = i32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(positions: &[Vec3]) -> i32 {
@@ -88,42 +107,13 @@ pub fn part2(positions: &[Vec3]) -> usize {
         )
     };
 
-    let (lam_x, _) = floyd(moons.clone(), next_state, eq_on(|v| v.x));
-    let (lam_y, _) = floyd(moons.clone(), next_state, eq_on(|v| v.y));
-    let (lam_z, _) = floyd(moons.clone(), next_state, eq_on(|v| v.z));
+    let lam_x = cycle::floyd(moons.clone(), next_state, eq_on(|v| v.x)).len;
+    let lam_y = cycle::floyd(moons.clone(), next_state, eq_on(|v| v.y)).len;
+    let lam_z = cycle::floyd(moons.clone(), next_state, eq_on(|v| v.z)).len;
 
     lam_x.lcm(&lam_y).lcm(&lam_z)
 }
 
-fn floyd<T: Clone, F: Fn(T) -> T, Cmp: Fn(&T, &T) -> bool>(x0: T, f: F, cmp: Cmp)
-    -> (usize, usize)
-{
-    let mut tortoise = f(x0.clone());
-    let mut hare = f(f(x0.clone()));
-
-    while !cmp(&tortoise, &hare) {
-        tortoise = f(tortoise);
-        hare = f(f(hare));
-    }
-
-    let mut mu = 0;
-    let mut tortoise = x0;
-    while !cmp(&tortoise, &hare) {
-        tortoise = f(tortoise);
-        hare = f(hare);
-        mu += 1
-    }
-
-    let mut lam = 1;
-    let mut hare = f(tortoise.clone());
-    while !cmp(&tortoise, &hare) {
-        hare = f(hare);
-        lam += 1
-    }
-
-    (lam, mu)
-}
-
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct Moon {
     position: Vec3,
@@ -207,15 +197,23 @@ impl Sum for Vec3 {
     }
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Vec3> + '_ {
+pub fn parse_input(input: &str) -> ParseResult<Vec<Vec3>> {
     input.lines()
         .map(|raw_pos| {
-            let stripped = &raw_pos[1..raw_pos.len()-1]; // skip '<' and '>'
-            let mut components = stripped.split(", ")
-                .map(|raw_comp| raw_comp[2..].parse().expect("Malformed axis value"));
-            let (x, y, z) = components.next_tuple().expect("Missing axis component");
-            Vec3 { x, y, z }
+            let stripped = raw_pos.strip_prefix('<')
+                .ok_or_else(|| ParseError::at(input, raw_pos, "`<`"))?
+                .strip_suffix('>')
+                .ok_or_else(|| ParseError::after(input, raw_pos, "`>`"))?;
+            let mut components = stripped.split(", ");
+            let mut component = |axis: &str| {
+                let raw_comp = parse::next(input, &mut components, stripped, &format!("`{}=`", axis))?;
+                let raw_value = raw_comp.strip_prefix(&format!("{}=", axis))
+                    .ok_or_else(|| ParseError::at(input, raw_comp, format!("`{}=`", axis)))?;
+                parse::field(input, raw_value, "an axis value")
+            };
+            Ok(Vec3 { x: component("x")?, y: component("y")?, z: component("z")? })
         })
+        .collect()
 }
 
 #[cfg(test)]
@@ -223,14 +221,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&input), 7077);
     }
 
     #[test]
     fn p2() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&input), 402_951_477_454_512);
     }
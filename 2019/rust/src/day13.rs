@@ -1,21 +1,40 @@
 use std::fmt::Debug;
-use std::collections::HashMap;
-use itertools::Itertools;
 use std::cmp::Ordering;
-use crate::intcode::{Int, vm::{VirtualMachine, VMBuilder}, io::{Output, Input}};
+use aoc_common::{Solution, ParseResult};
+use aoc_grid::{Pos, SparseGrid};
+use crate::intcode::{Int, parse_program, vm::{VirtualMachine, VMBuilder}, io::{Output, Input}};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day13.txt");
 
-pub fn day13() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day13;
 
-    (part1(&program), part2(&program))
+impl Solution for Day13 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 13;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = usize;
+    type Part2 = Int;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(program: &[Int]) -> usize {
     let screen = VirtualMachine::load(program)
         .driver(ArcadeGame::default())
         .run()
+        .expect("Failed to run the program")
         .driver.screen;
 
     screen.tiles
@@ -31,12 +50,13 @@ pub fn part2(program: &[Int]) -> Int {
     VirtualMachine::load(program)
         .driver(ArcadeGame::default())
         .run()
+        .expect("Failed to run the program")
         .driver.score
 }
 
 #[derive(Debug, Default)]
 struct Screen {
-    tiles: HashMap<Pos, Tile>,
+    tiles: SparseGrid<Tile>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -56,7 +76,7 @@ struct ArcadeGame {
 }
 
 impl Input for ArcadeGame {
-    fn input(&mut self) -> Int {
+    fn input(&mut self) -> Option<Int> {
         let target_x = self.ball_pos.x;
         let paddle_x = self.paddle_pos.x;
 
@@ -66,7 +86,7 @@ impl Input for ArcadeGame {
             Ordering::Greater => JoyStick::Left,
         };
 
-        joystick as _
+        Some(joystick as _)
     }
 }
 
@@ -79,7 +99,7 @@ impl Output for ArcadeGame {
                 if x == -1 && y == 0 {
                     OutputState::Score
                 } else {
-                    OutputState::TileId { pos: Pos { x, y } }
+                    OutputState::TileId { pos: Pos::new(x as _, y as _) }
                 }
             },
             OutputState::TileId { pos } => {
@@ -103,19 +123,15 @@ impl Output for ArcadeGame {
 }
 
 #[derive(Debug)]
+#[derive(Default)]
 enum OutputState {
+    #[default]
     X,
     Y { x: Int },
     TileId { pos: Pos },
     Score,
 }
 
-impl Default for OutputState {
-    fn default() -> Self { Self::X }
-}
-
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
-struct Pos { x: Int, y: Int }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tile {
@@ -139,24 +155,19 @@ impl From<Int> for Tile {
     }
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&code), 320);
     }
 
     #[test]
     fn p2() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&code), 15_156);
     }
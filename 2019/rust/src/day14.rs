@@ -1,15 +1,33 @@
 use std::fmt::Debug;
 use std::collections::HashMap;
 use std::cmp::Ordering;
-use itertools::Itertools;
 use num::Integer;
+use aoc_common::{Solution, ParseError, ParseResult, parse};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day14.txt");
 
-pub fn day14() -> impl Debug {
-    let reactions = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day14;
 
-    (part1(&reactions), part2(&reactions))
+impl Solution for Day14 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 14;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Reaction>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(reactions: &[Reaction]) -> usize {
@@ -46,7 +64,7 @@ fn ore_requirements(reactions: &[Reaction], target: Reagent) -> usize {
             let &(inputs, produced_amount) = producers.get(&target_chem)
                 .expect("Missing reaction");
 
-            let (reactions_needed, remainder) = target_amount.div_rem(&produced_amount);
+            let (reactions_needed, remainder) = target_amount.div_rem(produced_amount);
             let reactions_needed = reactions_needed + remainder.min(1);
             if remainder > 0 {
                 *leftovers.entry(target_chem).or_insert(0) += produced_amount - remainder;
@@ -114,36 +132,38 @@ pub struct Reagent {
 
 type Reaction = (Vec<Reagent>, Reagent);
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Reaction> + '_ {
+pub fn parse_input(input: &str) -> ParseResult<Vec<Reaction>> {
     input.lines()
         .map(|raw_reaction| {
-            let (input_reagents, mut output_reagents) = raw_reaction
-                .split(" => ")
-                .map(parse_reagent)
-                .collect_tuple()
-                .expect("Missing reagents");
-
-            assert!(output_reagents.len() == 1, "More than 1 output reagent in reaction");
-            (input_reagents, output_reagents.swap_remove(0))
+            let mut sides = raw_reaction.split(" => ");
+            let raw_inputs = parse::next(input, &mut sides, raw_reaction, "input reagents")?;
+            let raw_output = parse::next(input, &mut sides, raw_reaction, "` => ` and an output reagent")?;
+
+            Ok((parse_reagents(input, raw_inputs)?, parse_reagent(input, raw_output)?))
         })
+        .collect()
 }
 
-fn parse_reagent(raw_reagents: &str) -> Vec<Reagent> {
+fn parse_reagents(input: &str, raw_reagents: &str) -> ParseResult<Vec<Reagent>> {
     raw_reagents.split(", ")
-        .map(|raw_reagent| {
-            let (raw_amount, raw_chemical) = raw_reagent.split(' ')
-                .collect_tuple()
-                .expect("Malformed reagent");
+        .map(|raw_reagent| parse_reagent(input, raw_reagent))
+        .collect()
+}
 
-            let amount = raw_amount.parse().expect("Malformed reagent amount");
-            let chemical = raw_chemical.into();
+fn parse_reagent(input: &str, raw_reagent: &str) -> ParseResult<Reagent> {
+    let mut tokens = raw_reagent.split(' ');
 
-            Reagent {
-                chemical,
-                amount,
-            }
-        })
-        .collect()
+    let amount = parse::next_field(input, &mut tokens, raw_reagent, "a reagent amount")?;
+    let chemical = parse::next(input, &mut tokens, raw_reagent, "a chemical")?.into();
+
+    if let Some(extra) = tokens.next() {
+        return Err(ParseError::at(input, extra, "`, ` or ` => `"));
+    }
+
+    Ok(Reagent {
+        chemical,
+        amount,
+    })
 }
 
 #[cfg(test)]
@@ -151,14 +171,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&input), 202_617);
     }
 
     #[test]
     fn p2() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&input), 7_863_863);
     }
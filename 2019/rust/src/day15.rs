@@ -1,38 +1,59 @@
 use std::fmt::Debug;
-use std::collections::{HashMap, HashSet, VecDeque};
-use itertools::Itertools;
-use crate::intcode::{Int, vm::{VirtualMachine, VMBuilder}, io::{Output, Input}};
+use std::collections::HashSet;
+use aoc_common::{Solution, ParseResult, search};
+use aoc_grid::{Direction, Pos, SparseGrid};
+use crate::intcode::{Int, parse_program, vm::{VirtualMachine, VMBuilder}, io::{Output, Input}};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day15.txt");
 
-pub fn day15() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day15;
 
-    (part1(&program), part2(&program))
+impl Solution for Day15 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 15;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(program: &[Int]) -> usize {
     let mapper = VirtualMachine::load(program)
         .with_driver::<Mapper>()
         .run()
+        .expect("Failed to run the program")
         .driver;
 
     let oxygen_system_pos = mapper.oxygen_system_position()
         .expect("Oxygen system must be found at this point!");
 
-    mapper.shortest_route(Pos { x: 0, y: 0 }, oxygen_system_pos).0
+    mapper.shortest_route(Pos::ORIGIN, oxygen_system_pos).0
 }
 
 pub fn part2(program: &[Int]) -> usize {
     VirtualMachine::load(program)
         .with_driver::<Mapper>()
         .run()
+        .expect("Failed to run the program")
         .driver
         .oxygen_fill_time()
 }
 
 struct Mapper {
-    tiles: HashMap<Pos, Tile>,
+    tiles: SparseGrid<Tile>,
     pos: Pos,
     last_direction: Direction,
     pos_to_visit: HashSet<Pos>,
@@ -41,19 +62,17 @@ struct Mapper {
 
 impl Default for Mapper {
     fn default() -> Self {
-        let initial_pos = Pos { x: 0, y: 0 };
+        let initial_pos = Pos::ORIGIN;
 
-        let tiles = std::iter::once((initial_pos, Tile::Empty))
-            .collect();
+        let mut tiles = SparseGrid::new();
+        tiles.insert(initial_pos, Tile::Empty);
 
-        let pos_to_visit = Direction::all()
-            .map(|dir| initial_pos.translate(dir))
-            .collect();
+        let pos_to_visit = initial_pos.neighbours4().collect();
 
         Self {
             tiles,
             pos: initial_pos,
-            last_direction: Direction::North,
+            last_direction: Direction::Up,
             pos_to_visit,
             pos_target: None,
         }
@@ -63,79 +82,40 @@ impl Default for Mapper {
 impl Mapper {
     fn oxygen_system_position(&self) -> Option<Pos> {
         self.tiles.iter()
-            .find_map(|(&pos, &tile)| if tile == Tile::OxygenSystem { Some(pos) } else { None })
+            .find_map(|(pos, &tile)| if tile == Tile::OxygenSystem { Some(pos) } else { None })
     }
 
     fn shortest_route(&self, start_pos: Pos, destination: Pos) -> (usize, Direction) {
-        let mut open_set: VecDeque<_> = Direction::all()
-            .filter_map(|dir| {
-                let neighbor_pos = start_pos.translate(dir);
-
-                if let Some(Tile::Wall) = self.tiles.get(&neighbor_pos) {
-                    None
-                } else {
-                    Some((neighbor_pos, (1, dir)))
+        let mut search = search::bfs([start_pos], |&pos: &Pos| {
+            pos.neighbours4().filter(move |&neighbor_pos| {
+                match self.tiles.get(neighbor_pos) {
+                    _ if neighbor_pos == destination => true,
+                    None | Some(Tile::Wall) => false,
+                    Some(_) => true,
                 }
             })
-            .collect();
-
-        let mut closed_set: HashSet<_> = std::iter::once(start_pos).collect();
-
-        while let Some((pos, (move_count, start_dir))) = open_set.pop_front() {
-            if pos == destination {
-                return (move_count, start_dir)
-            }
+        });
 
-            closed_set.insert(pos);
+        let (_, move_count) = search.find(|&(pos, _)| pos == destination)
+            .expect("No route found!");
+        let first_step = search.path(&destination)[1];
+        let start_dir = Direction::all()
+            .find(|&dir| start_pos + dir == first_step)
+            .expect("Routes are made of adjacent positions");
 
-            for direction in Direction::all() {
-                let neighbor_pos = pos.translate(direction);
-                if neighbor_pos == destination {
-                    return (move_count + 1, start_dir)
-                }
-
-                if let None | Some(Tile::Wall) = self.tiles.get(&neighbor_pos) {
-                    continue
-                }
-
-                if !closed_set.contains(&neighbor_pos) {
-                    open_set.push_back((neighbor_pos, (move_count + 1, start_dir)));
-                }
-            }
-        }
-
-        panic!("No route found!")
+        (move_count, start_dir)
     }
 
     fn oxygen_fill_time(&self) -> usize {
         let oxygen_start_pos = self.oxygen_system_position()
             .expect("Oxygen system must be found at this point!");
 
-        let mut empty_tiles: HashSet<_> = self.tiles.iter()
-            .filter_map(|(&pos, &tile)| if tile == Tile::Empty { Some(pos) } else { None })
-            .collect();
-
-        let mut oxygen_positions = vec![oxygen_start_pos];
-        let mut minutes = 0;
-
-        while !empty_tiles.is_empty() {
-            let next_oxygen_positions = oxygen_positions.into_iter()
-                .flat_map(|pos| {
-                    Direction::all()
-                        .map(move |dir| pos.translate(dir))
-                        .filter(|pos| empty_tiles.contains(&pos))
-                })
-                .collect();
-
-            for next_pos in &next_oxygen_positions {
-                empty_tiles.remove(next_pos);
-            }
-
-            oxygen_positions = next_oxygen_positions;
-            minutes += 1;
-        }
-
-        minutes
+        search::bfs([oxygen_start_pos], |&pos: &Pos| {
+            pos.neighbours4().filter(move |&pos| self.tiles.get(pos) == Some(&Tile::Empty))
+        })
+        .map(|(_pos, minutes)| minutes)
+        .max()
+        .unwrap_or(0)
     }
 }
 
@@ -159,9 +139,8 @@ impl Output for Mapper {
         if tile != Tile::Wall {
             self.pos = pos_tried;
 
-            for direction in Direction::all() {
-                let pos = self.pos.translate(direction);
-                if !self.tiles.contains_key(&pos) {
+            for pos in self.pos.neighbours4() {
+                if !self.tiles.contains(pos) {
                     self.pos_to_visit.insert(pos);
                 }
             }
@@ -170,9 +149,9 @@ impl Output for Mapper {
 }
 
 impl Input for Mapper {
-    fn input(&mut self) -> Int {
+    fn input(&mut self) -> Option<Int> {
         if self.pos_to_visit.is_empty() {
-            return 0 // Invalid input should stop the program
+            return Some(0) // Invalid input should stop the program
         }
 
         if self.pos_target.is_none() {
@@ -188,7 +167,7 @@ impl Input for Mapper {
         let direction = self.shortest_route(self.pos, target).1;
         self.last_direction = direction;
 
-        direction as Int
+        Some(movement_command(direction))
     }
 }
 
@@ -199,65 +178,28 @@ enum Tile {
     OxygenSystem,
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
-struct Pos { x: i32, y: i32 }
-
-impl Pos {
-    fn manhattan_dist(self, other: Self) -> i32 {
-        (self.x - other.x).abs() + (self.y - other.y).abs()
-    }
-}
-
-impl Pos {
-    fn translate(self, dir: Direction) -> Self {
-        let Pos { x, y } = self;
-
-        let (x, y) = match dir {
-            Direction::North => (x, y - 1),
-            Direction::South => (x, y + 1),
-            Direction::West => (x - 1, y),
-            Direction::East => (x + 1, y),
-        };
-
-        Pos { x, y }
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    North = 1,
-    South = 2,
-    West = 3,
-    East = 4,
-}
-
-impl Direction {
-    fn all() -> impl Iterator<Item = Self> {
-        use Direction::*;
-        use std::array::IntoIter;
-
-        IntoIter::new([North, South, West, East])
+fn movement_command(direction: Direction) -> Int {
+    match direction {
+        Direction::Up => 1,
+        Direction::Down => 2,
+        Direction::Left => 3,
+        Direction::Right => 4,
     }
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&code), 266);
     }
 
     #[test]
     fn p2() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&code), 274);
     }
@@ -1,13 +1,51 @@
-use std::fmt::Debug;
 use itertools::Itertools;
-use rayon::prelude::*;
+use aoc_common::{Solution, Generate, ParseError, ParseResult};
+use aoc_common::generate::Rng;
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day16.txt");
 
-pub fn day16() -> impl Debug {
-    let sequence = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day16;
 
-    (part1(&sequence), part2(&sequence))
+impl Solution for Day16 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 16;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+impl Generate for Day16 {
+    const SIZES: &'static [usize] = &[100, 250, 650, 1_500];
+
+    // `size` is the number of digits. The first 7 are the message offset, which
+    // has to point past the middle of the repeated signal
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let repeated_len = size as u64 * 10_000;
+        let (lowest, highest) = (repeated_len / 2, repeated_len.saturating_sub(8).min(9_999_999));
+        assert!(size > 7 && lowest <= highest, "No valid message offset for {} digits", size);
+
+        let offset = rng.between(lowest, highest);
+
+        let digits: String = (7..size)
+            .map(|_| char::from(b'0' + rng.between(0, 9) as u8))
+            .collect();
+
+        format!("{:07}{}\n", offset, digits)
+    }
 }
 
 pub fn part1(sequence: &[i32]) -> i32 {
@@ -25,7 +63,6 @@ pub fn part1(sequence: &[i32]) -> i32 {
 }
 
 pub fn part2(sequence: &[i32]) -> i32 {
-    // let sequence = parse_input("02935109699940807407585447034323").collect_vec();
     let message_offset = sequence.iter()
         .take(7)
         .copied()
@@ -35,7 +72,6 @@ pub fn part2(sequence: &[i32]) -> i32 {
     let mut sequence = sequence.repeat(10_000);
 
     for _phase in 0..100 {
-        println!("PHASE");
         apply_phase_2(&mut sequence, message_offset as usize);
     }
 
@@ -56,49 +92,66 @@ fn apply_phase(sequence: &mut [i32]) {
     }
 }
 
+// The pattern of a digit is zero before it, so the digits before the offset
+// never matter. Past the middle of the sequence, the pattern is all ones from
+// the digit on: each new digit is the sum of the digits from there on
 fn apply_phase_2(sequence: &mut [i32], offset: usize) {
-    let s = (offset..sequence.len())
-        .into_par_iter()
-        .map(|idx| {
-            let mut h = 0_i32;
-            let mut start_idx = idx;
-            let mut pos = true;
-
-            while start_idx < sequence.len() {
-                let end_idx = (start_idx + idx + 1).min(sequence.len());
-                let chunk = &sequence[start_idx..end_idx];
-
-                let sum = chunk.iter().copied()
-                    .sum::<i32>();
-
-                h += if pos { sum } else { -sum };
-                pos = !pos;
+    if offset < sequence.len() / 2 {
+        return apply_phase_from(sequence, offset)
+    }
 
-                start_idx = end_idx + idx + 1; // skip 0
-            }
+    let mut sum = 0;
+    for digit in sequence[offset..].iter_mut().rev() {
+        sum = (sum + *digit) % 10;
+        *digit = sum;
+    }
+}
 
-            h.abs() % 10
-        })
-        .collect::<Vec<_>>();
+// The general case, summing the runs of ones and minus ones of each pattern
+// from prefix sums
+fn apply_phase_from(sequence: &mut [i32], offset: usize) {
+    let len = sequence.len();
+    let prefix_sums: Vec<i32> = std::iter::once(0)
+        .chain(sequence[offset..].iter().scan(0, |sum, &digit| {
+            *sum += digit;
+            Some(*sum)
+        }))
+        .collect();
+    let range_sum = |start: usize, end: usize| {
+        prefix_sums[end.min(len) - offset] - prefix_sums[start.min(len) - offset]
+    };
+
+    for (idx, digit) in sequence.iter_mut().enumerate().skip(offset) {
+        let width = idx + 1;
+        let h = (idx..len).step_by(4 * width)
+            .map(|start| range_sum(start, start + width) - range_sum(start + 2 * width, start + 3 * width))
+            .sum::<i32>();
 
-    sequence[offset..].copy_from_slice(&s);
+        *digit = h.abs() % 10;
+    }
 }
 
 fn patterns() -> impl Iterator<Item = impl Iterator<Item = i32>> {
     (1..)
         .map(|repeat| {
-            std::iter::repeat(0).take(repeat)
-                .chain(std::iter::repeat(1).take(repeat))
-                .chain(std::iter::repeat(0).take(repeat))
-                .chain(std::iter::repeat(-1).take(repeat))
+            std::iter::repeat_n(0, repeat)
+                .chain(std::iter::repeat_n(1, repeat))
+                .chain(std::iter::repeat_n(0, repeat))
+                .chain(std::iter::repeat_n(-1, repeat))
                 .cycle()
                 .skip(1)
         })
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = i32> + '_ {
-    input.chars()
-        .map(|c| c.to_digit(10).expect("Malformed digit") as _)
+pub fn parse_input(input: &str) -> ParseResult<Vec<i32>> {
+    input.trim_end()
+        .char_indices()
+        .map(|(offset, c)| {
+            c.to_digit(10)
+                .map(|digit| digit as _)
+                .ok_or_else(|| ParseError::new(input, offset, "a digit"))
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -106,15 +159,40 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&input), 18_933_364);
     }
 
     #[test]
     fn p2() {
-        let input: Vec<_> = parse_input(RAW_INPUT_STR).collect();
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&input), 28_872_305);
     }
+
+    #[test]
+    fn phases() {
+        let sequence = parse_input("80871224585914546619083218645595").unwrap().repeat(3);
+
+        for offset in [0, 7, 40, 48, 90] {
+            let (mut expected, mut sequence) = (sequence.clone(), sequence.clone());
+            apply_phase(&mut expected);
+            apply_phase_2(&mut sequence, offset);
+
+            assert_eq!(sequence[offset..], expected[offset..]);
+        }
+    }
+
+    #[test]
+    fn generated() {
+        let mut rng = Rng::new(aoc_common::generate::SEED);
+
+        for size in [8, 100, 650] {
+            let input = parse_input(&Day16::generate(&mut rng, size)).unwrap();
+
+            assert_eq!(input.len(), size);
+            assert!(part2(&input) < 100_000_000);
+        }
+    }
 }
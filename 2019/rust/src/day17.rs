@@ -1,90 +1,78 @@
 use std::fmt::Debug;
-use itertools::Itertools;
-use crate::intcode::{Int, vm::{VirtualMachine, VMBuilder}, io::Output};
+use aoc_common::{Solution, ParseResult};
+use aoc_grid::{Direction, Grid, Pos};
+use crate::intcode::{Int, parse_program, vm::{VirtualMachine, VMBuilder}, io::Output};
 use std::convert::TryInto;
-use std::array::IntoIter;
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day17.txt");
 
-pub fn day17() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day17;
 
-    (part1(&program), part2(&program))
+impl Solution for Day17 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 17;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = usize;
+    type Part2 = Int;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(program: &[Int]) -> usize {
-    let mapper = VirtualMachine::load(program)
+    let map = VirtualMachine::load(program)
         .with_output_driver::<Mapper>()
         .run()
-        .into_output();
-
-    let map_width = mapper.width.expect("Did not encounter a newline somehow");
-    let tiles = mapper.tiles;
-    let map_height = tiles.len() / map_width;
-
-    let scaffold_positions = tiles.iter()
-        .enumerate()
-        .filter_map(|(pos_1d, &tile)| {
-            if tile == Tile::Scaffold {
-                Some(pos_1d)
-            } else {
-                None
-            }
-        });
+        .expect("Failed to run the program")
+        .into_output()
+        .into_map();
 
-    scaffold_positions
-        .filter_map(|pos_1d| {
-            let (x, y) = (pos_1d % map_width, pos_1d / map_width);
+    let is_scaffold = |&(_pos, &tile): &(Pos, &Tile)| tile == Tile::Scaffold;
 
-            let scaffold_left = x > 0 && tiles[pos_1d - 1] == Tile::Scaffold;
-            let scaffold_right = x < map_width - 1 && tiles[pos_1d + 1] == Tile::Scaffold;
-            let scaffold_up = y > 0 && tiles[pos_1d - map_width] == Tile::Scaffold;
-            let scaffold_down = y < map_height - 1 && tiles[pos_1d + map_width] == Tile::Scaffold;
-
-            if (scaffold_left && scaffold_right) && (scaffold_up && scaffold_down) {
-                Some(x * y)
-            } else {
-                None
-            }
-        })
+    map.iter()
+        .filter(is_scaffold)
+        .filter(|&(pos, _)| map.neighbours4(pos).filter(is_scaffold).count() == 4)
+        .map(|(Pos { x, y }, _)| (x * y) as usize)
         .sum()
 }
 
 pub fn part2(program: &[Int]) -> Int {
-    let mapper = VirtualMachine::load(program)
+    let map = VirtualMachine::load(program)
         .with_output_driver::<Mapper>()
         .run()
-        .into_output();
-
-    let map_width = mapper.width.expect("Did not encounter a newline somehow");
-    let tiles = mapper.tiles;
-    let map_height = tiles.len() / map_width;
+        .expect("Failed to run the program")
+        .into_output()
+        .into_map();
 
-    let (mut rpos, mut rdir) = tiles.iter()
-        .enumerate()
-        .find_map(|(idx, tile)| {
+    let (mut rpos, mut rdir) = map.iter()
+        .find_map(|(pos, tile)| {
             if let Tile::Robot(dir) = tile {
-                Some((Pos { x: idx % map_width, y: idx / map_width }, *dir))
+                Some((pos, *dir))
             } else {
                 None
             }
         })
         .expect("Did not find the robot somehow");
 
-    let scaffold_at = |pos: Pos| -> bool {
-        let idx = pos.y * map_width + pos.x;
-        tiles[idx] == Tile::Scaffold
-    };
-
     let next_move = |pos: Pos, rdir: Direction| -> Option<(Pos, Movement)> {
         use Movement::*;
-        let mut directions = IntoIter::new([Advance, Left, Right]);
+        let mut directions = IntoIterator::into_iter([Advance, Left, Right]);
 
         directions
             .find_map(|movement| {
-                let direction = rdir.turn(movement);
-                let next_pos = pos.translate(direction, map_width, map_height)?;
-                if scaffold_at(next_pos) {
+                let next_pos = pos + turn(rdir, movement);
+                if map.get(next_pos) == Some(&Tile::Scaffold) {
                     Some((if movement == Advance { next_pos } else { pos }, movement))
                 } else {
                     None
@@ -94,13 +82,9 @@ pub fn part2(program: &[Int]) -> Int {
 
     let mut path = vec![];
 
-    loop {
-        let (next_pos, movement) = match next_move(rpos, rdir) {
-            Some(next_move) => next_move,
-            None => break,
-        };
+    while let Some((next_pos, movement)) = next_move(rpos, rdir) {
         rpos = next_pos;
-        rdir = rdir.turn(movement);
+        rdir = turn(rdir, movement);
         path.push(movement);
     }
 
@@ -120,8 +104,6 @@ R,4,L,12,L,12,R,6
 y
 ";
 
-    println!("{}", input);
-
     let mut program = program.to_vec();
     program[0] = 2;
 
@@ -129,6 +111,7 @@ y
         .input_iter(input.chars().map(|c| c as _))
         .single_output()
         .run()
+        .expect("Failed to run the program")
         .output()
         .expect("Robot did not report collected dust")
 }
@@ -153,55 +136,19 @@ enum Tile {
     Robot(Direction),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn turn(self, movement: Movement) -> Self {
-        use Direction::*;
-
-        match movement {
-            Movement::Left => match self {
-                Up => Left,
-                Down => Right,
-                Left => Down,
-                Right => Up,
-            },
-            Movement::Right => match self {
-                Up => Right,
-                Down => Left,
-                Left => Up,
-                Right => Down,
-            },
-            Movement::Advance => self,
-        }
+fn turn(direction: Direction, movement: Movement) -> Direction {
+    match movement {
+        Movement::Left => direction.turn_left(),
+        Movement::Right => direction.turn_right(),
+        Movement::Advance => direction,
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
-struct Pos { x: usize, y: usize }
+impl Mapper {
+    fn into_map(self) -> Grid<Tile> {
+        let width = self.width.expect("Did not encounter a newline somehow");
 
-impl Pos {
-    fn translate(self, dir: Direction, x_bound: usize, y_bound: usize) -> Option<Self> {
-        let Pos { x, y } = self;
-
-        let (x, y) = match dir {
-            Direction::Up => (x, y.checked_sub(1)?),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x.checked_sub(1)?, y),
-            Direction::Right => (x + 1, y),
-        };
-
-        if x >= x_bound || y >= y_bound {
-            return None
-        }
-
-        Some(Pos { x, y })
+        Grid::from_cells(width, self.tiles)
     }
 }
 
@@ -210,38 +157,30 @@ impl Output for Mapper {
         let ascii_value = value.try_into().expect("Invalid ascii output");
 
         match ascii_value {
-            b'\n' => if let None = self.width { self.width = Some(self.tiles.len()) },
+            b'\n' => if self.width.is_none() { self.width = Some(self.tiles.len()) },
             b'#' => self.tiles.push(Tile::Scaffold),
             b'.' => self.tiles.push(Tile::Space),
-            b'^' => self.tiles.push(Tile::Robot(Direction::Up)),
-            b'v' => self.tiles.push(Tile::Robot(Direction::Down)),
-            b'<' => self.tiles.push(Tile::Robot(Direction::Left)),
-            b'>' => self.tiles.push(Tile::Robot(Direction::Right)),
-            invalid => panic!("Invalid output value: {}", invalid)
+            arrow => match Direction::from_arrow(arrow) {
+                Some(direction) => self.tiles.push(Tile::Robot(direction)),
+                None => panic!("Invalid output value: {}", arrow),
+            }
         }
-
-        print!("{}", std::char::from_u32(value as _).expect("??"));
     }
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&code), 6244);
     }
 
     #[test]
     fn p2() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&code), 1_143_523);
     }
@@ -1,15 +1,32 @@
 use std::fmt::Debug;
 use itertools::Itertools;
-use std::collections::{VecDeque, HashSet, HashMap, BinaryHeap};
-use std::cmp::Ordering;
-use std::array::IntoIter;
+use std::collections::HashMap;
+use aoc_common::{Solution, ParseError, ParseResult, search};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day18.txt");
 
-pub fn day18() -> impl Debug {
-    let maze = parse_input(RAW_INPUT_STR);
+pub struct Day18;
 
-    (part1(&maze), part2(&maze))
+impl Solution for Day18 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 18;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Maze;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -60,108 +77,52 @@ pub fn part1(maze: &Maze) -> usize {
         key_paths.insert((maze.entrance, start_pos), path);
     }
 
-    #[derive(Debug, PartialEq, Eq, Hash)]
-    struct State {
-        pos: Pos,
-        keys: KeySet,
-        steps: usize,
-        estimate: usize,
-    }
-
-    impl State {
-        fn metric(&self) -> usize {
-            self.steps + self.estimate
-        }
-    }
-
-    impl Ord for State {
-        fn cmp(&self, other: &Self) -> Ordering {
-            self.metric().cmp(&other.metric()).reverse()
-        }
-    }
-
-    impl PartialOrd for State {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            Some(self.cmp(other))
-        }
-    }
+    let key_paths = &key_paths;
+    let positioned_keys = &positioned_keys;
+
+    // Heads for a key, which can be any key whose doors on the way are unlocked
+    let collect_key = |&(pos, keys): &(Pos, KeySet)| {
+        positioned_keys.iter()
+            .filter(move |&&(_, k)| !keys.contains(k))
+            .filter_map(move |&(key_pos, k)| {
+                let &(steps, req_keys) = &key_paths[&(pos, key_pos)];
+                if keys.superset_of(req_keys) {
+                    Some(((key_pos, keys.push(k)), steps))
+                } else {
+                    None
+                }
+            })
+    };
 
-    let new_state = |pos, keys: KeySet, steps| {
-        let estimate = positioned_keys
+    // The farthest key left still needs to be reached
+    let estimate = |&(pos, keys): &(Pos, KeySet)| {
+        positioned_keys
             .iter()
             .filter(|&(_, k)| !keys.contains(*k))
             .map(|&(k_pos, _)| key_paths[&(pos, k_pos)].0)
             .max()
-            .unwrap_or(0);
-
-        State {
-            pos,
-            keys,
-            steps,
-            estimate
-        }
+            .unwrap_or(0)
     };
 
-    let initial_state = new_state(maze.entrance, KeySet::new(), 0);
-
-    let mut open_set: BinaryHeap<_> = std::iter::once(initial_state).collect();
-    let mut closed_set = HashSet::new();
-
-     while let Some(state) = open_set.pop() {
-        if state.keys.total() == positioned_keys.len() as _ {
-            return state.steps
-        }
-
-        if closed_set.contains(&(state.pos, state.keys)) {
-            continue;
-        }
-
-        closed_set.insert((state.pos, state.keys));
-
-        for &(key_pos, k) in &positioned_keys {
-            if state.keys.contains(k) {
-                continue;
-            }
-
-            let &(steps, req_keys) = &key_paths[&(state.pos, key_pos)];
-            if state.keys.superset_of(req_keys) {
-                open_set.push(new_state(
-                    key_pos,
-                    state.keys.push(k),
-                    state.steps + steps
-                ));
-            }
-        }
-    }
-
-    panic!("No solution!")
+    search::astar([(maze.entrance, KeySet::new())], collect_key, estimate)
+        .find(|((_, keys), _)| keys.total() == positioned_keys.len() as u32)
+        .map(|(_, steps)| steps)
+        .expect("No solution!")
 }
 
 fn shortest_path(maze: &Maze, start_pos: Pos, end_pos: Pos) -> Option<(usize, KeySet)> {
-    let mut open_set: VecDeque<_> = std::iter::once((start_pos, KeySet::new(), 0)).collect();
-    let mut closed_set = HashSet::new();
-
-    while let Some((pos, keys, moves)) = open_set.pop_front() {
-        if pos == end_pos {
-            return Some((moves, keys))
-        }
-
-        if closed_set.contains(&pos) {
-            continue;
-        }
-
-        closed_set.insert(pos);
-
-        for (adj_pos, key) in maze.adjacent(pos) {
-            let mut new_keys = keys.clone();
-            if let Some(key) = key {
-                new_keys = new_keys.push(key)
-            }
-            open_set.push_back((adj_pos, new_keys, moves + 1))
-        }
-    }
-
-    None
+    let mut search = search::bfs([start_pos], |&pos: &Pos| maze.adjacent(pos));
+    let (_, moves) = search.find(|&(pos, _)| pos == end_pos)?;
+
+    // Keys for the doors on the way are required to take this path
+    let req_keys = search.path(&end_pos)
+        .into_iter()
+        .fold(KeySet::new(), |keys, pos| match maze.tiles[pos] {
+            Tile::Door(key) => keys.push(key),
+            _ => keys,
+        });
+
+    Some((moves, req_keys))
 }
 
 pub fn part2(maze: &Maze) -> usize {
@@ -205,86 +166,37 @@ pub fn part2(maze: &Maze) -> usize {
         }
     }
 
-    #[derive(Debug, PartialEq, Eq, Hash)]
-    struct State {
-        robot_pos: [Pos; 4],
-        keys: KeySet,
-        steps: usize,
-        estimate: usize,
-    }
-
-    impl State {
-        fn metric(&self) -> usize {
-            self.steps + self.estimate
-        }
-    }
-
-    impl Ord for State {
-        fn cmp(&self, other: &Self) -> Ordering {
-            self.metric().cmp(&other.metric()).reverse()
-        }
-    }
-
-    impl PartialOrd for State {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            Some(self.cmp(other))
-        }
-    }
-
-    let new_state = |robot_pos, keys: KeySet, steps| {
-        let estimate = 0;
-
-        State {
-            robot_pos,
-            keys,
-            steps,
-            estimate
-        }
+    let key_paths = &key_paths;
+    let positioned_keys = &positioned_keys;
+
+    // Only one robot can reach a given key
+    let collect_key = |&(robot_pos, keys): &([Pos; 4], KeySet)| {
+        positioned_keys.iter()
+            .filter(move |&&(_, k)| !keys.contains(k))
+            .filter_map(move |&(key_pos, k)| {
+                let (robot_idx, &(steps, req_keys)) = robot_pos
+                    .iter()
+                    .enumerate()
+                    .find_map(|(idx, &pos)| {
+                        let req = key_paths.get(&(pos, key_pos))?;
+                        Some((idx, req))
+                    })
+                    .expect("No robot can move");
+
+                if keys.superset_of(req_keys) {
+                    let mut robot_pos = robot_pos;
+                    robot_pos[robot_idx] = key_pos;
+                    Some(((robot_pos, keys.push(k)), steps))
+                } else {
+                    None
+                }
+            })
     };
 
-    let initial_state = new_state(robot_pos, KeySet::new(), 0);
-
-    let mut open_set: BinaryHeap<_> = std::iter::once(initial_state).collect();
-    let mut closed_set = HashSet::new();
-
-     while let Some(state) = open_set.pop() {
-        if state.keys.total() == positioned_keys.len() as _ {
-            return state.steps
-        }
-
-        if closed_set.contains(&(state.robot_pos, state.keys)) {
-            continue;
-        }
-
-        closed_set.insert((state.robot_pos, state.keys));
-
-        for &(key_pos, k) in &positioned_keys {
-            if state.keys.contains(k) {
-                continue;
-            }
-
-            let (robot_idx, &(steps, req_keys)) = state.robot_pos
-                .iter()
-                .enumerate()
-                .find_map(|(idx, &pos)| {
-                    let req = key_paths.get(&(pos, key_pos))?;
-                    Some((idx, req))
-                })
-                .expect("No robot can move");
-
-            if state.keys.superset_of(req_keys) {
-                let mut robot_pos = state.robot_pos.clone();
-                robot_pos[robot_idx] = key_pos;
-                open_set.push(new_state(
-                    robot_pos,
-                    state.keys.push(k),
-                    state.steps + steps
-                ));
-            }
-        }
-    }
-
-    panic!("No solution!")
+    search::dijkstra([(robot_pos, KeySet::new())], collect_key)
+        .find(|((_, keys), _)| keys.total() == positioned_keys.len() as u32)
+        .map(|(_, steps)| steps)
+        .expect("No solution!")
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -303,42 +215,42 @@ pub struct Maze {
 }
 
 impl Maze {
-    fn adjacent<'a>(&'a self, pos: Pos) -> impl Iterator<Item = (Pos, Option<u8>)> + 'a {
-        IntoIter::new([pos - 1, pos + 1, pos + self.width, pos - self.width])
-            .filter_map(move |pos| match self.tiles[pos] {
-                Tile::Passage | Tile::Key(_) => Some((pos, None)),
-                Tile::Door(c) => Some((pos, Some(c))),
-                _ => None
-            })
+    fn adjacent<'a>(&'a self, pos: Pos) -> impl Iterator<Item = Pos> + 'a {
+        IntoIterator::into_iter([pos - 1, pos + 1, pos + self.width, pos - self.width])
+            .filter(move |&pos| self.tiles[pos] != Tile::Wall)
     }
 }
 
 type Pos = usize;
 
-pub fn parse_input(input: &str) -> Maze {
+pub fn parse_input(input: &str) -> ParseResult<Maze> {
     let mut width = None;
     let mut entrance = None;
+    let mut tiles = Vec::with_capacity(input.len());
 
-    let tiles = input.lines()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            width = Some(line.len());
-            line.bytes().enumerate().map(|(x, ch)| match ch {
+    for line in input.lines() {
+        if *width.get_or_insert(line.len()) != line.len() {
+            return Err(ParseError::after(input, line, "lines of equal width"));
+        }
+
+        for (x, ch) in line.bytes().enumerate() {
+            let tile = match ch {
                 b'#' => Tile::Wall,
                 b'.' => Tile::Passage,
-                b'@' => { entrance = Some(y * line.len() + x); Tile::Passage },
+                b'@' => { entrance = Some(tiles.len()); Tile::Passage },
                 b'A'..=b'Z' => Tile::Door(ch.to_ascii_lowercase() - b'a'),
                 b'a'..=b'z' => Tile::Key(ch - b'a'),
-                invalid => panic!("Invalid maze character: {}", invalid),
-            }).collect_vec()
-        })
-        .collect();
+                _ => return Err(ParseError::at(input, &line[x..], "a maze tile (`#`, `.`, `@`, a door or a key)")),
+            };
+            tiles.push(tile);
+        }
+    }
 
-    Maze {
+    Ok(Maze {
         tiles,
-        width: width.expect("Empty maze"),
-        entrance: entrance.expect("Did not find any entrance!")
-    }
+        width: width.ok_or_else(|| ParseError::end(input, "a maze"))?,
+        entrance: entrance.ok_or_else(|| ParseError::end(input, "an entrance (`@`)"))?,
+    })
 }
 
 #[cfg(test)]
@@ -346,14 +258,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input = parse_input(RAW_INPUT_STR);
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&input), 3_646);
     }
 
     #[test]
     fn p2() {
-        let input = parse_input(RAW_INPUT_STR);
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&input), 1_730);
     }
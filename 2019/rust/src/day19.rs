@@ -1,15 +1,32 @@
 use std::fmt::Debug;
-use itertools::Itertools;
-use crate::intcode::{Int, vm::{VirtualMachine, VMBuilder}};
-use std::array::IntoIter;
+use aoc_common::{Solution, ParseResult};
+use crate::intcode::{Int, parse_program, vm::{VirtualMachine, VMBuilder}};
 use rayon::prelude::*;
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day19.txt");
 
-pub fn day19() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day19;
 
-    (part1(&program), part2(&program))
+impl Solution for Day19 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 19;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(program: &[Int]) -> usize {
@@ -29,7 +46,7 @@ pub fn part2(program: &[Int]) -> usize {
     let (x, y) = coordinates
         .find_first(|&(x, y)| {
             let corners = [(x, y), (x + 99, y), (x, y + 99), (x + 99, y + 99)];
-            IntoIter::new(corners)
+            IntoIterator::into_iter(corners)
                 .all(|(x, y)| beam_state(program, x, y) == DroneState::PulledIn)
         })
         .expect("No place to put the ship");
@@ -39,9 +56,10 @@ pub fn part2(program: &[Int]) -> usize {
 
 fn beam_state(program: &[Int], x: usize, y: usize) -> DroneState {
     let raw_beam_state = VirtualMachine::load(program)
-        .input_iter(IntoIter::new([x as _, y as _]))
+        .input_iter(IntoIterator::into_iter([x as _, y as _]))
         .single_output()
         .run()
+        .expect("Failed to run the program")
         .output()
         .expect("Did not get a beam state output");
 
@@ -66,24 +84,19 @@ enum DroneState {
     PulledIn,
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&code), 152);
     }
 
     #[test]
     fn p2() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&code), 10_730_411);
     }
@@ -1,63 +1,51 @@
 use std::fmt::Debug;
-use std::collections::{HashMap, HashSet, VecDeque};
-use itertools::Itertools;
-use std::array::IntoIter;
+use std::collections::HashMap;
+use aoc_common::{Solution, ParseError, ParseResult, search};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day20.txt");
 
-pub fn day20() -> impl Debug {
-    let maze = parse_input(RAW_INPUT_STR);
+pub struct Day20;
 
-    (part1(&maze), part2(&maze))
-}
-
-pub fn part1(maze: &Maze) -> usize {
-    let mut open_set: VecDeque<_> = std::iter::once((maze.entrance, 0)).collect();
-    let mut closed_set = HashSet::new();
+impl Solution for Day20 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 20;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
 
-    while let Some((pos, moves)) = open_set.pop_front() {
-        if pos == maze.exit {
-            return moves
-        }
+    type Parsed<'a> = Maze;
+    type Part1 = usize;
+    type Part2 = usize;
 
-        if closed_set.contains(&pos) {
-            continue;
-        }
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
 
-        closed_set.insert(pos);
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-        for (adj_pos, _) in maze.adjacent(pos) {
-            open_set.push_back((adj_pos, moves + 1))
-        }
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
     }
+}
 
-    panic!("No solution!")
+pub fn part1(maze: &Maze) -> usize {
+    search::bfs([maze.entrance], |&pos: &Pos| maze.adjacent(pos).map(|(adj_pos, _)| adj_pos))
+        .find(|&(pos, _)| pos == maze.exit)
+        .map(|(_, moves)| moves)
+        .expect("No solution!")
 }
 
 pub fn part2(maze: &Maze) -> usize {
-    let mut open_set: VecDeque<_> = std::iter::once((maze.entrance, 0, 0)).collect();
-    let mut closed_set = HashSet::new();
-
-    while let Some((pos, level, moves)) = open_set.pop_front() {
-        if pos == maze.exit && level == 0 {
-            return moves
-        }
-
-        if closed_set.contains(&(pos, level)) {
-            continue;
-        }
-
-        closed_set.insert((pos, level));
-
-        for (adj_pos, delta_level) in maze.adjacent(pos) {
-            let next_level = level + delta_level;
-            if next_level >= 0 {
-                open_set.push_back((adj_pos, next_level, moves + 1))
-            }
-        }
-    }
-
-    panic!("No solution!")
+    let next_levels = |&(pos, level): &(Pos, i32)| {
+        maze.adjacent(pos)
+            .map(move |(adj_pos, delta_level)| (adj_pos, level + delta_level))
+            .filter(|&(_, next_level)| next_level >= 0)
+    };
+
+    search::bfs([(maze.entrance, 0)], next_levels)
+        .find(|&(state, _)| state == (maze.exit, 0))
+        .map(|(_, moves)| moves)
+        .expect("No solution!")
 }
 
 #[derive(Debug, Clone)]
@@ -103,43 +91,46 @@ enum Tile {
 
 type Pos = usize;
 
-pub fn parse_input(input: &str) -> Maze {
+pub fn parse_input(input: &str) -> ParseResult<Maze> {
     let mut width = None;
+    let mut raw_tiles = Vec::with_capacity(input.len());
 
-    let raw_tiles = input.lines()
-        .flat_map(|line| {
-            width = Some(line.len());
-            line.bytes()
-        })
-        .collect_vec();
+    for line in input.lines() {
+        if *width.get_or_insert(line.len()) != line.len() {
+            return Err(ParseError::after(input, line, "lines of equal width"));
+        }
+        raw_tiles.extend(line.bytes());
+    }
 
-    let width = width.expect("Empty maze");
+    let width = width.ok_or_else(|| ParseError::end(input, "a maze"))?;
+    // Every line is followed by a newline, except maybe the last one
+    let error_at = |idx: Pos, expected: &str| ParseError::new(input, idx / width * (width + 1) + idx % width, expected);
 
     let tiles = raw_tiles.iter()
         .copied()
         .enumerate()
         .map(|(idx, raw_tile)| match raw_tile {
-            b' ' | b'#' => Tile::Wall,
-            b'.' => Tile::Passage,
+            b' ' | b'#' => Ok(Tile::Wall),
+            b'.' => Ok(Tile::Passage),
             b'A'..=b'Z' => {
                 let adjacent_letter = adjacent_pos(idx, width)
                     .find_map(|pos| match raw_tiles.get(pos)? {
                         ch @ b'A'..=b'Z' => Some(*ch),
                         _ => None
                     })
-                    .expect("single letter ?");
+                    .ok_or_else(|| error_at(idx, "a two letter portal"))?;
 
                 let adjacent_passage = adjacent_pos(idx, width)
                     .find(|&pos| raw_tiles.get(pos) == Some(&b'.'));
 
                 match adjacent_passage {
-                    Some(pos) => Tile::Teleport([raw_tile, adjacent_letter], pos),
-                    None => Tile::Wall,
+                    Some(pos) => Ok(Tile::Teleport([raw_tile, adjacent_letter], pos)),
+                    None => Ok(Tile::Wall),
                 }
             },
-            invalid => panic!("Invalid maze descriptor character: {}", invalid)
+            _ => Err(error_at(idx, "a maze tile (` `, `#`, `.` or a portal letter)")),
         })
-        .collect_vec();
+        .collect::<ParseResult<Vec<_>>>()?;
 
     let mut entrance = None;
     let mut exit = None;
@@ -167,28 +158,28 @@ pub fn parse_input(input: &str) -> Maze {
                                     _ => None
                                 }
                             })
-                            .expect("No connected portal!");
+                            .ok_or_else(|| error_at(pos, "a portal with a matching exit"));
 
-                        Some((pos, *connected_portal))
+                        Some(connected_portal.map(|connected_portal| (pos, *connected_portal)))
                     },
                 }
             } else {
                 None
             }
         })
-        .collect();
+        .collect::<ParseResult<_>>()?;
 
-    Maze {
+    Ok(Maze {
         tiles,
         width,
         teleports,
-        entrance: entrance.expect("Did not find any entrance!"),
-        exit: exit.expect("Did not find any exit!"),
-    }
+        entrance: entrance.ok_or_else(|| ParseError::end(input, "an entrance portal (`AA`)"))?,
+        exit: exit.ok_or_else(|| ParseError::end(input, "an exit portal (`ZZ`)"))?,
+    })
 }
 
 fn adjacent_pos(pos: Pos, width: usize) -> impl Iterator<Item = Pos> {
-    IntoIter::new([
+    IntoIterator::into_iter([
         pos - 1,
         pos + 1,
         pos - width,
@@ -201,14 +192,14 @@ mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let input = parse_input(RAW_INPUT_STR);
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&input), 606);
     }
 
     #[test]
     fn p2() {
-        let input = parse_input(RAW_INPUT_STR);
+        let input = parse_input(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&input), 7_186);
     }
@@ -1,13 +1,32 @@
 use std::fmt::{self, Display, Debug};
 use itertools::Itertools;
-use crate::intcode::{Int, vm::{VirtualMachine, VMBuilder}};
+use aoc_common::{Solution, ParseResult};
+use crate::intcode::{Int, parse_program, vm::{VirtualMachine, VMBuilder}};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day21.txt");
 
-pub fn day21() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day21;
 
-    (part1(&program), part2(&program))
+impl Solution for Day21 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 21;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
+
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = Int;
+    type Part2 = Int;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
 pub fn part1(program: &[Int]) -> Int {
@@ -46,6 +65,7 @@ fn run_springscript(program: &[Int], script: &[Instruction]) -> Int {
         .input_iter(script_bytes)
         .single_output()
         .run()
+        .expect("Failed to run the program")
         .output()
         .expect("Failed to reach the hull!")
 }
@@ -87,30 +107,25 @@ impl Display for Instruction {
             Instruction::And(r1, r2) => write!(f, "AND {} {}", r1, r2),
             Instruction::Not(r1, r2) => write!(f, "NOT {} {}", r1, r2),
             Instruction::Or(r1, r2) => write!(f, "OR {} {}", r1, r2),
-            Instruction::Walk => write!(f, "WALK\n"),
-            Instruction::Run => write!(f, "RUN\n"),
+            Instruction::Walk => writeln!(f, "WALK"),
+            Instruction::Run => writeln!(f, "RUN"),
         }
     }
 }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part1(&code), 19_353_692);
     }
 
     #[test]
     fn p2() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&code), 1_142_048_514);
     }
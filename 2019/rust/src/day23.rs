@@ -1,162 +1,150 @@
 use std::fmt::{Debug};
-use itertools::Itertools;
-use crate::intcode::{Int, vm::VirtualMachine, io::{Input, Output}};
-use std::{sync::{Arc, Mutex}};
-use std::collections::{VecDeque, HashSet};
+use aoc_common::{Solution, ParseResult};
+use crate::intcode::{Int, parse_program, vm::{VirtualMachine, IoEvent}, io::ext::Queue};
 
 const RAW_INPUT_STR: &str = include_str!("../../inputs/day23.txt");
 
-pub fn day23() -> impl Debug {
-    let program = parse_input(RAW_INPUT_STR).collect_vec();
+pub struct Day23;
 
-    (part1(&program), part2(&program))
-}
-
-pub fn part1(program: &[Int]) -> Int {
-    let network = Arc::new(Mutex::new(Network::new(50)));
-
-    let vms = (0..50)
-        .map(|addr| VirtualMachine::new(program, BoundNet::new(network.clone(), addr)));
+impl Solution for Day23 {
+    const YEAR: u32 = 2019;
+    const DAY: u32 = 23;
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;
 
-    let handles = vms.map(|vm| std::thread::spawn(|| vm.run() ))
-        .collect_vec();
+    type Parsed<'a> = Vec<Int>;
+    type Part1 = Int;
+    type Part2 = Int;
 
-    for handle in handles {
-        handle.join().expect("Failed to run vm");
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_program(input)
     }
 
-    let net = network.lock().unwrap();
-
-    net.nat
-        .expect("Did not get a message on addr 255")
-        .y
-}
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
 
-pub fn part2(_program: &[Int]) -> Int {
-    42
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
 }
 
-#[derive(Debug)]
-struct Network {
-    queue: Vec<VecDeque<Int>>,
-    nat: Option<Message>,
-    nat_values_sent: HashSet<Int>,
-    input_requests_while_empty: usize,
-}
+const NETWORK_SIZE: usize = 50;
+const NAT_ADDR: Int = 255;
 
-impl Network {
-    fn new(size: usize) -> Self {
-        Self {
-            queue: (0..size)
-                .map(|x|
-                    std::iter::once(x as _)
-                    .collect()
-                )
-                .collect(),
-            nat: None,
-            nat_values_sent: HashSet::new(),
-            input_requests_while_empty: 0,
-        }
-    }
-}
+pub fn part1(program: &[Int]) -> Int {
+    let mut network = Network::new(program, NETWORK_SIZE);
 
-struct BoundNet {
-    net: Arc<Mutex<Network>>,
-    addr: Int,
-    input_state: InputState,
-}
+    loop {
+        network.run_round();
 
-impl BoundNet {
-    fn new(net: Arc<Mutex<Network>>, addr: Int) -> Self {
-        Self {
-            net,
-            addr,
-            input_state: InputState::Dest,
+        if let Some(Message { y, .. }) = network.nat_packets.first() {
+            return *y
         }
     }
 }
 
-impl Output for BoundNet {
-    fn output(&mut self, value: Int) {
-        self.net.lock().unwrap()
-            .input_requests_while_empty = 0;
+pub fn part2(program: &[Int]) -> Int {
+    let mut network = Network::new(program, NETWORK_SIZE);
+    let mut last_y_delivered = None;
+    let mut quiet_rounds = 0;
 
-        let next_state = match self.input_state {
-            InputState::Dest => InputState::X { addr: value },
-            InputState::X { addr } => InputState::Y { addr, x: value },
-            InputState::Y { addr, x } => {
-                let y = value;
+    loop {
+        quiet_rounds = if network.run_round() { 0 } else { quiet_rounds + 1 };
 
-                let mut net = self.net.lock().unwrap();
-
-                if addr == 255 {
-                    net.nat = Some(Message { x, y });
-                } else {
-                    let queue = &mut net.queue[addr as usize];
+        // The first round is quiet too, the computers only reading their address in it
+        if quiet_rounds < 2 {
+            continue
+        }
+        quiet_rounds = 0;
 
-                    queue.push_back(x);
-                    queue.push_back(y);
-                }
+        let Message { x, y } = network.nat_packets.pop()
+            .expect("Network idle without a packet for the NAT");
+        network.nat_packets.clear();
 
-                InputState::Dest
-            },
-        };
+        if last_y_delivered.replace(y) == Some(y) {
+            return y
+        }
 
-        self.input_state = next_state;
+        network.computers[0].push_input(x);
+        network.computers[0].push_input(y);
     }
 }
 
-impl Input for BoundNet {
-    fn input(&mut self) -> Int {
-        let mut net = self.net.lock().unwrap();
-
-        if net.queue.iter().all(|q| q.is_empty()) {
-            if net.input_requests_while_empty >= 100000 {
-                if let Some(Message { x, y }) = net.nat.take() {
-                    if !net.nat_values_sent.insert(y) {
-                        dbg!(y);
-                    }
-                    net.queue[0].push_back(x);
-                    net.queue[0].push_back(y);
+struct Network {
+    computers: Vec<VirtualMachine<Queue>>,
+    nat_packets: Vec<Message>,
+}
+
+impl Network {
+    fn new(program: &[Int], size: usize) -> Self {
+        let computers = (0..size)
+            .map(|addr| {
+                let mut computer = VirtualMachine::load(program).pausable();
+                computer.push_input(addr as Int);
+                computer
+            })
+            .collect();
+
+        Self { computers, nat_packets: Vec::new() }
+    }
+
+    // Gives every computer a turn, until it waits on an empty queue.
+    // Returns whether any packet was sent
+    fn run_round(&mut self) -> bool {
+        let mut sent = false;
+
+        for addr in 0..self.computers.len() {
+            loop {
+                match self.computers[addr].run_until_io().expect("Failed to run a computer") {
+                    IoEvent::Output(dest) => {
+                        let mut next_value = || match self.computers[addr].run_until_io() {
+                            Ok(IoEvent::Output(value)) => value,
+                            event => panic!("Incomplete packet from {}: {:?}", addr, event),
+                        };
+                        let message = Message { x: next_value(), y: next_value() };
+                        self.send(dest, message);
+                        sent = true;
+                    },
+                    // Queues are only empty for a turn, and packets arriving meanwhile come after the -1
+                    IoEvent::NeedsInput => {
+                        self.computers[addr].push_input(-1);
+                        break
+                    },
+                    IoEvent::Halted => break,
                 }
-            } else {
-                net.input_requests_while_empty += 1;
             }
         }
 
-        net.queue[self.addr as usize]
-            .pop_front()
-            .unwrap_or(-1)
+        sent
     }
-}
 
-enum InputState {
-    Dest,
-    X { addr: Int },
-    Y { addr: Int, x: Int },
+    fn send(&mut self, dest: Int, Message { x, y }: Message) {
+        if dest == NAT_ADDR {
+            return self.nat_packets.push(Message { x, y })
+        }
+
+        let computer = &mut self.computers[dest as usize];
+        computer.push_input(x);
+        computer.push_input(y);
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 struct Message { x: Int, y: Int }
 
-pub fn parse_input(input: &str) -> impl Iterator<Item = Int> + '_ {
-    input.split(',')
-        .map(|raw_number| raw_number.parse().expect("Invalid integer code"))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
     fn p1() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
-        assert_eq!(part1(&code), 19_353_692);
+        assert_eq!(part1(&code), 16_549);
     }
 
     #[test]
     fn p2() {
-        let code = parse_input(RAW_INPUT_STR).collect_vec();
+        let code = parse_program(RAW_INPUT_STR).unwrap();
 
         assert_eq!(part2(&code), 11_462);
     }
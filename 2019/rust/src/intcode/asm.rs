@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use aoc_common::{ParseError, ParseResult};
+use super::Int;
+
+// The syntax is the one of the disassembler's listings:
+//
+//   loop:   in [rb+1]             ; labels end with a colon, comments start with a semicolon
+//           add [counter], 1, [counter]
+//           jt [rb+1], loop       ; immediates are bare: numbers, 'c'haracters, labels, label+2
+//           out 'A'               ; positions are [addr] and relative addresses [rb+offset]
+//           hlt
+//   counter: .data 0              ; .data, .text "with escapes\n" and .zero for a number of zeroes
+//
+// The relative base doubles as a stack pointer for `push`, `pop`, `call` and
+// `ret`, and `jmp` always jumps
+pub fn assemble(source: &str) -> ParseResult<Vec<Int>> {
+    let mut assembler = Assembler { source, words: Vec::new(), labels: HashMap::new() };
+
+    for line in source.lines() {
+        assembler.line(line)?;
+    }
+
+    assembler.words.iter()
+        .map(|word| assembler.resolve(word))
+        .collect()
+}
+
+// The mnemonics with their op codes and parameter counts
+const OPS: [(&str, Int, usize); 10] = [
+    ("add", 1, 3),
+    ("mul", 2, 3),
+    ("in",  3, 1),
+    ("out", 4, 1),
+    ("jt",  5, 2),
+    ("jf",  6, 2),
+    ("lt",  7, 3),
+    ("eq",  8, 3),
+    ("rb",  9, 1),
+    ("hlt", 99, 0),
+];
+
+// A sum of numbers and label addresses
+type Expr<'a> = Vec<(Int, Term<'a>)>;
+
+#[derive(Debug, Clone, Copy)]
+enum Term<'a> {
+    Number(Int),
+    Label(&'a str),
+}
+
+#[derive(Debug, Clone)]
+enum Operand<'a> {
+    Position(Expr<'a>),
+    Immediate(Expr<'a>),
+    Relative(Expr<'a>),
+}
+
+use Operand::*;
+
+fn number<'a>(value: Int) -> Expr<'a> {
+    vec![(1, Term::Number(value))]
+}
+
+struct Assembler<'a> {
+    source: &'a str,
+    words: Vec<Expr<'a>>,
+    labels: HashMap<&'a str, Int>,
+}
+
+impl<'a> Assembler<'a> {
+    fn line(&mut self, line: &'a str) -> ParseResult<()> {
+        let mut rest = split_unquoted(line, ';')[0].trim();
+
+        // Labels
+        while let Some((label, after)) = rest.split_once(':').filter(|(label, _)| is_identifier(label.trim_end())) {
+            let label = label.trim_end();
+            if label == "rb" || self.labels.insert(label, self.words.len() as Int).is_some() {
+                return Err(ParseError::at(self.source, label, "a label that is not already defined"));
+            }
+            rest = after.trim_start();
+        }
+
+        if rest.is_empty() {
+            return Ok(())
+        }
+
+        let (name, operands) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let operands: Vec<_> = split_unquoted(operands, ',').into_iter()
+            .map(str::trim)
+            .filter(|operand| !operand.is_empty())
+            .collect();
+
+        let expect_count = |count: usize| match operands.len() == count {
+            true => Ok(()),
+            false => Err(ParseError::at(self.source, name, format!("{} operands for {}", count, name))),
+        };
+
+        match name {
+            ".data" => for operand in operands {
+                let expr = self.expr(operand)?;
+                self.words.push(expr);
+            },
+            ".text" => {
+                expect_count(1)?;
+                let text = self.string(operands[0])?;
+                self.words.extend(text.chars().map(|c| number(c as Int)));
+            },
+            ".zero" => {
+                expect_count(1)?;
+                let count = aoc_common::parse::field(self.source, operands[0], "a number of zeroes")?;
+                self.words.extend(std::iter::repeat_n(number(0), count));
+            },
+            "jmp" => {
+                expect_count(1)?;
+                let target = self.operand(operands[0])?;
+                self.instruction(name, 5, vec![Immediate(number(1)), target])?;
+            },
+            "push" => {
+                expect_count(1)?;
+                let value = self.operand(operands[0])?;
+                self.instruction(name, 1, vec![value, Immediate(number(0)), Relative(number(0))])?;
+                self.instruction(name, 9, vec![Immediate(number(1))])?;
+            },
+            "pop" => {
+                expect_count(1)?;
+                let dest = self.operand(operands[0])?;
+                self.instruction(name, 1, vec![Relative(number(-1)), Immediate(number(0)), dest])?;
+                self.instruction(name, 9, vec![Immediate(number(-1))])?;
+            },
+            "call" => {
+                expect_count(1)?;
+                let target = self.operand(operands[0])?;
+                // Past the push of the return address and the jump
+                let return_addr = self.words.len() as Int + 4 + 2 + 3;
+                self.instruction(name, 1, vec![Immediate(number(return_addr)), Immediate(number(0)), Relative(number(0))])?;
+                self.instruction(name, 9, vec![Immediate(number(1))])?;
+                self.instruction(name, 5, vec![Immediate(number(1)), target])?;
+            },
+            "ret" => {
+                expect_count(0)?;
+                self.instruction(name, 9, vec![Immediate(number(-1))])?;
+                self.instruction(name, 5, vec![Immediate(number(1)), Relative(number(0))])?;
+            },
+            _ => {
+                let &(_, op_code, param_count) = OPS.iter()
+                    .find(|(mnemonic, _, _)| *mnemonic == name)
+                    .ok_or_else(|| ParseError::at(self.source, name, "a mnemonic or a directive"))?;
+                expect_count(param_count)?;
+
+                let params = operands.iter()
+                    .map(|operand| self.operand(operand))
+                    .collect::<ParseResult<_>>()?;
+                self.instruction(name, op_code, params)?;
+            },
+        }
+
+        Ok(())
+    }
+
+    fn instruction(&mut self, name: &'a str, op_code: Int, params: Vec<Operand<'a>>) -> ParseResult<()> {
+        let writes = matches!(op_code, 1 | 2 | 3 | 7 | 8);
+        if writes && matches!(params.last(), Some(Immediate(_))) {
+            return Err(ParseError::at(self.source, name, format!("a position or relative destination for {}", name)));
+        }
+
+        let descriptor = params.iter()
+            .rev()
+            .fold(0, |modes, param| modes * 10 + match param {
+                Position(_) => 0,
+                Immediate(_) => 1,
+                Relative(_) => 2,
+            });
+
+        self.words.push(number(descriptor * 100 + op_code));
+        self.words.extend(params.into_iter().map(|(Position(expr) | Immediate(expr) | Relative(expr))| expr));
+
+        Ok(())
+    }
+
+    fn operand(&self, raw: &'a str) -> ParseResult<Operand<'a>> {
+        let Some(inner) = raw.strip_prefix('[') else {
+            return Ok(Immediate(self.expr(raw)?))
+        };
+        let inner = inner.strip_suffix(']')
+            .ok_or_else(|| ParseError::after(self.source, raw, "a closing bracket"))?
+            .trim();
+
+        match inner.strip_prefix("rb") {
+            Some("") => Ok(Relative(number(0))),
+            Some(offset) if offset.starts_with(['+', '-', ' ']) => Ok(Relative(self.expr(offset)?)),
+            _ => Ok(Position(self.expr(inner)?)),
+        }
+    }
+
+    fn expr(&self, raw: &'a str) -> ParseResult<Expr<'a>> {
+        let mut terms = Vec::new();
+        let mut rest = raw.trim_start();
+        let mut sign = match rest.strip_prefix(['+', '-']) {
+            Some(after) => {
+                let sign = if rest.starts_with('-') { -1 } else { 1 };
+                rest = after.trim_start();
+                sign
+            },
+            None => 1,
+        };
+
+        loop {
+            let len = match rest.chars().next() {
+                Some('\'') => {
+                    let body_len = match rest[1..].chars().next() {
+                        Some('\\') => 2,
+                        Some(c) => c.len_utf8(),
+                        None => 0,
+                    };
+                    let closed = rest.get(1 + body_len..).is_some_and(|after| after.starts_with('\''));
+                    closed.then_some(body_len + 2)
+                },
+                Some(c) if c.is_ascii_digit() => Some(rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len())),
+                Some(c) if c.is_alphabetic() || c == '_' => Some(rest.find(|c: char| !is_identifier_char(c)).unwrap_or(rest.len())),
+                _ => None,
+            };
+            let Some(len) = len else {
+                return Err(ParseError::at(self.source, rest, "a number, a 'c'haracter or a label"))
+            };
+
+            let (token, after) = rest.split_at(len);
+            let term = match token.chars().next() {
+                Some('\'') => Term::Number(self.character(token)? as Int),
+                Some(c) if c.is_ascii_digit() => Term::Number(aoc_common::parse::field(self.source, token, "a number")?),
+                _ => Term::Label(token),
+            };
+            terms.push((sign, term));
+
+            rest = after.trim_start();
+            sign = match rest.chars().next() {
+                None => return Ok(terms),
+                Some('+') => 1,
+                Some('-') => -1,
+                Some(_) => return Err(ParseError::at(self.source, rest, "+, - or the end of the operand")),
+            };
+            rest = rest[1..].trim_start();
+        }
+    }
+
+    fn character(&self, raw: &'a str) -> ParseResult<char> {
+        let mut chars = unescape(self.source, &raw[1..raw.len() - 1])?.into_iter();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(ParseError::at(self.source, raw, "a single character")),
+        }
+    }
+
+    fn string(&self, raw: &'a str) -> ParseResult<String> {
+        let inner = raw.strip_prefix('"')
+            .and_then(|inner| inner.strip_suffix('"'))
+            .filter(|_| raw.len() >= 2)
+            .ok_or_else(|| ParseError::at(self.source, raw, "a quoted string"))?;
+
+        unescape(self.source, inner).map(|chars| chars.into_iter().collect())
+    }
+
+    fn resolve(&self, expr: &Expr<'a>) -> ParseResult<Int> {
+        expr.iter()
+            .map(|&(sign, term)| match term {
+                Term::Number(value) => Ok(sign * value),
+                Term::Label(label) => self.labels.get(label)
+                    .map(|&addr| sign * addr)
+                    .ok_or_else(|| ParseError::at(self.source, label, "a defined label")),
+            })
+            .sum()
+    }
+}
+
+fn unescape(source: &str, raw: &str) -> ParseResult<Vec<char>> {
+    let mut chars = raw.char_indices();
+    let mut unescaped = Vec::new();
+
+    while let Some((idx, c)) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue
+        }
+
+        let escaped = match chars.next() {
+            Some((_, 'n')) => '\n',
+            Some((_, 't')) => '\t',
+            Some((_, 'r')) => '\r',
+            Some((_, '0')) => '\0',
+            Some((_, c @ ('\\' | '\'' | '"'))) => c,
+            _ => return Err(ParseError::at(source, &raw[idx..], "an escape among \\n, \\t, \\r, \\0, \\\\, \\' and \\\"")),
+        };
+        unescaped.push(escaped);
+    }
+
+    Ok(unescaped)
+}
+
+// Splits on `separator` outside of quotes
+fn split_unquoted(raw: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (idx, c) in raw.char_indices() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => { },
+            (None, '"' | '\'') => quote = Some(c),
+            (None, c) if c == separator => {
+                parts.push(&raw[start..idx]);
+                start = idx + c.len_utf8();
+            },
+            (None, _) => { },
+        }
+    }
+    parts.push(&raw[start..]);
+
+    parts
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn is_identifier(raw: &str) -> bool {
+    raw.starts_with(|c: char| c.is_alphabetic() || c == '_') && raw.chars().all(is_identifier_char)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solution;
+    use crate::intcode::{disasm::{self, Line}, vm::{VirtualMachine, VMBuilder}};
+
+    #[test]
+    fn instructions() {
+        let program = assemble("
+            start:  add [rb+1], -2, [data]  ; comment
+                    jt 'A', start
+                    rb [rb-3]
+                    hlt
+            data:   .data 7, start + 2, -data, ';'
+                    .text \"a,\\\"b\\\"\\n\"
+                    .zero 2
+        ").unwrap();
+
+        assert_eq!(program, [
+            1201, 1, -2, 10,
+            1105, 65, 0,
+            209, -3,
+            99,
+            7, 2, -10, 59,
+            97, 44, 34, 98, 34, 10,
+            0, 0,
+        ]);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |source| assemble(source).unwrap_err().to_string();
+
+        assert_eq!(error("add 1, 2, 3"), "line 1, column 1: expected a position or relative destination for add");
+        assert_eq!(error("\n  jmp nowhere"), "line 2, column 7: expected a defined label");
+        assert_eq!(error("mov 1, [2]"), "line 1, column 1: expected a mnemonic or a directive");
+        assert_eq!(error("out 1, 2"), "line 1, column 1: expected 1 operands for out");
+        assert_eq!(error("a: hlt\na: hlt"), "line 2, column 1: expected a label that is not already defined");
+        assert_eq!(error("out [4"), "line 1, column 7: expected a closing bracket");
+        assert_eq!(error("out 1 2"), "line 1, column 7: expected +, - or the end of the operand");
+        assert_eq!(error("out '\\"), "line 1, column 5: expected a number, a 'c'haracter or a label");
+        assert_eq!(error(".text \"\\q\""), "line 1, column 8: expected an escape among \\n, \\t, \\r, \\0, \\\\, \\' and \\\"");
+    }
+
+    #[test]
+    fn stack() {
+        // Prints the sum of the squares of its inputs, with a subroutine
+        let program = assemble("
+                    rb stack
+            loop:   in [value]
+                    jf [value], done
+                    push [value]
+                    call square
+                    pop [square_result]
+                    add [sum], [square_result], [sum]
+                    jmp loop
+            done:   out [sum]
+                    hlt
+
+            ; Replaces the top of the stack with its square
+            square: mul [rb-2], [rb-2], [rb-2]
+                    ret
+
+            value:  .data 0
+            sum:    .data 0
+            square_result: .data 0
+            stack:  .zero 8
+        ").unwrap();
+
+        let output = VirtualMachine::load(&program)
+            .input_iter(vec![3, 4, 12, 0].into_iter())
+            .single_output()
+            .run()
+            .unwrap()
+            .into_output();
+
+        assert_eq!(output.get(), Some(9 + 16 + 144));
+    }
+
+    #[test]
+    fn round_trip() {
+        let program = crate::intcode::parse_program(crate::day21::Day21::EMBEDDED_INPUT).unwrap();
+
+        let source: String = disasm::disassemble(&program).lines.iter()
+            .map(|line| match line {
+                Line::Code { instr, .. } => format!("{}\n", instr),
+                Line::Text { text, .. } => format!(".text {:?}\n", text),
+                Line::Data { values, .. } => format!(".data {}\n", values.iter().map(Int::to_string).collect::<Vec<_>>().join(", ")),
+            })
+            .collect();
+
+        assert_eq!(assemble(&source).unwrap(), program);
+    }
+}
@@ -1,58 +1,82 @@
-use super::{Int, io::IO, memory::Memory, ops::Instruction};
+use super::{Int, error::{Fault, VmError, VmResult}, io::IO, memory::Memory, ops::Instruction};
 
 #[derive(Default)]
-pub struct CPU {
+pub struct Cpu {
     pc: usize,
 }
 
-impl CPU {
-    pub fn exec_next(&mut self, mem: &mut Memory, world: &mut impl IO) -> ExecResult {
+impl Cpu {
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    // The instruction about to be executed
+    pub fn peek(&self, mem: &mut Memory) -> VmResult<Instruction> {
         let instr_data = mem.read_4(self.pc);
 
-        let (instr, size) = Instruction::decode(instr_data);
-        self.pc += size;
+        Instruction::decode(instr_data)
+            .map(|(instr, _)| instr)
+            .ok_or(VmError::UnknownInstruction { pc: self.pc, opcode: instr_data[0] })
+    }
+
+    // The pc stays on instructions that fail, as well as on the final halt
+    pub fn exec_next(&mut self, mem: &mut Memory, world: &mut impl IO) -> VmResult<ExecResult> {
+        let (pc, instr_data) = (self.pc, mem.read_4(self.pc));
+        let opcode = instr_data[0];
 
+        let (instr, size) = Instruction::decode(instr_data)
+            .ok_or(VmError::UnknownInstruction { pc, opcode })?;
+
+        self.exec(instr, pc + size, mem, world)
+            .map_err(|fault| fault.at(pc, opcode))
+    }
+
+    #[inline(always)]
+    fn exec(&mut self, instr: Instruction, mut next_pc: usize, mem: &mut Memory, world: &mut impl IO) -> Result<ExecResult, Fault> {
         match instr {
             Instruction::Add([lhs, rhs, dest]) => {
-                let result = lhs.get(mem) + rhs.get(mem);
-                *dest.get_mut(mem) = result;
+                let result = lhs.get(mem)?.wrapping_add(rhs.get(mem)?);
+                *dest.get_mut(mem)? = result;
             },
             Instruction::Mul([lhs, rhs, dest]) => {
-                let result = lhs.get(mem) * rhs.get(mem);
-                *dest.get_mut(mem) = result;
+                let result = lhs.get(mem)?.wrapping_mul(rhs.get(mem)?);
+                *dest.get_mut(mem)? = result;
             },
             Instruction::Input([param]) => {
-                *param.get_mut(mem) = world.input();
+                let dest = param.get_mut(mem)?;
+                *dest = world.input().ok_or(Fault::NoInput)?;
             },
             Instruction::Output([param]) => {
-                world.output(param.get(mem));
+                world.output(param.get(mem)?);
             },
             Instruction::JmpTrue([cond, dest]) => {
-                if cond.get(mem) != 0 {
-                    self.pc = dest.get(mem) as usize;
+                if cond.get(mem)? != 0 {
+                    next_pc = Memory::check_address(dest.get(mem)?)?;
                 }
             },
             Instruction::JmpFalse([cond, dest]) => {
-                if cond.get(mem) == 0 {
-                    self.pc = dest.get(mem) as usize;
+                if cond.get(mem)? == 0 {
+                    next_pc = Memory::check_address(dest.get(mem)?)?;
                 }
             },
             Instruction::CmpLt([lhs, rhs, dest]) => {
-                let result = lhs.get(mem) < rhs.get(mem);
-                *dest.get_mut(mem) = Int::from(result);
+                let result = lhs.get(mem)? < rhs.get(mem)?;
+                *dest.get_mut(mem)? = Int::from(result);
             },
             Instruction::CmpEq([lhs, rhs, dest]) => {
-                let result = lhs.get(mem) == rhs.get(mem);
-                *dest.get_mut(mem) = Int::from(result);
+                let result = lhs.get(mem)? == rhs.get(mem)?;
+                *dest.get_mut(mem)? = Int::from(result);
             },
             Instruction::RelBase([param]) => {
-                let delta = param.get(mem);
+                let delta = param.get(mem)?;
                 mem.move_relative_base(delta)
             },
-            Instruction::Halt(_) => return ExecResult::Halt,
+            Instruction::Halt(_) => return Ok(ExecResult::Halt),
         }
 
-        ExecResult::Ok
+        self.pc = next_pc;
+
+        Ok(ExecResult::Ok)
     }
 }
 
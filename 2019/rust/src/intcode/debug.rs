@@ -0,0 +1,464 @@
+use std::borrow::Cow;
+use std::collections::{BTreeSet, VecDeque};
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use super::{Int, cpu::Cpu, error::VmError, io::{Input, Output}, memory::{Memory, MAX_SIZE}, ops::{Instruction, Param}};
+
+// Why a program stopped running under the debugger
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stop {
+    Stepped,
+    Breakpoint(usize),
+    Watchpoint { addr: usize, old: Int, new: Int },
+    NeedsInput,
+    Halted,
+    Error(VmError),
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stop::Stepped => write!(f, "stepped"),
+            Stop::Breakpoint(pc) => write!(f, "breakpoint at {}", pc),
+            Stop::Watchpoint { addr, old, new } => write!(f, "watchpoint on [{}]: {} -> {}", addr, old, new),
+            Stop::NeedsInput => write!(f, "waiting for input"),
+            Stop::Halted => write!(f, "halted"),
+            Stop::Error(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+// Inputs are queued ahead of time, so the program stops instead of blocking when there are none
+#[derive(Default)]
+struct Queues {
+    inputs: VecDeque<Int>,
+    outputs: Vec<Int>,
+}
+
+impl Input for Queues {
+    fn input(&mut self) -> Option<Int> {
+        self.inputs.pop_front()
+    }
+}
+
+impl Output for Queues {
+    fn output(&mut self, value: Int) {
+        self.outputs.push(value)
+    }
+}
+
+// Runs a program one instruction at a time, stopping at breakpoints on the pc
+// and after writes to watched addresses
+pub struct Debugger {
+    memory: Memory,
+    cpu: Cpu,
+    io: Queues,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<usize>,
+}
+
+impl Debugger {
+    pub fn new<'a>(program: impl Into<Cow<'a, [Int]>>) -> Self {
+        Self {
+            memory: Memory::load(program.into()),
+            cpu: Cpu::default(),
+            io: Queues::default(),
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+        }
+    }
+
+    pub fn pc(&self) -> usize {
+        self.cpu.pc()
+    }
+
+    pub fn relative_base(&self) -> Int {
+        self.memory.relative_base()
+    }
+
+    // Memory past the end of the program reads as zeroes
+    pub fn read(&self, addr: usize) -> Int {
+        self.memory.cells.get(addr).copied().unwrap_or(0)
+    }
+
+    pub fn memory(&self, range: Range<usize>) -> Vec<Int> {
+        range.map(|addr| self.read(addr)).collect()
+    }
+
+    // Inspecting memory never grows it, unlike running the program
+    pub fn instruction_at(&self, addr: usize) -> Option<(Instruction, usize)> {
+        let data = [0, 1, 2, 3].map(|idx| self.read(addr.saturating_add(idx)));
+        let (instr, size) = Instruction::try_decode(&data)?;
+
+        // Writes to immediates are not valid either
+        match instr.destination() {
+            Some(Param::Immediate { .. }) => None,
+            _ => Some((instr, size)),
+        }
+    }
+
+    pub fn breakpoints(&self) -> &BTreeSet<usize> {
+        &self.breakpoints
+    }
+
+    pub fn watchpoints(&self) -> &BTreeSet<usize> {
+        &self.watchpoints
+    }
+
+    pub fn add_breakpoint(&mut self, pc: usize) {
+        self.breakpoints.insert(pc);
+    }
+
+    pub fn remove_breakpoint(&mut self, pc: usize) -> bool {
+        self.breakpoints.remove(&pc)
+    }
+
+    pub fn add_watchpoint(&mut self, addr: usize) {
+        self.watchpoints.insert(addr);
+    }
+
+    pub fn remove_watchpoint(&mut self, addr: usize) -> bool {
+        self.watchpoints.remove(&addr)
+    }
+
+    pub fn input(&mut self, values: impl IntoIterator<Item = Int>) {
+        self.io.inputs.extend(values)
+    }
+
+    pub fn pending_inputs(&self) -> usize {
+        self.io.inputs.len()
+    }
+
+    // The values output since the last call
+    pub fn take_outputs(&mut self) -> Vec<Int> {
+        std::mem::take(&mut self.io.outputs)
+    }
+
+    // Executes the next instruction, unless the program halted or waits for an input
+    pub fn step(&mut self) -> Stop {
+        let pc = self.pc();
+        let Some((instr, _)) = self.instruction_at(pc) else {
+            return Stop::Error(VmError::UnknownInstruction { pc, opcode: self.read(pc) })
+        };
+
+        match instr {
+            Instruction::Halt(_) => return Stop::Halted,
+            Instruction::Input(_) if self.io.inputs.is_empty() => return Stop::NeedsInput,
+            _ => { },
+        }
+
+        let watched = instr.destination()
+            .and_then(|dest| self.address(dest))
+            .filter(|addr| self.watchpoints.contains(addr))
+            .map(|addr| (addr, self.read(addr)));
+
+        if let Err(e) = self.cpu.exec_next(&mut self.memory, &mut self.io) {
+            return Stop::Error(e)
+        }
+
+        match watched {
+            Some((addr, old)) => Stop::Watchpoint { addr, old, new: self.read(addr) },
+            None => Stop::Stepped,
+        }
+    }
+
+    // Steps until something else than a plain step stops the program
+    pub fn resume(&mut self) -> Stop {
+        loop {
+            match self.step() {
+                Stop::Stepped if self.breakpoints.contains(&self.pc()) => return Stop::Breakpoint(self.pc()),
+                Stop::Stepped => continue,
+                stop => return stop,
+            }
+        }
+    }
+
+    fn address(&self, param: Param) -> Option<usize> {
+        match param {
+            Param::Position { addr } => usize::try_from(addr.0).ok(),
+            Param::Relative { base_addr } => usize::try_from(base_addr.0.saturating_add(self.relative_base())).ok(),
+            Param::Immediate { .. } => None,
+        }
+    }
+}
+
+const HELP: &str = "\
+Commands:
+    s, step [count]         Executes the next instruction(s)
+    c, continue             Runs until a breakpoint, a watchpoint, an input or the end
+    b, break [pc]           Sets a breakpoint, or lists them
+    w, watch [addr]         Stops after writes to an address, or lists the watched ones
+    d, delete <pc|addr>     Removes a breakpoint or a watchpoint
+    i, input <values...>    Queues input values
+    a, ascii <text>         Queues a line of text, as ASCII codes
+    r, regs                 Shows the pc, the relative base and the next instruction
+    x <addr> [count]        Shows memory from an address
+    l, list [count]         Lists the instructions from the pc
+    h, help                 Shows this help
+    q, quit                 Leaves the debugger";
+
+// A line based front end for the debugger, reading commands until the end of `input`
+pub fn repl(debugger: &mut Debugger, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    write!(output, "> ")?;
+    output.flush()?;
+
+    for line in input.lines() {
+        let line = line?;
+        let (command, args) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+
+        match execute(debugger, command, args, &mut output) {
+            Ok(Flow::Quit) => return Ok(()),
+            Ok(Flow::Continue) => { },
+            Err(e) => writeln!(output, "{}", e)?,
+        }
+
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+
+    Ok(())
+}
+
+enum Flow {
+    Continue,
+    Quit,
+}
+
+fn execute(debugger: &mut Debugger, command: &str, args: &str, output: &mut impl Write) -> Result<Flow, String> {
+    let numbers = || args.split_whitespace()
+        .map(|arg| arg.parse::<Int>().map_err(|_| format!("Invalid number: {}", arg)))
+        .collect::<Result<Vec<_>, _>>();
+    let address = |arg: Option<&Int>| arg.and_then(|&arg| usize::try_from(arg).ok())
+        .ok_or_else(|| "Expected an address".to_owned());
+
+    let written = match command {
+        "" => Ok(()),
+        "s" | "step" => {
+            let count = numbers()?.first().copied().unwrap_or(1);
+            let stop = (0..count.max(1))
+                .map(|_| debugger.step())
+                .find(|stop| *stop != Stop::Stepped)
+                .unwrap_or(Stop::Stepped);
+            return show_stop(debugger, stop, output).map(|_| Flow::Continue)
+        },
+        "c" | "continue" => {
+            let stop = debugger.resume();
+            return show_stop(debugger, stop, output).map(|_| Flow::Continue)
+        },
+        "b" | "break" => match numbers()?.first() {
+            None => writeln!(output, "Breakpoints: {:?}", debugger.breakpoints()),
+            pc => {
+                debugger.add_breakpoint(address(pc)?);
+                Ok(())
+            },
+        },
+        "w" | "watch" => match numbers()?.first() {
+            None => writeln!(output, "Watchpoints: {:?}", debugger.watchpoints()),
+            addr => {
+                debugger.add_watchpoint(address(addr)?);
+                Ok(())
+            },
+        },
+        "d" | "delete" => {
+            let addr = address(numbers()?.first())?;
+            if !debugger.remove_breakpoint(addr) && !debugger.remove_watchpoint(addr) {
+                return Err(format!("No breakpoint or watchpoint at {}", addr))
+            }
+            Ok(())
+        },
+        "i" | "input" => {
+            debugger.input(numbers()?);
+            Ok(())
+        },
+        "a" | "ascii" => {
+            debugger.input(args.bytes().chain(Some(b'\n')).map(Int::from));
+            Ok(())
+        },
+        "r" | "regs" => return show_registers(debugger, output).map(|_| Flow::Continue),
+        "x" => {
+            let numbers = numbers()?;
+            let start = address(numbers.first())?;
+            let count = address(numbers.get(1)).unwrap_or(8);
+            if start >= MAX_SIZE {
+                return Err(format!("Addresses stop at {}", MAX_SIZE - 1))
+            }
+            let end = start.saturating_add(count).min(MAX_SIZE);
+            writeln!(output, "[{}]: {:?}", start, debugger.memory(start..end))
+        },
+        "l" | "list" => {
+            let count = address(numbers()?.first()).unwrap_or(5);
+            list(debugger, count, output)
+        },
+        "h" | "help" => writeln!(output, "{}", HELP),
+        "q" | "quit" => return Ok(Flow::Quit),
+        unknown => return Err(format!("Unknown command: {} (try help)", unknown)),
+    };
+
+    written.map(|_| Flow::Continue).map_err(|e| e.to_string())
+}
+
+// Lists up to `count` instructions from the pc, within the largest memory a program can have
+fn list(debugger: &Debugger, count: usize, output: &mut impl Write) -> io::Result<()> {
+    let mut addr = debugger.pc();
+
+    for _ in 0..count {
+        if addr >= MAX_SIZE {
+            break
+        }
+
+        match debugger.instruction_at(addr) {
+            Some((instr, size)) => {
+                writeln!(output, "{:>6}   {}", addr, instr)?;
+                addr += size;
+            },
+            None => {
+                writeln!(output, "{:>6}   .data {}", addr, debugger.read(addr))?;
+                addr += 1;
+            },
+        }
+    }
+
+    Ok(())
+}
+
+fn show_stop(debugger: &mut Debugger, stop: Stop, output: &mut impl Write) -> Result<(), String> {
+    let outputs = debugger.take_outputs();
+    let text = outputs.iter()
+        .map(|&value| u8::try_from(value).ok().filter(|c| c.is_ascii_graphic() || b" \n".contains(c)).map(char::from))
+        .collect::<Option<String>>();
+
+    let shown = match text {
+        Some(text) if !text.is_empty() => writeln!(output, "Output: {:?}", text),
+        _ if !outputs.is_empty() => writeln!(output, "Output: {:?}", outputs),
+        _ => Ok(()),
+    };
+
+    shown.and_then(|_| writeln!(output, "Stopped: {}", stop))
+        .map_err(|e| e.to_string())?;
+
+    show_registers(debugger, output)
+}
+
+fn show_registers(debugger: &mut Debugger, output: &mut impl Write) -> Result<(), String> {
+    let pc = debugger.pc();
+    let next = debugger.instruction_at(pc)
+        .map_or_else(|| format!(".data {}", debugger.read(pc)), |(instr, _)| instr.to_string());
+
+    writeln!(
+        output,
+        "pc: {}, rb: {}, inputs: {}, next: {}",
+        pc, debugger.relative_base(), debugger.pending_inputs(), next
+    ).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::asm::assemble;
+    use crate::intcode::memory::AddressRelative;
+
+    // Outputs the running sum of its inputs, until a zero
+    const SUMS: &str = "
+        loop:   in [value]
+                jf [value], done
+                add [sum], [value], [sum]
+                out [sum]
+                jmp loop
+        done:   hlt
+        value:  .data 0
+        sum:    .data 0
+    ";
+
+    #[test]
+    fn stops() {
+        let program = assemble(SUMS).unwrap();
+        let mut debugger = Debugger::new(&program);
+        let (jump_back, sum) = (11, 16);
+
+        assert_eq!(debugger.resume(), Stop::NeedsInput);
+        debugger.input(vec![3, 4, 0]);
+
+        assert_eq!(debugger.step(), Stop::Stepped);
+        assert_eq!((debugger.pc(), debugger.read(15)), (2, 3));
+
+        debugger.add_breakpoint(jump_back);
+        assert_eq!(debugger.resume(), Stop::Breakpoint(jump_back));
+        assert_eq!(debugger.take_outputs(), [3]);
+
+        debugger.add_watchpoint(sum);
+        assert_eq!(debugger.resume(), Stop::Watchpoint { addr: sum, old: 3, new: 7 });
+        assert!(debugger.remove_breakpoint(jump_back));
+        assert_eq!(debugger.resume(), Stop::Halted);
+        assert_eq!(debugger.resume(), Stop::Halted);
+        assert_eq!((debugger.take_outputs(), debugger.pending_inputs()), (vec![7], 0));
+    }
+
+    #[test]
+    fn invalid() {
+        let mut debugger = Debugger::new(vec![1101, 1, 2, 3, 1, 1, 1, 1, 42]);
+
+        assert_eq!(debugger.resume(), Stop::Error(VmError::UnknownInstruction { pc: 8, opcode: 42 }));
+        assert_eq!(debugger.memory(0..4), [1101, 2, 2, 3]);
+        assert_eq!(debugger.read(100), 0);
+
+        let mut debugger = Debugger::new(vec![1101, 1, 2, -5]);
+        let fault = VmError::InvalidAddress { pc: 0, opcode: 1101, addr: -5 };
+        assert_eq!(debugger.resume(), Stop::Error(fault));
+        assert_eq!(debugger.pc(), 0);
+    }
+
+    #[test]
+    fn inspection() {
+        let mut debugger = Debugger::new(vec![109, 5, 99]);
+        debugger.step();
+        let size = debugger.memory.cells.len();
+
+        assert_eq!(debugger.address(Param::Relative { base_addr: AddressRelative(Int::MAX) }), Some(Int::MAX as usize));
+        assert!(debugger.instruction_at(MAX_SIZE - 2).is_none());
+        assert!(debugger.instruction_at(usize::MAX).is_none());
+        assert_eq!(debugger.memory.cells.len(), size);
+
+        let script = format!("x {} 1000000000000\nx {}\nr\n", MAX_SIZE - 2, MAX_SIZE);
+        let mut output = Vec::new();
+        repl(&mut debugger, script.as_bytes(), &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!(
+                "> [{}]: [0, 0]\n> Addresses stop at {}\n> pc: 2, rb: 5, inputs: 0, next: hlt\n> ",
+                MAX_SIZE - 2, MAX_SIZE - 1
+            )
+        );
+        assert_eq!(debugger.memory.cells.len(), size);
+    }
+
+    #[test]
+    fn commands() {
+        let program = assemble(SUMS).unwrap();
+        let mut debugger = Debugger::new(&program);
+        let script = "b 11\nc\ni 65 66\nc\nw 16\nc\nx 15 2\nd 16\nd 16\nc\nl 2\nbogus\nq\nr\n";
+
+        let mut output = Vec::new();
+        repl(&mut debugger, script.as_bytes(), &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "\
+> > Stopped: waiting for input
+pc: 0, rb: 0, inputs: 0, next: in [15]
+> > Output: \"A\"
+Stopped: breakpoint at 11
+pc: 11, rb: 0, inputs: 1, next: jt 1, 0
+> > Stopped: watchpoint on [16]: 65 -> 131
+pc: 9, rb: 0, inputs: 0, next: out [16]
+> [15]: [66, 131]
+> > No breakpoint or watchpoint at 16
+> Output: [131]
+Stopped: breakpoint at 11
+pc: 11, rb: 0, inputs: 0, next: jt 1, 0
+>     11   jt 1, 0
+    14   hlt
+> Unknown command: bogus (try help)
+> ");
+    }
+}
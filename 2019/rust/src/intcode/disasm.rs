@@ -0,0 +1,263 @@
+use std::collections::{BTreeSet, HashMap};
+use std::convert::TryFrom;
+use std::fmt;
+use super::{Int, ops::{Instruction, Param}};
+
+// The shortest run of printable characters shown as text rather than numbers
+const MIN_TEXT_LEN: usize = 8;
+const MAX_TEXT_LEN: usize = 32;
+const DATA_PER_LINE: usize = 8;
+
+// A program split into instructions and data, with the targets of its jumps
+pub struct Listing {
+    pub lines: Vec<Line>,
+    pub jump_targets: BTreeSet<usize>,
+}
+
+pub enum Line {
+    Code { addr: usize, instr: Instruction },
+    Data { addr: usize, values: Vec<Int> },
+    Text { addr: usize, text: String },
+}
+
+impl Line {
+    pub fn addr(&self) -> usize {
+        match self {
+            Line::Code { addr, .. } | Line::Data { addr, .. } | Line::Text { addr, .. } => *addr,
+        }
+    }
+}
+
+// Only the instructions reachable from the start are decoded, the rest is
+// likely data. Jumps are followed when their destination is a constant, and
+// constants computed from immediates that point into the program are taken as
+// return addresses pushed before a call, since returns are computed jumps
+pub fn disassemble(program: &[Int]) -> Listing {
+    let mut code = HashMap::new();
+    let mut covered = vec![false; program.len()];
+    let mut jump_targets = BTreeSet::new();
+    let mut entries = vec![0];
+
+    loop {
+        while let Some(entry) = entries.pop() {
+            trace(program, entry, &mut code, &mut covered, &mut jump_targets, &mut entries);
+        }
+
+        let return_addrs: BTreeSet<_> = code.values()
+            .filter_map(|(instr, _)| constant_result(instr))
+            .filter(|&addr| addr < program.len() && !covered[addr])
+            .filter(|&addr| decode_at(program, addr).is_some())
+            .collect();
+
+        if return_addrs.is_empty() {
+            break
+        }
+
+        jump_targets.extend(&return_addrs);
+        entries.extend(return_addrs);
+    }
+
+    let mut lines = Vec::new();
+    let mut addr = 0;
+    while addr < program.len() {
+        match code.remove(&addr) {
+            Some((instr, size)) => {
+                lines.push(Line::Code { addr, instr });
+                addr += size;
+            },
+            None => {
+                let end = (addr..program.len())
+                    .find(|&addr| covered[addr])
+                    .unwrap_or(program.len());
+                lines.extend(data_lines(addr, &program[addr..end]));
+                addr = end;
+            },
+        }
+    }
+
+    Listing { lines, jump_targets }
+}
+
+// Decodes instructions from `addr` until the flow stops or joins known code
+fn trace(
+    program: &[Int],
+    mut addr: usize,
+    code: &mut HashMap<usize, (Instruction, usize)>,
+    covered: &mut [bool],
+    jump_targets: &mut BTreeSet<usize>,
+    entries: &mut Vec<usize>,
+) {
+    while addr < program.len() && !covered[addr] {
+        let Some((instr, size)) = decode_at(program, addr) else { break };
+        if covered[addr..addr + size].iter().any(|&covered| covered) {
+            break
+        }
+        covered[addr..addr + size].iter_mut().for_each(|covered| *covered = true);
+
+        let next = match &instr {
+            Instruction::Halt(_) => None,
+            Instruction::JmpTrue([cond, dest]) | Instruction::JmpFalse([cond, dest]) => {
+                let jumps_if_true = matches!(instr, Instruction::JmpTrue(_));
+                let taken = match cond {
+                    Param::Immediate { value } => Some((*value != 0) == jumps_if_true),
+                    _ => None,
+                };
+
+                if let (Param::Immediate { value }, false) = (dest, taken == Some(false)) {
+                    if let Ok(target) = usize::try_from(*value) {
+                        jump_targets.insert(target);
+                        entries.push(target);
+                    }
+                }
+
+                // An always taken jump does not fall through
+                if taken == Some(true) { None } else { Some(addr + size) }
+            },
+            _ => Some(addr + size),
+        };
+
+        code.insert(addr, (instr, size));
+
+        match next {
+            Some(next) => addr = next,
+            None => break,
+        }
+    }
+}
+
+fn decode_at(program: &[Int], addr: usize) -> Option<(Instruction, usize)> {
+    let mut data = [0; 4];
+    program.get(addr..)?.iter()
+        .zip(&mut data)
+        .for_each(|(&value, slot)| *slot = value);
+
+    let (instr, size) = Instruction::try_decode(&data)?;
+    let writes_immediate = matches!(instr.destination(), Some(Param::Immediate { .. }));
+
+    (addr + size <= program.len() && !writes_immediate).then_some((instr, size))
+}
+
+fn constant_result(instr: &Instruction) -> Option<usize> {
+    let result = match instr {
+        Instruction::Add([Param::Immediate { value: lhs }, Param::Immediate { value: rhs }, _]) => lhs + rhs,
+        Instruction::Mul([Param::Immediate { value: lhs }, Param::Immediate { value: rhs }, _]) => lhs * rhs,
+        _ => return None,
+    };
+
+    usize::try_from(result).ok()
+}
+
+// Splits data into runs of printable text and lines of numbers
+fn data_lines(start: usize, values: &[Int]) -> Vec<Line> {
+    let printable = |value: &Int| *value == Int::from(b'\n') || (0x20..0x7f).contains(value);
+    let text_len = |idx: usize| values[idx..].iter()
+        .take(MAX_TEXT_LEN)
+        .take_while(|value| printable(value))
+        .count();
+
+    let mut lines = Vec::new();
+    let mut idx = 0;
+    while idx < values.len() {
+        let len = text_len(idx);
+
+        if len >= MIN_TEXT_LEN {
+            let text = values[idx..idx + len].iter().map(|&value| value as u8 as char).collect();
+            lines.push(Line::Text { addr: start + idx, text });
+            idx += len;
+        } else {
+            // Numbers up to where some text starts
+            let end = (idx + 1..values.len().min(idx + DATA_PER_LINE))
+                .find(|&idx| text_len(idx) >= MIN_TEXT_LEN)
+                .unwrap_or_else(|| values.len().min(idx + DATA_PER_LINE));
+            lines.push(Line::Data { addr: start + idx, values: values[idx..end].to_vec() });
+            idx = end;
+        }
+    }
+
+    lines
+}
+
+// One line per instruction or data chunk, prefixed by its address, with `>`
+// marking the targets of jumps
+impl fmt::Display for Listing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            let marker = if self.jump_targets.contains(&line.addr()) { '>' } else { ' ' };
+            write!(f, "{:>6} {} ", line.addr(), marker)?;
+
+            match line {
+                Line::Code { instr, .. } => writeln!(f, "{}", instr)?,
+                Line::Text { text, .. } => writeln!(f, ".text {:?}", text)?,
+                Line::Data { values, .. } => {
+                    let values: Vec<_> = values.iter().map(Int::to_string).collect();
+                    writeln!(f, ".data {}", values.join(", "))?
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_and_data() {
+        // Prints a character of the text, in a loop until a flag is set
+        let program = [
+            // 0: out [17]
+            4, 17,
+            // 2: add [1], 1, [1]
+            1001, 1, 1, 1,
+            // 6: eq [1], 25, [16]
+            1008, 1, 25, 16,
+            // 10: jf [16], 0
+            1006, 16, 0,
+            // 13: hlt
+            99,
+            // 14: numbers, with the flag at 16
+            1, 16, 0,
+            // 17: the text
+            87, 97, 108, 107, 105, 110, 103, 10, 7,
+        ];
+        let listing = disassemble(&program);
+
+        assert_eq!(listing.jump_targets, [0].into());
+        assert_eq!(
+            listing.to_string(),
+            "     0 > out [17]\n\
+             \x20    2   add [1], 1, [1]\n\
+             \x20    6   eq [1], 25, [16]\n\
+             \x20   10   jf [16], 0\n\
+             \x20   13   hlt\n\
+             \x20   14   .data 1, 16, 0\n\
+             \x20   17   .text \"Walking\\n\"\n\
+             \x20   25   .data 7\n"
+        );
+    }
+
+    #[test]
+    fn calls() {
+        let program = [
+            // 0: rb 100
+            109, 100,
+            // 2: add 9, 0, [rb+0], pushing the return address
+            21101, 9, 0, 0,
+            // 6: jt 1, 12, calling the function
+            1105, 1, 12,
+            // 9: out 7, after the call returns
+            104, 7,
+            // 11: hlt
+            99,
+            // 12: jf 0, [rb+0], returning
+            2106, 0, 0,
+        ];
+        let listing = disassemble(&program);
+
+        assert_eq!(listing.jump_targets, [9, 12].into());
+        assert!(listing.lines.iter().all(|line| matches!(line, Line::Code { .. })));
+        assert_eq!(listing.lines.iter().map(Line::addr).collect::<Vec<_>>(), [0, 2, 6, 9, 11, 12]);
+    }
+}
@@ -0,0 +1,49 @@
+use std::error::Error;
+use std::fmt;
+use super::Int;
+
+// Why a program stopped before halting, with the instruction at fault
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VmError {
+    UnknownInstruction { pc: usize, opcode: Int },
+    ImmediateDestination { pc: usize, opcode: Int },
+    InvalidAddress { pc: usize, opcode: Int, addr: Int },
+    NoInput { pc: usize, opcode: Int },
+}
+
+pub type VmResult<T> = Result<T, VmError>;
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::UnknownInstruction { pc, opcode } =>
+                write!(f, "Unknown instruction {} at {}", opcode, pc),
+            VmError::ImmediateDestination { pc, opcode } =>
+                write!(f, "Instruction {} at {} writes to an immediate", opcode, pc),
+            VmError::InvalidAddress { pc, opcode, addr } =>
+                write!(f, "Instruction {} at {} accesses the invalid address {}", opcode, pc, addr),
+            VmError::NoInput { pc, opcode } =>
+                write!(f, "Instruction {} at {} reads an input, but none is left", opcode, pc),
+        }
+    }
+}
+
+impl Error for VmError { }
+
+// What went wrong while executing, before knowing which instruction did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    ImmediateDestination,
+    InvalidAddress(Int),
+    NoInput,
+}
+
+impl Fault {
+    pub fn at(self, pc: usize, opcode: Int) -> VmError {
+        match self {
+            Fault::ImmediateDestination => VmError::ImmediateDestination { pc, opcode },
+            Fault::InvalidAddress(addr) => VmError::InvalidAddress { pc, opcode, addr },
+            Fault::NoInput => VmError::NoInput { pc, opcode },
+        }
+    }
+}
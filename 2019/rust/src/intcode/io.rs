@@ -1,16 +1,17 @@
 use super::Int;
 
+// Inputs give `None` when there are no values left, which stops the program with an error
 pub trait IO {
-    fn input(&mut self) -> Int;
+    fn input(&mut self) -> Option<Int>;
     fn output(&mut self, value: Int);
 }
 
 pub trait Input {
-    fn input(&mut self) -> Int;
+    fn input(&mut self) -> Option<Int>;
 }
 
 impl<T: Input> Input for &mut T {
-    fn input(&mut self) -> Int { Input::input(*self) }
+    fn input(&mut self) -> Option<Int> { Input::input(*self) }
 }
 
 pub trait Output {
@@ -22,7 +23,7 @@ impl<T: Output> Output for &mut T {
 }
 
 impl<T: Input + Output> IO for T {
-    fn input(&mut self) -> Int { Input::input(self) }
+    fn input(&mut self) -> Option<Int> { Input::input(self) }
     fn output(&mut self, value: Int) { Output::output(self, value) }
 }
 
@@ -32,7 +33,7 @@ pub mod ext {
     pub struct Split<I, O>(pub I, pub O);
 
     impl<I: Input, O: Output> IO for Split<I, O> {
-        fn input(&mut self) -> Int {
+        fn input(&mut self) -> Option<Int> {
             self.0.input()
         }
         fn output(&mut self, value: Int) {
@@ -42,7 +43,7 @@ pub mod ext {
     pub struct Pure;
 
     impl Input for Pure {
-        fn input(&mut self) -> Int { panic!("No value available") }
+        fn input(&mut self) -> Option<Int> { None }
     }
 
     impl Output for Pure {
@@ -52,7 +53,7 @@ pub mod ext {
     pub struct Iter<I>(pub I);
 
     impl<I: Iterator<Item = Int>> Input for Iter<I> {
-        fn input(&mut self) -> Int { self.0.next().expect("No more values available") }
+        fn input(&mut self) -> Option<Int> { self.0.next() }
     }
 
     use super::*;
@@ -81,18 +82,20 @@ pub mod ext {
         fn output(&mut self, value: Int) { self.0 = Some(value) }
     }
 
-    use std::sync::mpsc;
+    use std::collections::VecDeque;
 
-    impl Input for mpsc::Receiver<Int> {
-        fn input(&mut self) -> Int {
-            self.recv().expect("Failed to recv value")
-        }
+    // Inputs given ahead of time, and the last output not collected yet
+    #[derive(Default, Debug)]
+    pub struct Queue {
+        pub inputs: VecDeque<Int>,
+        pub output: Option<Int>,
     }
 
+    impl Input for Queue {
+        fn input(&mut self) -> Option<Int> { self.inputs.pop_front() }
+    }
 
-    impl Output for mpsc::Sender<Int> {
-        fn output(&mut self, value: Int) {
-            self.send(value).expect("Failed to send value")
-        }
+    impl Output for Queue {
+        fn output(&mut self, value: Int) { self.output = Some(value) }
     }
 }
@@ -1,5 +1,9 @@
-use super::Int;
+use super::{Int, error::Fault};
 use std::borrow::Cow;
+use std::convert::{TryFrom, TryInto};
+
+// Programs only use a few thousand cells, anything far past that is a bug rather than a need
+pub const MAX_SIZE: usize = 1 << 24;
 
 pub struct Memory {
     pub cells: Vec<Int>,
@@ -12,7 +16,7 @@ pub struct AddressRelative(pub Int);
 pub struct AddressAbsolute(pub Int);
 
 pub trait Address<Idx> {
-    fn get(&mut self, idx: Idx) -> &mut Int;
+    fn get(&mut self, idx: Idx) -> Result<&mut Int, Fault>;
 }
 
 impl Memory {
@@ -23,19 +27,27 @@ impl Memory {
         }
     }
 
+    // The pc is kept within `MAX_SIZE` by the jumps, so that this can grow the memory as needed
     pub fn read_4(&mut self, offset: usize) -> &[Int; 4] {
         if offset + 3 >= self.cells.len() {
             self.grow(offset + 4)
         }
 
-        unsafe {
-            let slice = self.cells.get_unchecked(offset..offset + 4);
-            &*(slice.as_ptr() as *const _)
-        }
+        self.cells[offset..offset + 4].try_into().expect("Slice of 4 cells")
+    }
+
+    pub fn check_address(addr: Int) -> Result<usize, Fault> {
+        usize::try_from(addr).ok()
+            .filter(|&offset| offset < MAX_SIZE)
+            .ok_or(Fault::InvalidAddress(addr))
+    }
+
+    pub fn relative_base(&self) -> Int {
+        self.rel_base
     }
 
     pub fn move_relative_base(&mut self, delta: Int) {
-        self.rel_base += delta;
+        self.rel_base = self.rel_base.saturating_add(delta);
     }
 
     fn grow(&mut self, min_size: usize) {
@@ -44,20 +56,20 @@ impl Memory {
 }
 
 impl Address<AddressAbsolute> for Memory {
-    fn get(&mut self, offset: AddressAbsolute) -> &mut Int {
-        let offset = offset.0 as usize;
+    fn get(&mut self, addr: AddressAbsolute) -> Result<&mut Int, Fault> {
+        let offset = Self::check_address(addr.0)?;
 
         if offset >= self.cells.len() {
             self.grow(offset);
         }
 
-        unsafe { self.cells.get_unchecked_mut(offset) }
+        Ok(&mut self.cells[offset])
     }
 }
 
 impl Address<AddressRelative> for Memory {
-    fn get(&mut self, rel_offset: AddressRelative) -> &mut Int {
-        let abs_offset = AddressAbsolute(rel_offset.0 + self.rel_base);
+    fn get(&mut self, rel_offset: AddressRelative) -> Result<&mut Int, Fault> {
+        let abs_offset = AddressAbsolute(rel_offset.0.saturating_add(self.rel_base));
 
         self.get(abs_offset)
     }
@@ -1,7 +1,21 @@
 pub type Int = i64;
 
 mod cpu;
+pub mod asm;
+pub mod debug;
+pub mod disasm;
+pub mod error;
 pub mod io;
 mod memory;
-mod ops;
+pub mod ops;
 pub mod vm;
+
+use aoc_common::{ParseResult, parse};
+
+// The comma separated integers of a program, as found in the inputs
+pub fn parse_program(input: &str) -> ParseResult<Vec<Int>> {
+    input.trim_end()
+        .split(',')
+        .map(|raw_number| parse::field(input, raw_number, "an integer code"))
+        .collect()
+}
@@ -1,21 +1,21 @@
 use super::Int;
-use super::memory::{Memory, Address, AddressAbsolute, AddressRelative};
+use super::{error::Fault, memory::{Memory, Address, AddressAbsolute, AddressRelative}};
 use num::Integer;
-use typenum::{Unsigned, U0, U1, U2, U3};
-use arraytools::ArrayTools;
+use std::convert::TryFrom;
+use std::fmt;
 
 #[derive(Debug)]
 pub enum Instruction {
-    Add(Params<U3>),
-    Mul(Params<U3>),
-    Input(Params<U1>),
-    Output(Params<U1>),
-    JmpTrue(Params<U2>),
-    JmpFalse(Params<U2>),
-    CmpLt(Params<U3>),
-    CmpEq(Params<U3>),
-    RelBase(Params<U1>),
-    Halt(Params<U0>),
+    Add([Param; 3]),
+    Mul([Param; 3]),
+    Input([Param; 1]),
+    Output([Param; 1]),
+    JmpTrue([Param; 2]),
+    JmpFalse([Param; 2]),
+    CmpLt([Param; 3]),
+    CmpEq([Param; 3]),
+    RelBase([Param; 1]),
+    Halt([Param; 0]),
 }
 
 use Instruction::*;
@@ -23,29 +23,35 @@ use Instruction::*;
 const MAX_OP_SIZE: usize = 4;
 
 impl Instruction {
-    pub fn decode(&[op_descriptor, params @ ..]: &[Int; MAX_OP_SIZE]) -> (Self, usize) {
-        let (mut param_codes, op_code) = (op_descriptor as u16).div_rem(&100);
+    // Unknown opcodes and parameter modes give `None`
+    #[inline(always)]
+    pub fn decode(&[op_descriptor, params @ ..]: &[Int; MAX_OP_SIZE]) -> Option<(Self, usize)> {
+        let (mut param_codes, op_code) = u16::try_from(op_descriptor).ok()?.div_rem(&100);
+        let mut valid_modes = true;
 
-        let next_param = |idx| {
+        let mut next_param = |idx| {
             let (next_param_codes, param_code) = param_codes.div_rem(&10);
             param_codes = next_param_codes;
-            Param::from_code_and_value(param_code, params[idx])
+            Param::from_code_and_value(param_code, params[idx]).unwrap_or_else(|| {
+                valid_modes = false;
+                Param::Immediate { value: params[idx] }
+            })
         };
 
         macro_rules! decode_ops {
             ($($code:literal => $instr:ident),*) => {
                 match op_code {
                     $($code => {
-                        const PARAM_COUNT: usize = instruction_param_count(&$instr);
-                        let params = <[_; PARAM_COUNT]>::indices().map(next_param);
-                        ($instr(params), PARAM_COUNT + 1)
+                        // The parameter count is inferred from the instruction's variant
+                        let params = std::array::from_fn(&mut next_param);
+                        ($instr(params), params.len() + 1)
                     }),*,
-                    unknown => unimplemented!("Unknown op code: {}", unknown),
+                    _unknown => return None,
                 }
             }
         }
 
-        decode_ops!(
+        let decoded = decode_ops!(
             1  => Add,
             2  => Mul,
             3  => Input,
@@ -56,7 +62,80 @@ impl Instruction {
             8  => CmpEq,
             9  => RelBase,
             99 => Halt
-        )
+        );
+
+        valid_modes.then_some(decoded)
+    }
+
+    // Stricter than `decode`: integers that do not exactly describe an
+    // instruction, like data with modes left over, give `None` as well.
+    // Kept apart from `decode`, which runs programs as fast as it can
+    pub fn try_decode(data: &[Int; MAX_OP_SIZE]) -> Option<(Self, usize)> {
+        let (param_codes, op_code) = u16::try_from(data[0]).ok()?.div_rem(&100);
+        let param_count = match op_code {
+            1 | 2 | 7 | 8 => 3,
+            5 | 6 => 2,
+            3 | 4 | 9 => 1,
+            99 => 0,
+            _unknown => return None,
+        };
+
+        // Every parameter needs a known mode, and there are no modes left over
+        let unused_codes = (0..param_count).try_fold(param_codes, |param_codes, _| {
+            let (next_param_codes, param_code) = param_codes.div_rem(&10);
+            (param_code <= 2).then_some(next_param_codes)
+        });
+
+        if unused_codes != Some(0) {
+            return None
+        }
+
+        Self::decode(data)
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Add(_) => "add",
+            Mul(_) => "mul",
+            Input(_) => "in",
+            Output(_) => "out",
+            JmpTrue(_) => "jt",
+            JmpFalse(_) => "jf",
+            CmpLt(_) => "lt",
+            CmpEq(_) => "eq",
+            RelBase(_) => "rb",
+            Halt(_) => "hlt",
+        }
+    }
+
+    pub fn params(&self) -> &[Param] {
+        match self {
+            Add(params) | Mul(params) | CmpLt(params) | CmpEq(params) => params,
+            JmpTrue(params) | JmpFalse(params) => params,
+            Input(params) | Output(params) | RelBase(params) => params,
+            Halt(params) => params,
+        }
+    }
+
+    // The parameter the instruction writes to, if any
+    pub fn destination(&self) -> Option<Param> {
+        match self {
+            Add([_, _, dest]) | Mul([_, _, dest]) | CmpLt([_, _, dest]) | CmpEq([_, _, dest]) => Some(*dest),
+            Input([dest]) => Some(*dest),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mnemonic())?;
+
+        for (idx, param) in self.params().iter().enumerate() {
+            write!(f, "{}{}", if idx == 0 { " " } else { ", " }, param)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -68,37 +147,42 @@ pub enum Param {
 }
 
 impl Param {
-    fn from_code_and_value(code: u16, value: Int) -> Self {
+    fn from_code_and_value(code: u16, value: Int) -> Option<Self> {
         match code {
-            0 => Param::Position { addr: AddressAbsolute(value) },
-            1 => Param::Immediate { value },
-            2 => Param::Relative { base_addr: AddressRelative(value) },
-            unknown => unreachable!("Unknown param code: {}", unknown)
+            0 => Some(Param::Position { addr: AddressAbsolute(value) }),
+            1 => Some(Param::Immediate { value }),
+            2 => Some(Param::Relative { base_addr: AddressRelative(value) }),
+            _unknown => None,
         }
     }
 
-    pub fn get(self, mem: &mut Memory) -> Int {
+    pub fn get(self, mem: &mut Memory) -> Result<Int, Fault> {
         match self {
-            Param::Position { addr } => *mem.get(addr),
-            Param::Immediate { value } => value,
-            Param::Relative { base_addr } => *mem.get(base_addr),
+            Param::Position { addr } => mem.get(addr).copied(),
+            Param::Immediate { value } => Ok(value),
+            Param::Relative { base_addr } => mem.get(base_addr).copied(),
         }
     }
 
-    pub fn get_mut(self, mem: &mut Memory) -> &mut Int {
+    pub fn get_mut(self, mem: &mut Memory) -> Result<&mut Int, Fault> {
         match self {
             Param::Position { addr } => mem.get(addr),
-            Param::Immediate { .. } => unreachable!("Immediate as destination"),
+            Param::Immediate { .. } => Err(Fault::ImmediateDestination),
             Param::Relative { base_addr } => mem.get(base_addr),
         }
     }
 }
 
-#[allow(type_alias_bounds)]
-type Params<N: Unsigned> = [Param; N::USIZE];
-
-const fn instruction_param_count<T>(_: &impl Fn(T) -> Instruction) -> usize {
-    std::mem::size_of::<T>() / std::mem::size_of::<Param>()
+// Position parameters read as `[addr]` and relative ones as `[rb+offset]`
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Param::Position { addr } => write!(f, "[{}]", addr.0),
+            Param::Immediate { value } => write!(f, "{}", value),
+            Param::Relative { base_addr } if base_addr.0 < 0 => write!(f, "[rb-{}]", -base_addr.0),
+            Param::Relative { base_addr } => write!(f, "[rb+{}]", base_addr.0),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -109,7 +193,7 @@ mod tests {
     #[test]
     fn ops() {
         assert_matches!(
-            Instruction::decode(&[1, 2, 3, 4]).0,
+            Instruction::decode(&[1, 2, 3, 4]).unwrap().0,
             Instruction::Add([
                 Param::Position { addr: AddressAbsolute(2) },
                 Param::Position { addr: AddressAbsolute(3) },
@@ -117,4 +201,30 @@ mod tests {
             ])
         )
     }
+
+    #[test]
+    fn invalid() {
+        assert_matches!(Instruction::try_decode(&[1001, 2, 3, 4]), Some((Instruction::Add(_), 4)));
+        assert_matches!(Instruction::try_decode(&[42, 0, 0, 0]), None);
+        assert_matches!(Instruction::try_decode(&[301, 0, 0, 0]), None);
+        assert_matches!(Instruction::try_decode(&[10004, 0, 0, 0]), None);
+        assert_matches!(Instruction::try_decode(&[-1, 0, 0, 0]), None);
+
+        // Running programs only need known opcodes and modes
+        assert_matches!(Instruction::decode(&[42, 0, 0, 0]), None);
+        assert_matches!(Instruction::decode(&[301, 0, 0, 0]), None);
+        assert_matches!(Instruction::decode(&[10004, 0, 0, 0]), Some((Instruction::Output(_), 2)));
+        // Would be an `add` if truncated to 16 bits
+        assert_matches!(Instruction::decode(&[65537, 0, 0, 0]), None);
+        assert_matches!(Instruction::decode(&[-1, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn display() {
+        let (instr, _) = Instruction::decode(&[21207, -3, 5, 1]).unwrap();
+        assert_eq!(instr.to_string(), "lt [rb-3], 5, [rb+1]");
+
+        let (instr, _) = Instruction::decode(&[99, 0, 0, 0]).unwrap();
+        assert_eq!(instr.to_string(), "hlt");
+    }
 }
@@ -1,11 +1,11 @@
 use std::borrow::Cow;
-use super::{Int, io::{IO, Input, Output, ext::{Pure, Split, Iter, SingleOutput}}, memory::Memory, cpu::{CPU, ExecResult}};
+use super::{Int, error::VmResult, io::{IO, Input, Output, ext::{Pure, Split, Iter, SingleOutput, Queue}}, memory::Memory, cpu::{Cpu, ExecResult}, ops::Instruction};
 use std::iter::{once, Once};
 
 pub struct VirtualMachine<D> {
     driver: D,
     memory: Memory,
-    cpu: CPU,
+    cpu: Cpu,
 }
 
 impl<D: IO> VirtualMachine<D> {
@@ -13,25 +13,57 @@ impl<D: IO> VirtualMachine<D> {
         Self {
             driver,
             memory: Memory::load(program.into()),
-            cpu: CPU::default(),
+            cpu: Cpu::default(),
         }
     }
 
-    pub fn run(mut self) -> EndRunState<D> {
+    pub fn run(mut self) -> VmResult<EndRunState<D>> {
         let driver = &mut self.driver;
         let mem = &mut self.memory;
 
         loop {
-            let exec_result = self.cpu.exec_next(mem, driver);
+            let exec_result = self.cpu.exec_next(mem, driver)?;
 
             if let ExecResult::Halt = exec_result {
                 break
             }
         }
 
-        EndRunState {
+        Ok(EndRunState {
             memory: self.memory.cells,
             driver: self.driver,
+        })
+    }
+}
+
+// Where a paused program gave control back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoEvent {
+    NeedsInput,
+    Output(Int),
+    Halted,
+}
+
+// Programs talking to each other can be run in turns, without blocking on their IO
+impl VirtualMachine<Queue> {
+    pub fn push_input(&mut self, value: Int) {
+        self.driver.inputs.push_back(value)
+    }
+
+    // Runs until the program outputs a value, halts, or wants an input that was not pushed yet
+    pub fn run_until_io(&mut self) -> VmResult<IoEvent> {
+        loop {
+            match self.cpu.peek(&mut self.memory)? {
+                Instruction::Halt(_) => return Ok(IoEvent::Halted),
+                Instruction::Input(_) if self.driver.inputs.is_empty() => return Ok(IoEvent::NeedsInput),
+                _ => { },
+            }
+
+            self.cpu.exec_next(&mut self.memory, &mut self.driver)?;
+
+            if let Some(value) = self.driver.output.take() {
+                return Ok(IoEvent::Output(value))
+            }
         }
     }
 }
@@ -81,6 +113,10 @@ impl<'a> VMBuilderProgram<'a> {
     pub fn input_once(self, value: Int) -> VMBuilderProgramInput<'a, Iter<Once<Int>>> {
         self.input_iter(once(value))
     }
+
+    pub fn pausable(self) -> VirtualMachine<Queue> {
+        VirtualMachine::new(self.0, Queue::default())
+    }
 }
 
 impl<'a, I: Input> VMBuilderProgramInput<'a, I> {
@@ -126,7 +162,7 @@ impl<O: Output> VMBuilder<Split<Pure, O>> for VMBuilderProgramOutput<'_, O> {
 pub trait VMBuilder<D: IO>: Sized {
     fn build(self) -> VirtualMachine<D>;
 
-    fn run(self) -> EndRunState<D> {
+    fn run(self) -> VmResult<EndRunState<D>> {
         self.build()
             .run()
     }
@@ -154,3 +190,57 @@ impl<I: Input, O: Output> EndRunState<Split<I, O>> {
         self.driver.1
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::intcode::{asm::assemble, error::VmError};
+
+    #[test]
+    fn pauses() {
+        // Doubles its inputs until a zero
+        let program = assemble("
+            loop:   in [value]
+                    jf [value], done
+                    mul [value], 2, [value]
+                    out [value]
+                    jmp loop
+            done:   hlt
+            value:  .data 0
+        ").unwrap();
+        let mut vm = VirtualMachine::load(&program).pausable();
+
+        assert_eq!(vm.run_until_io(), Ok(IoEvent::NeedsInput));
+        assert_eq!(vm.run_until_io(), Ok(IoEvent::NeedsInput));
+
+        vm.push_input(3);
+        vm.push_input(5);
+        assert_eq!(vm.run_until_io(), Ok(IoEvent::Output(6)));
+        assert_eq!(vm.run_until_io(), Ok(IoEvent::Output(10)));
+        assert_eq!(vm.run_until_io(), Ok(IoEvent::NeedsInput));
+
+        vm.push_input(0);
+        assert_eq!(vm.run_until_io(), Ok(IoEvent::Halted));
+        assert_eq!(vm.run_until_io(), Ok(IoEvent::Halted));
+    }
+
+    #[test]
+    fn errors() {
+        let run = |program: &[Int]| VirtualMachine::load(program).run().err();
+
+        assert_eq!(run(&[1, 0, 0, 0, 42]), Some(VmError::UnknownInstruction { pc: 4, opcode: 42 }));
+        assert_eq!(run(&[301, 0, 0, 0]), Some(VmError::UnknownInstruction { pc: 0, opcode: 301 }));
+        assert_eq!(run(&[65537, 0, 0, 0, 99]), Some(VmError::UnknownInstruction { pc: 0, opcode: 65537 }));
+        assert_eq!(run(&[1101, 1, 1, 0, 11101, 1, 1, 0]), Some(VmError::ImmediateDestination { pc: 4, opcode: 11101 }));
+        assert_eq!(run(&[1, -1, 0, 0]), Some(VmError::InvalidAddress { pc: 0, opcode: 1, addr: -1 }));
+        assert_eq!(run(&[109, -3, 204, 0]), Some(VmError::InvalidAddress { pc: 2, opcode: 204, addr: -3 }));
+        assert_eq!(run(&[1105, 1, -7]), Some(VmError::InvalidAddress { pc: 0, opcode: 1105, addr: -7 }));
+        assert_eq!(run(&[104, 1 << 40, 99]), None);
+        assert_eq!(run(&[4, 1 << 40]), Some(VmError::InvalidAddress { pc: 0, opcode: 4, addr: 1 << 40 }));
+        assert_eq!(run(&[3, 0, 99]), Some(VmError::NoInput { pc: 0, opcode: 3 }));
+
+        let mut vm = VirtualMachine::load(&[3, 0, 3, 0, 99][..]).input_once(7).build();
+        assert_eq!(vm.cpu.exec_next(&mut vm.memory, &mut vm.driver).ok().map(|_| vm.cpu.pc()), Some(2));
+        assert_eq!(vm.run().err().map(|e| e.to_string()).as_deref(), Some("Instruction 3 at 2 reads an input, but none is left"));
+    }
+}
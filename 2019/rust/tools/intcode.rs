@@ -0,0 +1,56 @@
+use std::fs;
+use std::io;
+use aoc_2019::{days, intcode::{self, Int, asm, debug, disasm}};
+
+const USAGE: &str = "\
+Usage: intcode disasm <program>
+       intcode asm <source>
+       intcode debug <program>
+
+Commands:
+    disasm    Lists the instructions and likely data of the program
+    asm       Assembles a source file into comma separated integers
+    debug     Runs the program step by step, type `help` for the commands
+
+The program is either a file of comma separated integers, or the number of a
+day with an intcode input, e.g. `21`";
+
+fn load_program(source: &str) -> Result<Vec<Int>, String> {
+    let embedded = source.parse::<u32>().ok()
+        .and_then(|day| days().into_iter().find(|d| d.day == day))
+        .map(|day| day.embedded_input.to_owned());
+
+    let input = match embedded {
+        Some(input) => input,
+        None => fs::read_to_string(source).map_err(|e| format!("Failed to read {}: {}", source, e))?,
+    };
+
+    intcode::parse_program(&input).map_err(|e| format!("Invalid program {}: {}", source, e))
+}
+
+fn assemble(path: &str) -> Result<Vec<Int>, String> {
+    let source = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    asm::assemble(&source).map_err(|e| format!("Invalid source {}: {}", path, e))
+}
+
+fn main() {
+    let args: Vec<_> = std::env::args().skip(1).collect();
+
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["disasm", source] => load_program(source)
+            .map(|program| print!("{}", disasm::disassemble(&program))),
+        ["asm", path] => assemble(path)
+            .map(|program| println!("{}", program.iter().map(Int::to_string).collect::<Vec<_>>().join(","))),
+        ["debug", source] => load_program(source).and_then(|program| {
+            let mut debugger = debug::Debugger::new(program);
+            debug::repl(&mut debugger, io::stdin().lock(), io::stdout()).map_err(|e| e.to_string())
+        }),
+        _ => Err(USAGE.to_owned()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
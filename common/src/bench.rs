@@ -0,0 +1,38 @@
+use criterion::{AxisScale, BenchmarkId, Criterion, PlotConfiguration, Throughput, black_box};
+
+use crate::generate::{self, Generate, Rng};
+use crate::solution::Solution;
+
+// Parses the embedded input, then solves each part of it
+pub fn bench_day<S: Solution>(c: &mut Criterion) {
+    let name = format!("day{:02}", S::DAY);
+
+    c.bench_function(&format!("{} parse", name), |b| b.iter(|| S::parse(black_box(S::EMBEDDED_INPUT))));
+
+    let parsed = S::parse(S::EMBEDDED_INPUT).expect("Invalid embedded input");
+    c.bench_function(&format!("{} p1", name), |b| b.iter(|| S::part1(black_box(&parsed))));
+    if S::HAS_PART2 {
+        c.bench_function(&format!("{} p2", name), |b| b.iter(|| S::part2(black_box(&parsed))));
+    }
+}
+
+// Sweeps generated inputs of growing sizes, for criterion to plot how each part scales
+pub fn bench_scaling<S: Generate>(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("day{:02} scaling", S::DAY));
+    group.sample_size(10)
+        .plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+
+    let mut rng = Rng::new(generate::SEED);
+    for &size in S::SIZES {
+        let input = S::generate(&mut rng, size);
+        let parsed = S::parse(&input).expect("Invalid generated input");
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("p1", size), &parsed, |b, parsed| b.iter(|| S::part1(black_box(parsed))));
+        if S::HAS_PART2 {
+            group.bench_with_input(BenchmarkId::new("p2", size), &parsed, |b, parsed| b.iter(|| S::part2(black_box(parsed))));
+        }
+    }
+
+    group.finish();
+}
@@ -0,0 +1,108 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::input;
+use crate::server::Server;
+
+// Inputs downloaded from a puzzle server, kept as `<dir>/YYYY/dayNN.txt`
+// so that each one is only ever fetched once
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    dir: PathBuf,
+    server: Option<Server>,
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), server: None }
+    }
+
+    // e.g. `~/.cache/advent-of-code` on Linux
+    pub fn user_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("advent-of-code"))
+    }
+
+    // Without a server, only the inputs already in the cache are available
+    pub fn with_server(self, server: Server) -> Self {
+        Self { server: Some(server), ..self }
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        self.dir.join(year.to_string()).join(input::file_name(day))
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> io::Result<String> {
+        let path = self.path(year, day);
+
+        match fs::read_to_string(&path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+            cached => return cached,
+        }
+
+        let server = self.server.as_ref().ok_or_else(|| io::Error::new(
+            io::ErrorKind::NotFound,
+            "not in the cache, and no session to download it with",
+        ))?;
+
+        let input = server.input(year, day)?;
+        fs::create_dir_all(path.parent().expect("Cached inputs are in a year directory"))?;
+        fs::write(&path, &input)?;
+
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        dir
+    }
+
+    #[test]
+    fn download_once() {
+        let dir = scratch_dir("download");
+        let (base_url, requests) = server::stand_in(vec![(200, "+1\n-2\n")]);
+        let cache = InputCache::new(&dir).with_server(Server::new(&base_url, "secret"));
+
+        assert_eq!(cache.get(2018, 1).unwrap(), "+1\n-2\n");
+        assert_eq!(requests.join().unwrap().len(), 1);
+
+        // The stand-in is gone: this one has to come from the cache
+        assert_eq!(cache.get(2018, 1).unwrap(), "+1\n-2\n");
+        assert_eq!(fs::read_to_string(dir.join("2018").join("day01.txt")).unwrap(), "+1\n-2\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn offline() {
+        let dir = scratch_dir("offline");
+        let cache = InputCache::new(&dir);
+
+        assert_eq!(cache.get(2015, 25).unwrap_err().kind(), io::ErrorKind::NotFound);
+
+        fs::create_dir_all(dir.join("2015")).unwrap();
+        fs::write(cache.path(2015, 25), "row 1, column 2").unwrap();
+        assert_eq!(cache.get(2015, 25).unwrap(), "row 1, column 2");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_download() {
+        let dir = scratch_dir("failed");
+        let (base_url, requests) = server::stand_in(vec![(404, "Not Found")]);
+        let cache = InputCache::new(&dir).with_server(Server::new(&base_url, "secret"));
+
+        assert!(cache.get(2019, 26).is_err());
+        requests.join().unwrap();
+        assert!(!cache.path(2019, 26).exists());
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use serde::Deserialize;
+
+use crate::report::Report;
+
+// FNV-1a: stable across platforms and toolchains, unlike `DefaultHasher`.
+// Trailing whitespace is ignored so that editors adding a final newline don't matter
+pub fn input_hash(input: &str) -> String {
+    let hash = input.trim_end()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+
+    format!("{:016x}", hash)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { .. } => write!(f, "fail"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+// Rows without a part or an answer are skipped, so that the CSV output of a
+// previous run can be used as is
+#[derive(Debug, Deserialize)]
+struct ExpectedRow {
+    year: u32,
+    day: u32,
+    part: Option<u32>,
+    input_hash: String,
+    answer: Option<String>,
+}
+
+type Key = (u32, u32, u32, String);
+
+#[derive(Debug, Default)]
+pub struct ExpectedAnswers(HashMap<Key, String>);
+
+impl ExpectedAnswers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        Self::from_reader(file).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn from_reader(reader: impl Read) -> Result<Self, csv::Error> {
+        csv::Reader::from_reader(reader)
+            .deserialize()
+            .filter_map(|row: Result<ExpectedRow, _>| match row {
+                Ok(ExpectedRow { year, day, part: Some(part), input_hash, answer: Some(answer) }) =>
+                    Some(Ok(((year, day, part, input_hash), answer))),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect::<Result<_, _>>()
+            .map(ExpectedAnswers)
+    }
+
+    pub fn verdict(&self, year: u32, day: u32, part: u32, input_hash: &str, answer: &str) -> Verdict {
+        match self.0.get(&(year, day, part, input_hash.to_owned())) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail { expected: expected.clone() },
+            None => Verdict::Unknown,
+        }
+    }
+
+    pub fn check(&self, reports: &mut [Report]) {
+        for report in reports {
+            if let Ok(solved) = &mut report.solved {
+                for answer in &mut solved.answers {
+                    let verdict = self.verdict(report.year, report.day, answer.part, &solved.input_hash, &answer.value);
+                    answer.verdict = Some(verdict);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CheckSummary {
+    pub passed: usize,
+    pub failed: usize,
+    pub unknown: usize,
+}
+
+impl CheckSummary {
+    pub fn new(reports: &[Report]) -> Self {
+        reports.iter()
+            .filter_map(|report| report.solved.as_ref().ok())
+            .flat_map(|solved| &solved.answers)
+            .filter_map(|answer| answer.verdict.as_ref())
+            .fold(Self::default(), |mut summary, verdict| {
+                match verdict {
+                    Verdict::Pass => summary.passed += 1,
+                    Verdict::Fail { .. } => summary.failed += 1,
+                    Verdict::Unknown => summary.unknown += 1,
+                }
+                summary
+            })
+    }
+}
+
+impl fmt::Display for CheckSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} passed, {} failed, {} unknown", self.passed, self.failed, self.unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("1723\n"), input_hash("1723"));
+        assert_ne!(input_hash("1723"), input_hash("1724"));
+    }
+
+    #[test]
+    fn verdicts() {
+        let mut expected = ExpectedAnswers::default();
+        expected.0.insert((2018, 11, 1, input_hash("1723")), "34,13".into());
+
+        let hash = input_hash("1723");
+        assert_eq!(expected.verdict(2018, 11, 1, &hash, "34,13"), Verdict::Pass);
+        assert_eq!(expected.verdict(2018, 11, 1, &hash, "34,12"), Verdict::Fail { expected: "34,13".into() });
+        assert_eq!(expected.verdict(2018, 11, 2, &hash, "280,218,11"), Verdict::Unknown);
+        assert_eq!(expected.verdict(2018, 11, 1, &input_hash("42"), "34,13"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn csv_output_as_expected() {
+        let csv = "year,day,part,input_hash,answer,parse_ms,elapsed_ms,success,error,check\n\
+                   2018,11,1,cafe,\"34,13\",1.0,2.0,true,,\n\
+                   2019,22,,,,,,false,missing input,\n";
+        let expected = ExpectedAnswers::from_reader(csv.as_bytes()).unwrap();
+
+        assert_eq!(expected.0.len(), 1);
+        assert_eq!(expected.verdict(2018, 11, 1, "cafe", "34,13"), Verdict::Pass);
+    }
+}
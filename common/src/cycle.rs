@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// The sequence x0, f(x0), f(f(x0)), ... of a function over a finite state space
+// ends up cycling: the state at step `start + len` is the state at step `start`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T> {
+    pub start: usize,
+    pub len: usize,
+    // The state at step `start`
+    pub entry: T,
+}
+
+impl<T> Cycle<T> {
+    // The first step that yields the same state as step `n`
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    // Extrapolates the state at step `n` by only simulating the end of its last cycle
+    pub fn state_at(&self, n: usize, f: impl Fn(T) -> T) -> T where T: Clone {
+        assert!(n >= self.start, "Step {} is before the start of the cycle at {}", n, self.start);
+
+        iterate(self.entry.clone(), &f, (n - self.start) % self.len)
+    }
+}
+
+fn iterate<T>(x0: T, f: impl Fn(T) -> T, steps: usize) -> T {
+    (0..steps).fold(x0, |x, _| f(x))
+}
+
+// Finds where the tortoise and the hare first meet, then needs only O(1) states
+// `eq` lets callers compare only the part of the state that cycles
+pub fn floyd<T, F, Eq>(x0: T, f: F, eq: Eq) -> Cycle<T>
+where
+    T: Clone,
+    F: Fn(T) -> T,
+    Eq: Fn(&T, &T) -> bool,
+{
+    let mut tortoise = f(x0.clone());
+    let mut hare = f(f(x0.clone()));
+
+    while !eq(&tortoise, &hare) {
+        tortoise = f(tortoise);
+        hare = f(f(hare));
+    }
+
+    let mut start = 0;
+    let mut tortoise = x0;
+    while !eq(&tortoise, &hare) {
+        tortoise = f(tortoise);
+        hare = f(hare);
+        start += 1
+    }
+
+    let mut len = 1;
+    let mut hare = f(tortoise.clone());
+    while !eq(&tortoise, &hare) {
+        hare = f(hare);
+        len += 1
+    }
+
+    Cycle { start, len, entry: tortoise }
+}
+
+// Same results as `floyd`, usually with fewer evaluations of `f`
+pub fn brent<T, F, Eq>(x0: T, f: F, eq: Eq) -> Cycle<T>
+where
+    T: Clone,
+    F: Fn(T) -> T,
+    Eq: Fn(&T, &T) -> bool,
+{
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(x0.clone());
+
+    while !eq(&tortoise, &hare) {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = f(hare);
+        len += 1;
+    }
+
+    let mut start = 0;
+    let mut tortoise = x0.clone();
+    let mut hare = iterate(x0, &f, len);
+    while !eq(&tortoise, &hare) {
+        tortoise = f(tortoise);
+        hare = f(hare);
+        start += 1
+    }
+
+    Cycle { start, len, entry: tortoise }
+}
+
+// Remembers the key of every state, which finds the cycle as soon as it completes
+// once but needs as much memory as there are steps before that
+pub fn by_key<T, K, F, Key>(x0: T, f: F, key: Key) -> Cycle<T>
+where
+    T: Clone,
+    K: Hash + std::cmp::Eq,
+    F: Fn(T) -> T,
+    Key: Fn(&T) -> K,
+{
+    let mut seen = HashMap::new();
+    let mut state = x0.clone();
+
+    for step in 0.. {
+        if let Some(start) = seen.insert(key(&state), step) {
+            return Cycle { start, len: step - start, entry: iterate(x0, &f, start) }
+        }
+        state = f(state);
+    }
+
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Starting from 3: 3, 10, 101, 2, 5, 26, 167, 95, 101, ...
+    fn next(x: u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    #[test]
+    fn detectors_agree() {
+        let expected = Cycle { start: 2, len: 6, entry: 101 };
+
+        assert_eq!(floyd(3, next, PartialEq::eq), expected);
+        assert_eq!(brent(3, next, PartialEq::eq), expected);
+        assert_eq!(by_key(3, next, |&x| x), expected);
+    }
+
+    #[test]
+    fn extrapolation() {
+        let cycle = brent(3, next, PartialEq::eq);
+
+        for n in cycle.start..100 {
+            let simulated = iterate(3, next, n);
+            assert_eq!(cycle.state_at(n, next), simulated);
+            assert_eq!(iterate(3, next, cycle.equivalent_step(n)), simulated);
+        }
+        assert_eq!(cycle.equivalent_step(1), 1);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::solution::Solution;
+
+// A solution able to make up valid inputs of any size, to see how it scales
+// past the single committed input
+pub trait Generate: Solution {
+    // The sizes worth sweeping, from around the real input's to what a benchmark can still afford
+    const SIZES: &'static [usize];
+
+    // An input of `size` units of the day's format (units, marbles, digits, ...)
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+// The seed of the benchmarks, so that their runs can be compared with each other
+pub const SEED: u64 = 20181225;
+
+// A small seeded generator (SplitMix64): the same seed always yields the same inputs
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // A number in `low..=high`
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "Empty range {}..={}", low, high);
+
+        match (high - low).checked_add(1) {
+            Some(len) => low + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.next_u64() % denominator < numerator
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.between(0, items.len() as u64 - 1) as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(SEED), numbers(SEED));
+        assert_ne!(numbers(SEED), numbers(SEED + 1));
+    }
+
+    #[test]
+    fn bounds() {
+        let mut rng = Rng::new(SEED);
+
+        let dice: Vec<_> = (0..1000).map(|_| rng.between(1, 6)).collect();
+        assert!(dice.iter().all(|die| (1..=6).contains(die)));
+        assert!((1..=6).all(|face| dice.contains(&face)));
+
+        assert_eq!(rng.between(7, 7), 7);
+        rng.between(0, u64::MAX);
+
+        assert!(!rng.chance(0, 3));
+        assert!(rng.chance(3, 3));
+        assert_eq!(rng.pick(&['x']), &'x');
+    }
+}
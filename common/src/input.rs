@@ -0,0 +1,4 @@
+// Inputs are named the same way for every year, in the repository and in the cache
+pub fn file_name(day: u32) -> String {
+    format!("day{:02}.txt", day)
+}
@@ -0,0 +1,27 @@
+// Criterion benchmarks shared by the years, which enable it as a dev-dependency
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod cache;
+pub mod check;
+pub mod cycle;
+pub mod generate;
+pub mod input;
+pub mod output;
+pub mod parse;
+pub mod report;
+pub mod search;
+pub mod server;
+pub mod simd;
+pub mod solution;
+pub mod submit;
+pub mod timing;
+
+pub use cache::InputCache;
+pub use check::{CheckSummary, ExpectedAnswers, Verdict};
+pub use generate::Generate;
+pub use parse::{ParseError, ParseResult};
+pub use report::Report;
+pub use server::Server;
+pub use solution::{Solution, Day, Solved, Answer};
+pub use submit::Submitter;
+pub use timing::TimingTable;
@@ -0,0 +1,150 @@
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+use serde::Serialize;
+
+use crate::report::Report;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            unknown => Err(format!("unknown output format {:?} (expected text, json or csv)", unknown)),
+        }
+    }
+}
+
+// A day that could not run at all yields a single entry without a part
+#[derive(Debug, Serialize)]
+pub struct Entry<'a> {
+    pub year: u32,
+    pub day: u32,
+    pub part: Option<u32>,
+    pub input_hash: Option<&'a str>,
+    pub answer: Option<&'a str>,
+    pub parse_ms: Option<f64>,
+    pub elapsed_ms: Option<f64>,
+    pub success: bool,
+    pub error: Option<&'a str>,
+    pub check: Option<String>,
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.
+}
+
+pub fn entries(reports: &[Report]) -> Vec<Entry<'_>> {
+    reports.iter()
+        .flat_map(|report| {
+            let (year, day) = (report.year, report.day);
+
+            match &report.solved {
+                Ok(solved) => solved.answers.iter()
+                    .map(|answer| Entry {
+                        year,
+                        day,
+                        part: Some(answer.part),
+                        input_hash: Some(&solved.input_hash),
+                        answer: Some(&answer.value),
+                        parse_ms: Some(millis(solved.parse_elapsed)),
+                        elapsed_ms: Some(millis(answer.elapsed)),
+                        success: true,
+                        error: None,
+                        check: answer.verdict.as_ref().map(ToString::to_string),
+                    })
+                    .collect(),
+                Err(e) => vec![Entry {
+                    year,
+                    day,
+                    part: None,
+                    input_hash: None,
+                    answer: None,
+                    parse_ms: None,
+                    elapsed_ms: None,
+                    success: false,
+                    error: Some(e),
+                    check: None,
+                }],
+            }
+        })
+        .collect()
+}
+
+pub fn write(format: Format, reports: &[Report], mut out: impl Write) -> io::Result<()> {
+    match format {
+        Format::Text => reports.iter().try_for_each(|report| write!(out, "{}", report)),
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut out, &entries(reports))?;
+            writeln!(out)
+        },
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            entries(reports).iter().try_for_each(|entry| writer.serialize(entry))?;
+            writer.flush()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::Verdict;
+    use crate::solution::{Answer, Solved};
+
+    fn reports() -> Vec<Report> {
+        vec![
+            Report {
+                year: 2018,
+                day: 11,
+                solved: Ok(Solved {
+                    input_hash: "cafe".into(),
+                    parse_elapsed: Duration::from_millis(1),
+                    answers: vec![Answer {
+                        part: 1,
+                        value: "34,13".into(),
+                        elapsed: Duration::from_millis(2),
+                        verdict: Some(Verdict::Pass),
+                    }],
+                }),
+            },
+            Report { year: 2019, day: 22, solved: Err("missing input".into()) },
+        ]
+    }
+
+    #[test]
+    fn csv() {
+        let mut out = Vec::new();
+        write(Format::Csv, &reports(), &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "year,day,part,input_hash,answer,parse_ms,elapsed_ms,success,error,check\n\
+             2018,11,1,cafe,\"34,13\",1.0,2.0,true,,pass\n\
+             2019,22,,,,,,false,missing input,\n"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let mut out = Vec::new();
+        write(Format::Json, &reports(), &mut out).unwrap();
+
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed[0]["answer"], "34,13");
+        assert_eq!(parsed[0]["success"], true);
+        assert_eq!(parsed[0]["check"], "pass");
+        assert_eq!(parsed[1]["part"], serde_json::Value::Null);
+        assert_eq!(parsed[1]["error"], "missing input");
+    }
+}
@@ -0,0 +1,104 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+impl ParseError {
+    // Lines and columns start at 1, columns are counted in bytes
+    pub fn new<B: AsRef<[u8]> + ?Sized>(input: &B, offset: usize, expected: impl Into<String>) -> Self {
+        let before = &input.as_ref()[..offset];
+        let line_start = before.iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |newline| newline + 1);
+
+        Self {
+            line: before.iter().filter(|&&byte| byte == b'\n').count() + 1,
+            column: offset - line_start + 1,
+            expected: expected.into(),
+        }
+    }
+
+    // Locates the start of `fragment`, which must be a slice of `input`
+    pub fn at<B: AsRef<[u8]> + ?Sized>(input: &B, fragment: &B, expected: impl Into<String>) -> Self {
+        Self::new(input, offset_of(input.as_ref(), fragment.as_ref()), expected)
+    }
+
+    // Locates the end of `fragment`, e.g. to report a missing token at the end of a line
+    pub fn after<B: AsRef<[u8]> + ?Sized>(input: &B, fragment: &B, expected: impl Into<String>) -> Self {
+        let fragment = fragment.as_ref();
+
+        Self::new(input, offset_of(input.as_ref(), fragment) + fragment.len(), expected)
+    }
+
+    pub fn end<B: AsRef<[u8]> + ?Sized>(input: &B, expected: impl Into<String>) -> Self {
+        Self::new(input, input.as_ref().len(), expected)
+    }
+}
+
+fn offset_of(input: &[u8], fragment: &[u8]) -> usize {
+    let offset = (fragment.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    debug_assert!(offset + fragment.len() <= input.len(), "The fragment is not a slice of the input");
+
+    offset.min(input.len())
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: expected {}", self.line, self.column, self.expected)
+    }
+}
+
+impl Error for ParseError { }
+
+impl From<ParseError> for io::Error {
+    fn from(error: ParseError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+pub fn field<T: FromStr>(input: &str, fragment: &str, expected: &str) -> ParseResult<T> {
+    fragment.parse()
+        .map_err(|_| ParseError::at(input, fragment, expected))
+}
+
+// Missing tokens are reported right after `line`
+pub fn next<'a>(input: &str, tokens: &mut impl Iterator<Item = &'a str>, line: &str, expected: &str) -> ParseResult<&'a str> {
+    tokens.next()
+        .ok_or_else(|| ParseError::after(input, line, expected))
+}
+
+pub fn next_field<'a, T: FromStr>(input: &str, tokens: &mut impl Iterator<Item = &'a str>, line: &str, expected: &str) -> ParseResult<T> {
+    field(input, next(input, tokens, line, expected)?, expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locations() {
+        let input = "12,7\n3,x\n";
+        let line = input.lines().nth(1).unwrap();
+        let mut tokens = line.split(',');
+
+        assert_eq!(next_field::<u32>(input, &mut tokens, line, "a number"), Ok(3));
+        assert_eq!(
+            next_field::<u32>(input, &mut tokens, line, "a number").unwrap_err().to_string(),
+            "line 2, column 3: expected a number"
+        );
+        assert_eq!(
+            next_field::<u32>(input, &mut tokens, line, "a number").unwrap_err().to_string(),
+            "line 2, column 4: expected a number"
+        );
+        assert_eq!(ParseError::end(input.as_bytes(), "a line"), ParseError { line: 3, column: 1, expected: "a line".into() });
+    }
+}
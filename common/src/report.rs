@@ -0,0 +1,36 @@
+use std::fmt;
+
+use crate::check::Verdict;
+use crate::solution::{Answer, Solved};
+
+pub struct Report {
+    pub year: u32,
+    pub day: u32,
+    pub solved: Result<Solved, String>,
+}
+
+fn verdict(answer: &Answer) -> String {
+    match &answer.verdict {
+        None => String::new(),
+        Some(Verdict::Fail { expected }) => format!(" [fail, expected {}]", expected),
+        Some(verdict) => format!(" [{}]", verdict),
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} day {:02}:", self.year, self.day)?;
+
+        match &self.solved {
+            Err(e) => writeln!(f, "  {}", e),
+            Ok(solved) => solved.answers.iter().try_for_each(|answer| {
+                if answer.value.contains('\n') {
+                    writeln!(f, "  Part {}:{}", answer.part, verdict(answer))?;
+                    answer.value.lines().try_for_each(|line| writeln!(f, "    {}", line))
+                } else {
+                    writeln!(f, "  Part {}: {}{}", answer.part, answer.value, verdict(answer))
+                }
+            }),
+        }
+    }
+}
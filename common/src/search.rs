@@ -0,0 +1,282 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// Both searches are iterators over the states they reach, yielding each state
+// once along with its distance from the closest start. Stopping at a goal is done
+// with the usual iterator adapters, e.g. `find`, `take_while` or `max_by_key`.
+//
+// Ties are broken deterministically: among equally distant states, the one
+// discovered first is yielded first, and neighbours are discovered in the order
+// the neighbour function returns them.
+
+pub struct Bfs<S, N> {
+    queue: VecDeque<(S, usize)>,
+    parents: HashMap<S, Option<S>>,
+    neighbours: N,
+}
+
+// Unweighted search, where every move costs 1
+pub fn bfs<S, N, I>(starts: impl IntoIterator<Item = S>, neighbours: N) -> Bfs<S, N>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut queue = VecDeque::new();
+    let mut parents = HashMap::new();
+
+    for start in starts {
+        if !parents.contains_key(&start) {
+            parents.insert(start.clone(), None);
+            queue.push_back((start, 0));
+        }
+    }
+
+    Bfs { queue, parents, neighbours }
+}
+
+impl<S, N, I> Iterator for Bfs<S, N>
+where
+    S: Clone + Eq + Hash,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    type Item = (S, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (state, distance) = self.queue.pop_front()?;
+
+        for neighbour in (self.neighbours)(&state) {
+            if !self.parents.contains_key(&neighbour) {
+                self.parents.insert(neighbour.clone(), Some(state.clone()));
+                self.queue.push_back((neighbour, distance + 1));
+            }
+        }
+
+        Some((state, distance))
+    }
+}
+
+impl<S: Clone + Eq + Hash, N> Bfs<S, N> {
+    // The states leading to an already discovered `state`, from a start to `state` itself
+    pub fn path(&self, state: &S) -> Vec<S> {
+        reconstruct(state, |state| self.parents.get(state).and_then(Option::as_ref))
+    }
+}
+
+pub struct BestFirst<S, C, N, H> {
+    heap: BinaryHeap<Candidate<S, C>>,
+    best: HashMap<S, (C, Option<S>)>,
+    settled: HashSet<S>,
+    neighbours: N,
+    heuristic: H,
+    discovered: usize,
+}
+
+// Weighted search, the neighbour function returns each neighbour with the cost to move there
+pub fn dijkstra<S, C, N, I>(starts: impl IntoIterator<Item = S>, neighbours: N)
+    -> BestFirst<S, C, N, fn(&S) -> C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbours, |_| C::default())
+}
+
+// `heuristic` must be consistent: never more than the cost of a move plus the
+// heuristic of the neighbour it leads to (which also means it never
+// overestimates). States are settled once, so with a heuristic that is only
+// admissible the first goal state yielded might not be the cheapest one
+pub fn astar<S, C, N, I, H>(starts: impl IntoIterator<Item = S>, neighbours: N, heuristic: H)
+    -> BestFirst<S, C, N, H>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+{
+    let mut search = BestFirst {
+        heap: BinaryHeap::new(),
+        best: HashMap::new(),
+        settled: HashSet::new(),
+        neighbours,
+        heuristic,
+        discovered: 0,
+    };
+
+    for start in starts {
+        search.discover(start, C::default(), None);
+    }
+
+    search
+}
+
+impl<S, C, N, I, H> BestFirst<S, C, N, H>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+{
+    fn discover(&mut self, state: S, cost: C, parent: Option<S>) {
+        if self.settled.contains(&state) {
+            return
+        }
+
+        match self.best.get(&state) {
+            Some(&(best_cost, _)) if best_cost <= cost => (),
+            _better_or_undiscovered => {
+                let priority = cost + (self.heuristic)(&state);
+                self.best.insert(state.clone(), (cost, parent));
+                self.heap.push(Candidate { priority, discovered: self.discovered, cost, state });
+                self.discovered += 1;
+            }
+        }
+    }
+}
+
+impl<S, C, N, I, H> Iterator for BestFirst<S, C, N, H>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    H: FnMut(&S) -> C,
+{
+    type Item = (S, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Candidate { state, cost, .. } = self.heap.pop()?;
+
+            // Skip the stale entries left behind when a cheaper path was found
+            if self.settled.contains(&state) || self.best[&state].0 < cost {
+                continue
+            }
+
+            self.settled.insert(state.clone());
+            for (neighbour, move_cost) in (self.neighbours)(&state) {
+                self.discover(neighbour, cost + move_cost, Some(state.clone()));
+            }
+
+            return Some((state, cost))
+        }
+    }
+}
+
+impl<S: Clone + Eq + Hash, C, N, H> BestFirst<S, C, N, H> {
+    // The cheapest known states leading to `state`, from a start to `state` itself
+    pub fn path(&self, state: &S) -> Vec<S> {
+        reconstruct(state, |state| self.best.get(state).and_then(|(_, parent)| parent.as_ref()))
+    }
+}
+
+fn reconstruct<'a, S: Clone + 'a>(state: &'a S, parent: impl Fn(&'a S) -> Option<&'a S>) -> Vec<S> {
+    let mut path: Vec<_> = std::iter::successors(Some(state), |&state| parent(state))
+        .cloned()
+        .collect();
+    path.reverse();
+
+    path
+}
+
+struct Candidate<S, C> {
+    priority: C,
+    discovered: usize,
+    cost: C,
+    state: S,
+}
+
+// Reversed so that the max-heap pops the lowest priority, then the earliest discovered
+impl<S, C: Ord> Ord for Candidate<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+            .then(other.discovered.cmp(&self.discovered))
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Candidate<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Candidate<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S, C: Ord> Eq for Candidate<S, C> { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 5x4 maze, `#` are walls
+    const MAZE: [&[u8]; 4] = [
+        b"..#..",
+        b".##.#",
+        b"...#.",
+        b"#....",
+    ];
+
+    fn open_neighbours(&(x, y): &(i32, i32)) -> Vec<(i32, i32)> {
+        [(x, y - 1), (x - 1, y), (x + 1, y), (x, y + 1)].iter()
+            .copied()
+            .filter(|&(x, y)| x >= 0 && y >= 0 && x < 5 && y < 4)
+            .filter(|&(x, y)| MAZE[y as usize][x as usize] == b'.')
+            .collect()
+    }
+
+    #[test]
+    fn breadth_first() {
+        let mut search = bfs(vec![(0, 0)], open_neighbours);
+
+        let (goal, distance) = search.find(|&(pos, _)| pos == (4, 2)).unwrap();
+        assert_eq!(distance, 8);
+        assert_eq!(search.path(&goal), [
+            (0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (2, 3), (3, 3), (4, 3), (4, 2)
+        ]);
+
+        // (3, 0), (4, 0) and (3, 1) are walled off
+        assert_eq!(bfs(vec![(0, 0)], open_neighbours).count(), 11);
+        assert_eq!(bfs(vec![(3, 0)], open_neighbours).map(|(_, d)| d).max(), Some(1));
+    }
+
+    #[test]
+    fn multi_source() {
+        let distances: HashMap<_, _> = bfs(vec![(0, 0), (4, 2)], open_neighbours).collect();
+
+        assert_eq!(distances[&(4, 3)], 1);
+        assert_eq!(distances[&(2, 2)], 4);
+        assert_eq!(distances[&(1, 0)], 1);
+    }
+
+    #[test]
+    fn weighted() {
+        // Moving down costs 10, every other move costs 1
+        let weighted = |&(x, y): &(i32, i32)| {
+            open_neighbours(&(x, y)).into_iter()
+                .map(move |(nx, ny)| ((nx, ny), if ny > y { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+
+        let (_, cost) = dijkstra(vec![(0, 0)], weighted)
+            .find(|&(pos, _)| pos == (4, 2))
+            .unwrap();
+        assert_eq!(cost, 35);
+
+        let manhattan = |&(x, y): &(i32, i32)| (4 - x).abs() + (2 - y).abs();
+        let mut search = astar(vec![(0, 0)], weighted, manhattan);
+        let (goal, cost) = search.find(|&(pos, _)| pos == (4, 2)).unwrap();
+        assert_eq!(cost, 35);
+        assert_eq!(search.path(&goal).len(), 9);
+    }
+}
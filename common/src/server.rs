@@ -0,0 +1,154 @@
+use std::io;
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+// Identifies the requests to the puzzle server, as its maintainer asks of automated tools
+const USER_AGENT: &str = "github.com/Globidev/advent-of-code";
+
+// A puzzle server, reached with the session cookie of a logged in user.
+// The base URL is configurable so that a local stand-in can be used instead
+#[derive(Debug, Clone)]
+pub struct Server {
+    base_url: String,
+    session: String,
+}
+
+impl Server {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.trim().to_owned(),
+        }
+    }
+
+    pub fn input(&self, year: u32, day: u32) -> io::Result<String> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    // The response is the HTML page telling how the answer was received
+    pub fn answer(&self, year: u32, day: u32, part: u32, answer: &str) -> io::Result<String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+
+        self.request("POST", &url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| request_error("POST", &url, e))?
+            .into_string()
+    }
+
+    fn get(&self, path: &str) -> io::Result<String> {
+        let url = format!("{}{}", self.base_url, path);
+
+        self.request("GET", &url)
+            .call()
+            .map_err(|e| request_error("GET", &url, e))?
+            .into_string()
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        ureq::request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+    }
+}
+
+fn request_error(method: &str, url: &str, error: ureq::Error) -> io::Error {
+    match error {
+        // The server explains what went wrong (expired session, locked puzzle, ...) on the first line
+        ureq::Error::Status(status, response) => {
+            let body = response.into_string().unwrap_or_default();
+            let reason = body.lines().next().unwrap_or("");
+
+            io::Error::other(format!("{} {}: HTTP {} {}", method, url, status, reason).trim_end().to_owned())
+        },
+        ureq::Error::Transport(transport) => io::Error::other(format!("{} {}: {}", method, url, transport)),
+    }
+}
+
+// A local stand-in for the puzzle server, answering each connection with the
+// next canned response. The requests it received are given back when joining
+#[cfg(test)]
+pub(crate) fn stand_in(responses: Vec<(u16, &'static str)>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind the stand-in server");
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = std::thread::spawn(move || {
+        responses.into_iter()
+            .map(|(status, body)| {
+                let (stream, _) = listener.accept().expect("Failed to accept a connection");
+                let mut reader = BufReader::new(&stream);
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request += &line;
+                    if line == "\r\n" { break }
+                }
+
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request += &String::from_utf8(content).unwrap();
+
+                write!(
+                    &stream,
+                    "HTTP/1.1 {} Stand-in\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status, body.len(), body
+                ).unwrap();
+
+                request
+            })
+            .collect()
+    });
+
+    (base_url, handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input() {
+        let (base_url, requests) = stand_in(vec![(200, "1\n2\n3\n")]);
+        let server = Server::new(&format!("{}/", base_url), "secret\n");
+
+        assert_eq!(server.input(2018, 7).unwrap(), "1\n2\n3\n");
+
+        let request = &requests.join().unwrap()[0];
+        assert!(request.starts_with("GET /2018/day/7/input HTTP/1.1\r\n"));
+        assert!(request.to_ascii_lowercase().contains("\r\ncookie: session=secret\r\n"));
+    }
+
+    #[test]
+    fn answer() {
+        let (base_url, requests) = stand_in(vec![(200, "<article><p>That's the right answer!</p></article>")]);
+        let server = Server::new(&base_url, "secret");
+
+        assert!(server.answer(2019, 4, 2, "1 234").unwrap().contains("right answer"));
+
+        let request = &requests.join().unwrap()[0];
+        assert!(request.starts_with("POST /2019/day/4/answer HTTP/1.1\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=1+234"));
+    }
+
+    #[test]
+    fn status_error() {
+        let (base_url, requests) = stand_in(vec![(400, "Puzzle inputs differ by user.  Please log in.\n")]);
+        let server = Server::new(&base_url, "expired");
+
+        let error = server.input(2015, 1).unwrap_err().to_string();
+        requests.join().unwrap();
+
+        assert_eq!(
+            error,
+            format!("GET {}/2015/day/1/input: HTTP 400 Puzzle inputs differ by user.  Please log in.", base_url)
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use std::ops::{Add, AddAssign, BitAnd, BitOr, BitXor, Mul, Shr, Sub};
+
+// Portable vectors for the vectorized solutions, with the API of the `packed_simd`
+// crate they were first written against, so that they build on a stable toolchain.
+// The lanes are plain arrays processed one by one and arithmetic wraps like it
+// did with `packed_simd`
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Simd<T, const N: usize>([T; N]);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mask<const N: usize>([bool; N]);
+
+#[allow(non_camel_case_types)] pub type u8x64 = Simd<u8, 64>;
+#[allow(non_camel_case_types)] pub type u16x32 = Simd<u16, 32>;
+#[allow(non_camel_case_types)] pub type i16x32 = Simd<i16, 32>;
+#[allow(non_camel_case_types)] pub type i32x16 = Simd<i32, 16>;
+#[allow(non_camel_case_types)] pub type f32x2 = Simd<f32, 2>;
+#[allow(non_camel_case_types)] pub type m8x64 = Mask<64>;
+
+pub trait Lane: Copy + PartialOrd {
+    const ZERO: Self;
+
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+    fn mul(self, other: Self) -> Self;
+}
+
+macro_rules! wrapping_lanes {
+    ($($lane: ty),*) => { $(
+        impl Lane for $lane {
+            const ZERO: Self = 0;
+
+            fn add(self, other: Self) -> Self { self.wrapping_add(other) }
+            fn sub(self, other: Self) -> Self { self.wrapping_sub(other) }
+            fn mul(self, other: Self) -> Self { self.wrapping_mul(other) }
+        }
+
+        impl<const N: usize> Add<Simd<$lane, N>> for $lane {
+            type Output = Simd<$lane, N>;
+
+            fn add(self, vector: Simd<$lane, N>) -> Simd<$lane, N> {
+                Simd::splat(self) + vector
+            }
+        }
+    )* };
+}
+
+wrapping_lanes!(u8, u16, i16, i32);
+
+impl Lane for f32 {
+    const ZERO: Self = 0.0;
+
+    fn add(self, other: Self) -> Self { self + other }
+    fn sub(self, other: Self) -> Self { self - other }
+    fn mul(self, other: Self) -> Self { self * other }
+}
+
+impl<T: Copy, const N: usize> Simd<T, N> {
+    pub const fn splat(value: T) -> Self {
+        Self([value; N])
+    }
+
+    pub const fn lanes() -> usize {
+        N
+    }
+
+    pub fn from_slice_unaligned(slice: &[T]) -> Self {
+        let mut lanes = [slice[0]; N];
+        lanes.copy_from_slice(&slice[..N]);
+
+        Self(lanes)
+    }
+
+    pub fn write_to_slice_unaligned(self, slice: &mut [T]) {
+        slice[..N].copy_from_slice(&self.0)
+    }
+
+    pub fn extract(self, idx: usize) -> T {
+        self.0[idx]
+    }
+
+    fn map(self, f: impl Fn(T) -> T) -> Self {
+        Self(self.0.map(f))
+    }
+
+    fn zip(self, other: Self, f: impl Fn(T, T) -> T) -> Self {
+        let mut lanes = self.0;
+        lanes.iter_mut().zip(other.0).for_each(|(lane, other)| *lane = f(*lane, other));
+
+        Self(lanes)
+    }
+
+    fn test(self, other: Self, f: impl Fn(&T, &T) -> bool) -> Mask<N> {
+        let mut mask = [false; N];
+        mask.iter_mut().enumerate().for_each(|(i, lane)| *lane = f(&self.0[i], &other.0[i]));
+
+        Mask(mask)
+    }
+}
+
+impl<T: Lane, const N: usize> Simd<T, N> {
+    pub fn eq(self, other: Self) -> Mask<N> { self.test(other, T::eq) }
+    pub fn lt(self, other: Self) -> Mask<N> { self.test(other, T::lt) }
+    pub fn gt(self, other: Self) -> Mask<N> { self.test(other, T::gt) }
+    pub fn ge(self, other: Self) -> Mask<N> { self.test(other, T::ge) }
+
+    pub fn max_element(self) -> T {
+        self.0.iter().copied().fold(self.0[0], |max, lane| if lane > max { lane } else { max })
+    }
+
+    pub fn min_element(self) -> T {
+        self.0.iter().copied().fold(self.0[0], |min, lane| if lane < min { lane } else { min })
+    }
+
+    pub fn wrapping_sum(self) -> T {
+        self.0.iter().copied().fold(T::ZERO, T::add)
+    }
+
+    // Moves lane `indices[i]` to lane `i`
+    pub fn shuffle(self, indices: [usize; N]) -> Self {
+        Self(indices.map(|idx| self.0[idx]))
+    }
+}
+
+impl<const N: usize> Simd<f32, N> {
+    pub fn abs(self) -> Self {
+        self.map(f32::abs)
+    }
+
+    pub fn sum(self) -> f32 {
+        self.0.iter().sum()
+    }
+}
+
+impl<T: Lane, const N: usize> Add for Simd<T, N> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self { self.zip(other, T::add) }
+}
+
+impl<T: Lane, const N: usize> AddAssign for Simd<T, N> {
+    fn add_assign(&mut self, other: Self) { *self = *self + other }
+}
+
+impl<T: Lane, const N: usize> Sub for Simd<T, N> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self { self.zip(other, T::sub) }
+}
+
+impl<T: Lane, const N: usize> Mul for Simd<T, N> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self { self.zip(other, T::mul) }
+}
+
+impl<T: Copy + BitAnd<Output = T>, const N: usize> BitAnd for Simd<T, N> {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self { self.zip(other, T::bitand) }
+}
+
+impl<T: Copy + BitOr<Output = T>, const N: usize> BitOr for Simd<T, N> {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self { self.zip(other, T::bitor) }
+}
+
+impl<T: Copy + BitXor<Output = T>, const N: usize> BitXor for Simd<T, N> {
+    type Output = Self;
+    fn bitxor(self, other: Self) -> Self { self.zip(other, T::bitxor) }
+}
+
+impl<T: Copy + Shr<u32, Output = T>, const N: usize> Shr<u32> for Simd<T, N> {
+    type Output = Self;
+    fn shr(self, bits: u32) -> Self { self.map(|lane| lane >> bits) }
+}
+
+impl<const N: usize> Mask<N> {
+    pub fn select<T: Copy>(self, if_true: Simd<T, N>, if_false: Simd<T, N>) -> Simd<T, N> {
+        let mut lanes = if_false.0;
+        lanes.iter_mut().enumerate()
+            .filter(|&(i, _)| self.0[i])
+            .for_each(|(i, lane)| *lane = if_true.0[i]);
+
+        Simd(lanes)
+    }
+}
+
+impl<const N: usize> BitAnd for Mask<N> {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        let mut lanes = self.0;
+        lanes.iter_mut().zip(other.0).for_each(|(lane, other)| *lane &= other);
+
+        Mask(lanes)
+    }
+}
+
+// `packed_simd` builds vectors from one argument per lane
+macro_rules! constructors {
+    ($($vector: ty, $lane: ty: $($x: ident)*;)*) => { $(
+        impl $vector {
+            #[allow(clippy::too_many_arguments)]
+            pub const fn new($($x: $lane),*) -> Self {
+                Self([$($x),*])
+            }
+        }
+    )* };
+}
+
+constructors! {
+    i16x32, i16: x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15 x16 x17 x18 x19 x20 x21 x22 x23 x24 x25 x26 x27 x28 x29 x30 x31;
+    i32x16, i32: x0 x1 x2 x3 x4 x5 x6 x7 x8 x9 x10 x11 x12 x13 x14 x15;
+    f32x2, f32: x0 x1;
+}
+
+impl m8x64 {
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(x0: bool, x1: bool, x2: bool, x3: bool, x4: bool, x5: bool, x6: bool, x7: bool, x8: bool, x9: bool, x10: bool, x11: bool, x12: bool, x13: bool, x14: bool, x15: bool, x16: bool, x17: bool, x18: bool, x19: bool, x20: bool, x21: bool, x22: bool, x23: bool, x24: bool, x25: bool, x26: bool, x27: bool, x28: bool, x29: bool, x30: bool, x31: bool, x32: bool, x33: bool, x34: bool, x35: bool, x36: bool, x37: bool, x38: bool, x39: bool, x40: bool, x41: bool, x42: bool, x43: bool, x44: bool, x45: bool, x46: bool, x47: bool, x48: bool, x49: bool, x50: bool, x51: bool, x52: bool, x53: bool, x54: bool, x55: bool, x56: bool, x57: bool, x58: bool, x59: bool, x60: bool, x61: bool, x62: bool, x63: bool) -> Self {
+        Self([x0, x1, x2, x3, x4, x5, x6, x7, x8, x9, x10, x11, x12, x13, x14, x15, x16, x17, x18, x19, x20, x21, x22, x23, x24, x25, x26, x27, x28, x29, x30, x31, x32, x33, x34, x35, x36, x37, x38, x39, x40, x41, x42, x43, x44, x45, x46, x47, x48, x49, x50, x51, x52, x53, x54, x55, x56, x57, x58, x59, x60, x61, x62, x63])
+    }
+}
+
+// Same syntax as the `packed_simd` macro, the indices being taken from the first vector
+#[macro_export]
+macro_rules! shuffle {
+    ($vector: expr, [$($idx: expr),* $(,)?]) => {
+        $vector.shuffle([$($idx),*])
+    };
+}
+
+pub use crate::shuffle;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lanes() {
+        let values = [250_u8, 3, 7, 1];
+        let vector = Simd::<u8, 4>::from_slice_unaligned(&values);
+
+        assert_eq!((vector + Simd::splat(10)).extract(0), 4);
+        assert_eq!(vector.max_element(), 250);
+        assert_eq!(vector.wrapping_sum(), 5);
+        assert_eq!(shuffle!(vector, [3, 0, 1, 2]).extract(0), 1);
+
+        let mask = vector.gt(Simd::splat(5));
+        assert_eq!(mask.select(Simd::splat(1), Simd::splat(0)).wrapping_sum(), 2);
+
+        let signed = i16x32::splat(-4) >> 15;
+        assert_eq!(signed.extract(31), -1);
+        assert_eq!((1 + signed).min_element(), 0);
+    }
+}
@@ -0,0 +1,185 @@
+use std::any::Any;
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use crate::check::{self, Verdict};
+use crate::parse::ParseResult;
+
+pub trait Solution {
+    const YEAR: u32;
+    const DAY: u32;
+    const EMBEDDED_INPUT: &'static str;
+    // The last day of a year only has one puzzle, `part2` is never called then
+    const HAS_PART2: bool = true;
+
+    type Parsed<'a>;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>>;
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1;
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2;
+}
+
+#[derive(Debug, Clone)]
+pub struct Answer {
+    pub part: u32,
+    pub value: String,
+    pub elapsed: Duration,
+    pub verdict: Option<Verdict>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Solved {
+    pub input_hash: String,
+    pub parse_elapsed: Duration,
+    pub answers: Vec<Answer>,
+}
+
+impl Solved {
+    pub fn total_elapsed(&self) -> Duration {
+        self.answers.iter()
+            .map(|answer| answer.elapsed)
+            .sum::<Duration>() + self.parse_elapsed
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    pub embedded_input: &'static str,
+    solver: fn(&str, &dyn Fn(u32) -> bool) -> ParseResult<Solved>,
+}
+
+impl Day {
+    pub fn new<S: Solution>() -> Self {
+        Self {
+            year: S::YEAR,
+            day: S::DAY,
+            embedded_input: S::EMBEDDED_INPUT,
+            solver: solve::<S>,
+        }
+    }
+
+    pub fn solve(&self, input: &str, part_selected: impl Fn(u32) -> bool) -> ParseResult<Solved> {
+        (self.solver)(input, &part_selected)
+    }
+
+    // Catches a panicking solution so that it only fails its own day
+    pub fn try_solve(&self, input: &str, part_selected: impl Fn(u32) -> bool) -> Result<Solved, String> {
+        panic::catch_unwind(AssertUnwindSafe(|| self.solve(input, part_selected)))
+            .map_err(|payload| format!("panicked: {}", panic_message(&*payload)))?
+            .map_err(|e| format!("invalid input: {}", e))
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload.downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+
+    (result, start.elapsed())
+}
+
+fn solve<S: Solution>(input: &str, part_selected: &dyn Fn(u32) -> bool) -> ParseResult<Solved> {
+    let (parsed, parse_elapsed) = timed(|| S::parse(input));
+    let parsed = parsed?;
+
+    let mut answers = Vec::with_capacity(2);
+    if part_selected(1) {
+        let (value, elapsed) = timed(|| S::part1(&parsed));
+        answers.push(Answer { part: 1, value: value.to_string(), elapsed, verdict: None });
+    }
+    if S::HAS_PART2 && part_selected(2) {
+        let (value, elapsed) = timed(|| S::part2(&parsed));
+        answers.push(Answer { part: 2, value: value.to_string(), elapsed, verdict: None });
+    }
+
+    Ok(Solved { input_hash: check::input_hash(input), parse_elapsed, answers })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::ParseError;
+
+    struct Failing;
+
+    impl Solution for Failing {
+        const YEAR: u32 = 2019;
+        const DAY: u32 = 1;
+        const EMBEDDED_INPUT: &'static str = "";
+
+        type Parsed<'a> = &'a str;
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+            if input.is_empty() {
+                return Err(ParseError::end(input, "a number"));
+            }
+
+            Ok(input)
+        }
+
+        fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+            input.len()
+        }
+
+        fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+            input.parse().expect("Invalid number")
+        }
+    }
+
+    struct SinglePart;
+
+    impl Solution for SinglePart {
+        const YEAR: u32 = 2018;
+        const DAY: u32 = 25;
+        const EMBEDDED_INPUT: &'static str = "";
+        const HAS_PART2: bool = false;
+
+        type Parsed<'a> = ();
+        type Part1 = usize;
+        type Part2 = usize;
+
+        fn parse(_input: &str) -> ParseResult<Self::Parsed<'_>> {
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Parsed<'_>) -> Self::Part1 {
+            1
+        }
+
+        fn part2(_input: &Self::Parsed<'_>) -> Self::Part2 {
+            unreachable!("No second part")
+        }
+    }
+
+    #[test]
+    fn single_part() {
+        let day = Day::new::<SinglePart>();
+
+        let parts = |solved: Solved| solved.answers.iter().map(|answer| answer.part).collect::<Vec<_>>();
+        assert_eq!(parts(day.try_solve("", |_| true).unwrap()), [1]);
+        assert!(day.try_solve("", |part| part == 2).unwrap().answers.is_empty());
+    }
+
+    #[test]
+    fn panic_isolation() {
+        let day = Day::new::<Failing>();
+
+        assert_eq!(day.try_solve("12", |_| true).unwrap().answers[1].value, "12");
+        assert_eq!(day.try_solve("x", |part| part == 1).unwrap().answers[0].value, "1");
+        assert!(day.try_solve("x", |_| true).unwrap_err().starts_with("panicked: Invalid number"));
+        assert_eq!(day.try_solve("", |_| true).unwrap_err(), "invalid input: line 1, column 1: expected a number");
+    }
+}
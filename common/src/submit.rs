@@ -0,0 +1,387 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+
+use crate::server::Server;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    // Submitted too soon after a previous answer
+    Wait,
+    // The part was already solved, or the previous one was not
+    WrongLevel,
+}
+
+impl Outcome {
+    fn is_wrong(self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect)
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "that's the right answer"),
+            Outcome::TooHigh => write!(f, "the answer is too high"),
+            Outcome::TooLow => write!(f, "the answer is too low"),
+            Outcome::Incorrect => write!(f, "that's not the right answer"),
+            Outcome::Wait => write!(f, "an answer was given too recently"),
+            Outcome::WrongLevel => write!(f, "this part is already solved or locked"),
+        }
+    }
+}
+
+// The outcome of a submission, along with how long the server wants us to wait
+// before the next one. `None` if the page is not one of the known responses
+pub fn parse_response(page: &str) -> Option<(Outcome, Duration)> {
+    let outcome = if page.contains("That's the right answer") {
+        Outcome::Correct
+    } else if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if page.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        }
+    } else if page.contains("You gave an answer too recently") {
+        Outcome::Wait
+    } else if page.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        return None
+    };
+
+    Some((outcome, cooldown(page)))
+}
+
+// "You have 4m 12s left to wait" after an early answer, or "Please wait one
+// minute before trying again" after a wrong one
+fn cooldown(page: &str) -> Duration {
+    let between = |start: &str, end: &str| {
+        let from = page.find(start)? + start.len();
+        let to = from + page[from..].find(end)?;
+        Some(&page[from..to])
+    };
+
+    let seconds = if let Some(left) = between("You have ", " left to wait") {
+        left.split_whitespace()
+            .map(|amount| {
+                let (value, unit) = amount.split_at(amount.len() - 1);
+                let scale = match unit { "h" => 3600, "m" => 60, _ => 1 };
+                value.parse::<u64>().unwrap_or(0) * scale
+            })
+            .sum()
+    } else if let Some(wait) = between("wait ", " before trying again") {
+        let minutes = wait.split_whitespace().next().unwrap_or("");
+        match minutes {
+            "one" => 60,
+            minutes => minutes.parse::<u64>().unwrap_or(0) * 60,
+        }
+    } else {
+        0
+    };
+
+    Duration::from_secs(seconds)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub outcome: Outcome,
+    // Seconds since the Unix epoch
+    pub submitted_at: u64,
+    pub cooldown_secs: u64,
+}
+
+// Why an answer is not worth sending, judging by the previous guesses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyCorrect,
+    AlreadySolved { answer: String },
+    KnownWrong(Outcome),
+    TooHigh { bound: i64 },
+    TooLow { bound: i64 },
+    RateLimited { remaining: Duration },
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect => write!(f, "this answer was already accepted"),
+            Refusal::AlreadySolved { answer } => write!(f, "already solved with {}", answer),
+            Refusal::KnownWrong(outcome) => write!(f, "already submitted: {}", outcome),
+            Refusal::TooHigh { bound } => write!(f, "too high: {} already was", bound),
+            Refusal::TooLow { bound } => write!(f, "too low: {} already was", bound),
+            Refusal::RateLimited { remaining } => write!(f, "the server asked to wait {}s more", remaining.as_secs()),
+        }
+    }
+}
+
+// The guesses submitted so far, oldest first
+#[derive(Debug, Default, Clone)]
+pub struct History(Vec<Guess>);
+
+impl History {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let file = match File::open(path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            file => file?,
+        };
+
+        csv::Reader::from_reader(file)
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .map(History)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut writer = csv::Writer::from_path(path)?;
+        for guess in &self.0 {
+            writer.serialize(guess)?;
+        }
+
+        writer.flush()
+    }
+
+    pub fn guesses(&self, year: u32, day: u32, part: u32) -> impl Iterator<Item = &Guess> {
+        self.0.iter()
+            .filter(move |guess| (guess.year, guess.day, guess.part) == (year, day, part))
+    }
+
+    pub fn is_accepted(&self, year: u32, day: u32, part: u32, answer: &str) -> bool {
+        self.guesses(year, day, part)
+            .any(|guess| guess.outcome == Outcome::Correct && guess.answer == answer.trim())
+    }
+
+    pub fn record(&mut self, guess: Guess) {
+        self.0.push(guess)
+    }
+
+    // The exclusive range left for a numeric answer: above every answer that was
+    // too low and below every answer that was too high
+    pub fn bounds(&self, year: u32, day: u32, part: u32) -> (Option<i64>, Option<i64>) {
+        self.guesses(year, day, part)
+            .filter_map(|guess| Some((guess.outcome, guess.answer.trim().parse::<i64>().ok()?)))
+            .fold((None, None), |(low, high), (outcome, value)| match outcome {
+                Outcome::TooLow => (low.max(Some(value)), high),
+                Outcome::TooHigh => (low, Some(high.map_or(value, |high| high.min(value)))),
+                _ => (low, high),
+            })
+    }
+
+    pub fn check(&self, year: u32, day: u32, part: u32, answer: &str, now: u64) -> Result<(), Refusal> {
+        let answer = answer.trim();
+        let guesses: Vec<_> = self.guesses(year, day, part).collect();
+
+        if let Some(correct) = guesses.iter().find(|guess| guess.outcome == Outcome::Correct) {
+            return Err(match correct.answer == answer {
+                true => Refusal::AlreadyCorrect,
+                false => Refusal::AlreadySolved { answer: correct.answer.clone() },
+            });
+        }
+
+        if let Some(wrong) = guesses.iter().find(|guess| guess.outcome.is_wrong() && guess.answer == answer) {
+            return Err(Refusal::KnownWrong(wrong.outcome));
+        }
+
+        if let Ok(value) = answer.parse::<i64>() {
+            match self.bounds(year, day, part) {
+                (Some(low), _) if value <= low => return Err(Refusal::TooLow { bound: low }),
+                (_, Some(high)) if value >= high => return Err(Refusal::TooHigh { bound: high }),
+                _ => (),
+            }
+        }
+
+        // The server's cooldown applies to every puzzle
+        let ready_at = self.0.iter()
+            .map(|guess| guess.submitted_at + guess.cooldown_secs)
+            .max()
+            .unwrap_or(0);
+        if ready_at > now {
+            return Err(Refusal::RateLimited { remaining: Duration::from_secs(ready_at - now) });
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    UnknownResponse(String),
+    Io(io::Error),
+}
+
+impl From<io::Error> for SubmitError {
+    fn from(error: io::Error) -> Self {
+        SubmitError::Io(error)
+    }
+}
+
+impl fmt::Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubmitError::Refused(refusal) => write!(f, "not submitted, {}", refusal),
+            SubmitError::UnknownResponse(page) => write!(f, "unrecognised response: {:?}", page),
+            SubmitError::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+// Sends answers to a puzzle server, keeping track of every guess in a CSV file
+// so that none is ever sent twice
+pub struct Submitter {
+    server: Server,
+    history_path: PathBuf,
+    history: History,
+}
+
+impl Submitter {
+    pub fn new(server: Server, history_path: impl Into<PathBuf>) -> io::Result<Self> {
+        let history_path = history_path.into();
+        let history = History::load(&history_path)?;
+
+        Ok(Self { server, history_path, history })
+    }
+
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    pub fn submit(&mut self, year: u32, day: u32, part: u32, answer: &str) -> Result<Outcome, SubmitError> {
+        let answer = answer.trim();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("The clock is set before 1970")
+            .as_secs();
+
+        self.history.check(year, day, part, answer, now)
+            .map_err(SubmitError::Refused)?;
+
+        let page = self.server.answer(year, day, part, answer)?;
+        let (outcome, cooldown) = parse_response(&page)
+            .ok_or_else(|| SubmitError::UnknownResponse(page.clone()))?;
+
+        self.history.record(Guess {
+            year,
+            day,
+            part,
+            answer: answer.to_owned(),
+            outcome,
+            submitted_at: now,
+            cooldown_secs: cooldown.as_secs(),
+        });
+        self.history.save(&self.history_path)?;
+
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, \
+        make sure you're using the full input data. Please wait one minute before trying again. \
+        <a href=\"/2018/day/1\">[Return to Day 1]</a></p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting \
+        an answer before trying again.  You have 4m 12s left to wait. \
+        <a href=\"/2018/day/1\">[Return to Day 1]</a></p></article>";
+
+    fn guess(answer: &str, outcome: Outcome) -> Guess {
+        Guess {
+            year: 2018, day: 1, part: 1,
+            answer: answer.to_owned(),
+            outcome,
+            submitted_at: 1000,
+            cooldown_secs: 60,
+        }
+    }
+
+    #[test]
+    fn responses() {
+        assert_eq!(parse_response(TOO_HIGH), Some((Outcome::TooHigh, Duration::from_secs(60))));
+        assert_eq!(parse_response(TOO_RECENT), Some((Outcome::Wait, Duration::from_secs(252))));
+        assert_eq!(parse_response("That's the right answer!"), Some((Outcome::Correct, Duration::from_secs(0))));
+        assert_eq!(
+            parse_response("That's not the right answer.  Please wait 5 minutes before trying again."),
+            Some((Outcome::Incorrect, Duration::from_secs(300)))
+        );
+        assert_eq!(
+            parse_response("You don't seem to be solving the right level.  Did you already complete it?"),
+            Some((Outcome::WrongLevel, Duration::from_secs(0)))
+        );
+        assert_eq!(parse_response("<html>Maintenance</html>"), None);
+    }
+
+    #[test]
+    fn refusals() {
+        let mut history = History::default();
+        history.record(guess("100", Outcome::TooHigh));
+        history.record(guess("10", Outcome::TooLow));
+        history.record(guess("42", Outcome::Incorrect));
+
+        assert_eq!(history.bounds(2018, 1, 1), (Some(10), Some(100)));
+        assert_eq!(history.bounds(2018, 1, 2), (None, None));
+
+        assert_eq!(history.check(2018, 1, 1, "42", 1000), Err(Refusal::KnownWrong(Outcome::Incorrect)));
+        assert_eq!(history.check(2018, 1, 1, "150", 1000), Err(Refusal::TooHigh { bound: 100 }));
+        assert_eq!(history.check(2018, 1, 1, "-3", 1000), Err(Refusal::TooLow { bound: 10 }));
+        assert_eq!(
+            history.check(2018, 1, 1, "50", 1030),
+            Err(Refusal::RateLimited { remaining: Duration::from_secs(30) })
+        );
+        assert_eq!(history.check(2018, 1, 1, "50", 1060), Ok(()));
+        assert_eq!(history.check(2018, 1, 1, "abc", 1060), Ok(()));
+
+        history.record(guess("50", Outcome::Correct));
+        assert_eq!(history.check(2018, 1, 1, " 50\n", 2000), Err(Refusal::AlreadyCorrect));
+        assert!(history.is_accepted(2018, 1, 1, " 50\n"));
+        assert!(!history.is_accepted(2018, 1, 1, "51"));
+        assert!(!history.is_accepted(2018, 1, 2, "50"));
+        assert_eq!(
+            history.check(2018, 1, 1, "51", 2000),
+            Err(Refusal::AlreadySolved { answer: "50".to_owned() })
+        );
+    }
+
+    #[test]
+    fn submission() {
+        let path = std::env::temp_dir().join(format!("aoc-submissions-{}.csv", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let (base_url, requests) = server::stand_in(vec![(200, TOO_HIGH)]);
+        let mut submitter = Submitter::new(Server::new(&base_url, "secret"), &path).unwrap();
+
+        assert_eq!(submitter.submit(2018, 1, 1, "100").unwrap(), Outcome::TooHigh);
+        assert_eq!(requests.join().unwrap().len(), 1);
+
+        // Reloaded from the file, the history keeps the stand-in from being asked again
+        let mut submitter = Submitter::new(Server::new(&base_url, "secret"), &path).unwrap();
+        assert_eq!(submitter.history().guesses(2018, 1, 1).count(), 1);
+        assert!(matches!(
+            submitter.submit(2018, 1, 1, "120"),
+            Err(SubmitError::Refused(Refusal::TooHigh { bound: 100 }))
+        ));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::report::Report;
+use crate::solution::Solved;
+
+pub struct TimingTable<'a> {
+    rows: Vec<(&'a Report, &'a Solved)>,
+}
+
+impl<'a> TimingTable<'a> {
+    // Failed days have no meaningful timings and are left out
+    pub fn new(reports: &'a [Report]) -> Self {
+        let mut rows: Vec<_> = reports.iter()
+            .filter_map(|report| report.solved.as_ref().ok().map(|solved| (report, solved)))
+            .collect();
+
+        rows.sort_by_key(|(_, solved)| std::cmp::Reverse(solved.total_elapsed()));
+
+        Self { rows }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.rows.iter()
+            .map(|(_, solved)| solved.total_elapsed())
+            .sum()
+    }
+}
+
+fn cell(elapsed: Option<Duration>) -> String {
+    elapsed.map_or_else(|| String::from("-"), |elapsed| format!("{:.2?}", elapsed))
+}
+
+impl fmt::Display for TimingTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:<10} {:>12} {:>12} {:>12} {:>12}", "day", "parse", "part 1", "part 2", "total")?;
+
+        for (report, solved) in &self.rows {
+            let part_elapsed = |part| solved.answers.iter()
+                .find(|answer| answer.part == part)
+                .map(|answer| answer.elapsed);
+
+            writeln!(
+                f, "{:<10} {:>12} {:>12} {:>12} {:>12}",
+                format!("{} {:02}", report.year, report.day),
+                cell(Some(solved.parse_elapsed)),
+                cell(part_elapsed(1)),
+                cell(part_elapsed(2)),
+                cell(Some(solved.total_elapsed())),
+            )?;
+        }
+
+        writeln!(f, "{:<10} {:>51}", "total", cell(Some(self.total())))
+    }
+}
@@ -0,0 +1,47 @@
+use crate::Pos;
+
+// Inclusive on both ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Pos,
+    pub max: Pos,
+}
+
+impl Bounds {
+    pub fn of(pos: Pos) -> Self {
+        Self { min: pos, max: pos }
+    }
+
+    pub fn enclosing(positions: impl IntoIterator<Item = Pos>) -> Option<Self> {
+        let mut positions = positions.into_iter();
+        let mut bounds = Self::of(positions.next()?);
+        positions.for_each(|pos| bounds.extend(pos));
+
+        Some(bounds)
+    }
+
+    pub fn extend(&mut self, pos: Pos) {
+        self.min.x = self.min.x.min(pos.x);
+        self.min.y = self.min.y.min(pos.y);
+        self.max.x = self.max.x.max(pos.x);
+        self.max.y = self.max.y.max(pos.y);
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        (self.min.x..=self.max.x).contains(&pos.x) && (self.min.y..=self.max.y).contains(&pos.y)
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+
+    // In reading order
+    pub fn positions(self) -> impl Iterator<Item = Pos> {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Pos { x, y }))
+    }
+}
@@ -0,0 +1,188 @@
+use std::ops::{Index, IndexMut};
+use aoc_common::{ParseError, ParseResult};
+use crate::{Bounds, Pos};
+
+// A rectangular grid whose top left corner is the origin, stored row by row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self where T: Clone {
+        Self { width, height, cells: vec![fill; width * height] }
+    }
+
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Self {
+        assert!(width > 0 && cells.len().is_multiple_of(width), "The cells do not fill whole rows");
+
+        Self { width, height: cells.len() / width, cells }
+    }
+
+    // Builds a grid from a character map, one line per row
+    // Empty lines are ignored and every other line must have the same width
+    pub fn parse<B, F>(input: &B, mut tile: F, expected: &str) -> ParseResult<Self>
+    where
+        B: AsRef<[u8]> + ?Sized,
+        F: FnMut(Pos, u8) -> Option<T>,
+    {
+        let input = input.as_ref();
+        let lines = input.split(|&byte| byte == b'\n').filter(|line| !line.is_empty());
+
+        let width = lines.clone().next()
+            .ok_or_else(|| ParseError::end(input, "a line of tiles"))?
+            .len();
+
+        let mut cells = Vec::with_capacity(input.len());
+        for (y, line) in lines.enumerate() {
+            if line.len() != width {
+                return Err(ParseError::after(input, line, format!("a line of {} tiles", width)))
+            }
+
+            for (x, &byte) in line.iter().enumerate() {
+                let pos = Pos { x: x as i32, y: y as i32 };
+                let cell = tile(pos, byte)
+                    .ok_or_else(|| ParseError::at(input, &line[x..=x], expected))?;
+                cells.push(cell);
+            }
+        }
+
+        Ok(Self::from_cells(width, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds {
+            min: Pos::ORIGIN,
+            max: Pos { x: self.width as i32 - 1, y: self.height as i32 - 1 },
+        }
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.y as usize * self.width + pos.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(move |idx| &mut self.cells[idx])
+    }
+
+    // In reading order
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        self.bounds().positions()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find_map(|(pos, cell)| if predicate(cell) { Some(pos) } else { None })
+    }
+
+    // Only the neighbours that are within the grid are yielded
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        pos.neighbours4().filter_map(move |pos| Some((pos, self.get(pos)?)))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        pos.neighbours8().filter_map(move |pos| Some((pos, self.get(pos)?)))
+    }
+
+    pub fn render(&self, mut glyph: impl FnMut(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&mut glyph).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is out of the {}x{} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is out of the {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wall(_pos: Pos, byte: u8) -> Option<bool> {
+        match byte {
+            b'#' => Some(true),
+            b'.' => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parsing() {
+        let grid = Grid::parse("#.#\n...\n", wall, "a wall or a floor").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert!(grid[Pos::new(2, 0)]);
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.render(|&wall| if wall { '#' } else { '.' }), "#.#\n...");
+
+        let error = Grid::parse("#.#\n..\n", wall, "a wall or a floor").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        let error = Grid::parse("#.#\n.x.\n", wall, "a wall or a floor").unwrap_err();
+        assert_eq!((error.line, error.column, error.expected.as_str()), (2, 2, "a wall or a floor"));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::from_cells(3, (0..9).collect());
+
+        let corner: Vec<_> = grid.neighbours4(Pos::ORIGIN).map(|(_, &cell)| cell).collect();
+        assert_eq!(corner, [1, 3]);
+
+        let center: Vec<_> = grid.neighbours8(Pos::new(1, 1)).map(|(_, &cell)| cell).collect();
+        assert_eq!(center, [0, 1, 2, 3, 5, 6, 7, 8]);
+    }
+}
@@ -0,0 +1,12 @@
+pub mod bounds;
+pub mod dense;
+pub mod direction;
+pub mod ocr;
+pub mod pos;
+pub mod sparse;
+
+pub use bounds::Bounds;
+pub use dense::Grid;
+pub use direction::Direction;
+pub use pos::Pos;
+pub use sparse::SparseGrid;
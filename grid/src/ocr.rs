@@ -0,0 +1,157 @@
+use std::fmt;
+use crate::{Grid, Pos};
+
+// Reads the letters some puzzles draw with lit pixels
+// Two fonts are known: a 4x6 one (2016, 2019, ...) and a 6x10 one (2018 day 10)
+
+const SMALL_FONT: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const LARGE_FONT: &[(char, &str)] = &[
+    ('A', "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#"),
+    ('B', "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####."),
+    ('C', ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####."),
+    ('E', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('F', "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('G', ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#"),
+    ('H', "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#"),
+    ('J', "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n#...#.\n.###.."),
+    ('K', "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#"),
+    ('L', "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######"),
+    ('N', "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#"),
+    ('P', "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#....."),
+    ('R', "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#"),
+    ('X', "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#"),
+    ('Z', "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######"),
+];
+
+// The letters drawn by the lit pixels, or `None` if any of them is unknown
+// Blank rows and columns around the text are ignored and letters are told apart
+// by the blank columns between them
+pub fn recognise(pixels: &Grid<bool>) -> Option<String> {
+    let lit_rows: Vec<_> = (0..pixels.height())
+        .filter(|&y| (0..pixels.width()).any(|x| lit(pixels, x, y)))
+        .collect();
+    let (&top, &bottom) = (lit_rows.first()?, lit_rows.last()?);
+
+    let font = match bottom - top + 1 {
+        6 => SMALL_FONT,
+        10 => LARGE_FONT,
+        _ => return None,
+    };
+
+    let lit_columns: Vec<_> = (0..pixels.width())
+        .map(|x| (top..=bottom).any(|y| lit(pixels, x, y)))
+        .collect();
+
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < pixels.width() {
+        if !lit_columns[x] {
+            x += 1;
+            continue
+        }
+
+        let width = lit_columns[x..].iter().take_while(|&&lit| lit).count();
+        let glyph = (top..=bottom)
+            .map(|y| (x..x + width).map(|x| if lit(pixels, x, y) { '#' } else { '.' }).collect())
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let &(letter, _) = font.iter().find(|(_, shape)| *shape == glyph)?;
+        letters.push(letter);
+        x += width;
+    }
+
+    Some(letters)
+}
+
+fn lit(pixels: &Grid<bool>, x: usize, y: usize) -> bool {
+    pixels[Pos { x: x as i32, y: y as i32 }]
+}
+
+// The pixels as they would be seen on screen
+pub fn render(pixels: &Grid<bool>) -> String {
+    pixels.render(|&lit| if lit { '▓' } else { '░' })
+}
+
+// The recognised letters, falling back to the rendering for unknown ones
+pub fn read(pixels: &Grid<bool>) -> String {
+    recognise(pixels).unwrap_or_else(|| render(pixels))
+}
+
+// Debug formats as the letters followed by the pixels they were read from
+pub struct Banner(pub Grid<bool>);
+
+impl fmt::Debug for Banner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match recognise(&self.0) {
+            Some(letters) => write!(f, "{}\n{}", letters, render(&self.0)),
+            None => write!(f, "\n{}", render(&self.0)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels(picture: &str) -> Grid<bool> {
+        Grid::parse(picture, |_, byte| Some(byte == b'#'), "a pixel").unwrap()
+    }
+
+    #[test]
+    fn small_font() {
+        let text = pixels("\
+            ..........................\n\
+            .#..#.####.#....#.....##..\n\
+            .#..#.#....#....#....#..#.\n\
+            .####.###..#....#....#..#.\n\
+            .#..#.#....#....#....#..#.\n\
+            .#..#.#....#....#....#..#.\n\
+            .#..#.####.####.####..##..\n\
+        ");
+
+        assert_eq!(recognise(&text).as_deref(), Some("HELLO"));
+        assert_eq!(render(&text).lines().nth(1), Some("░▓░░▓░▓▓▓▓░▓░░░░▓░░░░░▓▓░░"));
+    }
+
+    #[test]
+    fn large_font() {
+        let text = pixels(&[LARGE_FONT[10].1, LARGE_FONT[13].1]
+            .iter()
+            .map(|shape| shape.lines().collect::<Vec<_>>())
+            .fold(vec![String::new(); 10], |mut rows, glyph| {
+                rows.iter_mut().zip(glyph).for_each(|(row, line)| *row += &format!("{}..", line));
+                rows
+            })
+            .join("\n"));
+
+        assert_eq!(recognise(&text).as_deref(), Some("NX"));
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(recognise(&pixels("#.#\n.#.\n#.#")), None);
+        assert_eq!(read(&pixels("#.#\n.#.\n#.#")), "▓░▓\n░▓░\n▓░▓");
+        assert_eq!(recognise(&pixels("...\n...")), None);
+    }
+}
@@ -0,0 +1,101 @@
+use std::cmp::Ordering;
+use std::ops::{Add, AddAssign};
+use crate::Direction;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pos {
+    pub x: i32,
+    pub y: i32,
+}
+
+const NEIGHBOUR8_DELTAS: [(i32, i32); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1,  0),          (1,  0),
+    (-1,  1), (0,  1), (1,  1),
+];
+
+impl Pos {
+    pub const ORIGIN: Pos = Pos { x: 0, y: 0 };
+
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn translate(self, direction: Direction) -> Self {
+        let (dx, dy) = direction.delta();
+
+        Self { x: self.x + dx, y: self.y + dy }
+    }
+
+    pub fn manhattan_dist(self, other: Self) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // Both neighbour iterators yield positions in reading order
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Direction::all().map(move |direction| self.translate(direction))
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        NEIGHBOUR8_DELTAS.iter()
+            .map(move |&(dx, dy)| Self { x: self.x + dx, y: self.y + dy })
+    }
+}
+
+impl Add<Direction> for Pos {
+    type Output = Pos;
+
+    fn add(self, direction: Direction) -> Self::Output {
+        self.translate(direction)
+    }
+}
+
+impl AddAssign<Direction> for Pos {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = self.translate(direction);
+    }
+}
+
+// Positions are ordered in reading order: top to bottom, then left to right
+impl Ord for Pos {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Pos {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbours() {
+        let pos = Pos::new(3, -2);
+
+        let neighbours4: Vec<_> = pos.neighbours4().collect();
+        assert_eq!(neighbours4, [Pos::new(3, -3), Pos::new(2, -2), Pos::new(4, -2), Pos::new(3, -1)]);
+        assert!(neighbours4.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let neighbours8: Vec<_> = pos.neighbours8().collect();
+        assert_eq!(neighbours8.len(), 8);
+        assert!(neighbours8.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(neighbours8.iter().all(|&n| n != pos && (n.x - pos.x).abs() <= 1 && (n.y - pos.y).abs() <= 1));
+    }
+
+    #[test]
+    fn moves() {
+        let mut pos = Pos::ORIGIN;
+        pos += Direction::Right;
+        pos += Direction::Right;
+        pos += Direction::Up;
+
+        assert_eq!(pos, Pos::new(2, -1));
+        assert_eq!(pos.manhattan_dist(Pos::ORIGIN), 3);
+        assert_eq!(pos + Direction::Down + Direction::Left, Pos::new(1, 0));
+    }
+}
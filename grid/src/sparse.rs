@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use crate::{Bounds, Grid, Pos};
+
+// An unbounded grid that keeps track of the area its cells span
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self { cells: HashMap::new(), bounds: None }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, pos: Pos, cell: T) -> Option<T> {
+        self.track(pos);
+        self.cells.insert(pos, cell)
+    }
+
+    pub fn get_or_insert_with(&mut self, pos: Pos, cell: impl FnOnce() -> T) -> &mut T {
+        self.track(pos);
+        self.cells.entry(pos).or_insert_with(cell)
+    }
+
+    fn track(&mut self, pos: Pos) {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(pos),
+            None => self.bounds = Some(Bounds::of(pos)),
+        }
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().map(|(&pos, cell)| (pos, cell))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        pos.neighbours4().filter_map(move |pos| Some((pos, self.get(pos)?)))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = (Pos, &T)> {
+        pos.neighbours8().filter_map(move |pos| Some((pos, self.get(pos)?)))
+    }
+
+    // Copies the area within `bounds`, the dense grid's origin being `bounds.min`
+    pub fn to_dense<U>(&self, bounds: Bounds, mut cell: impl FnMut(Option<&T>) -> U) -> Grid<U> {
+        let cells = bounds.positions()
+            .map(|pos| cell(self.get(pos)))
+            .collect();
+
+        Grid::from_cells(bounds.width(), cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_tracking() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Pos::new(2, 1), 'a');
+        grid.insert(Pos::new(-1, 3), 'b');
+        *grid.get_or_insert_with(Pos::new(0, 0), || 'z') = 'c';
+
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(bounds, Bounds { min: Pos::new(-1, 0), max: Pos::new(2, 3) });
+        assert_eq!((bounds.width(), bounds.height()), (4, 4));
+
+        let dense = grid.to_dense(bounds, |cell| cell.copied().unwrap_or('.'));
+        assert_eq!(dense.render(|&c| c), ".c..\n...a\n....\nb...");
+    }
+}
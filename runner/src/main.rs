@@ -0,0 +1,254 @@
+mod scaffold;
+mod selection;
+mod watch;
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use rayon::prelude::*;
+use structopt::StructOpt;
+use aoc_common::{CheckSummary, Day, ExpectedAnswers, InputCache, Report, Server, Submitter, Verdict, input, output::{self, Format}, server};
+
+use selection::Selection;
+
+const REPO_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/..");
+
+// Some solutions keep large grids on the stack, like the 4MB one of 2015 day 6
+const SOLVER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "aoc", about = "Runs the Advent of Code solutions of every year")]
+struct Options {
+    /// Years to run, e.g. `2018` or `2015,2018-2019` (all by default)
+    #[structopt(short, long)]
+    year: Option<Selection>,
+
+    /// Days to run, e.g. `7` or `1-5,12` (all by default)
+    #[structopt(short, long)]
+    day: Option<Selection>,
+
+    /// Parts to run, `1`, `2` or `1-2` (all by default)
+    #[structopt(short, long)]
+    part: Option<Selection>,
+
+    /// Input file to use instead of the cached or repository one (requires a single day)
+    #[structopt(short, long, parse(from_os_str))]
+    input: Option<PathBuf>,
+
+    /// Output format: `text`, `json` or `csv`
+    #[structopt(short, long, default_value = "text")]
+    format: Format,
+
+    /// Directory of the downloaded inputs and of the submission history (a per-user cache directory by default)
+    #[structopt(long, global = true, parse(from_os_str))]
+    cache_dir: Option<PathBuf>,
+
+    /// Session cookie used to download the inputs missing from the repository and the cache, and to submit answers
+    #[structopt(long, global = true, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+
+    /// Puzzle server to download the inputs from
+    #[structopt(long, global = true, default_value = server::DEFAULT_URL)]
+    server: String,
+
+    /// Submits the answer of a single day and part to the puzzle server, unless previous guesses or `--check` rule it out
+    #[structopt(long, requires = "session")]
+    submit: bool,
+
+    /// CSV file of expected answers to verify the results against, e.g. a previous `--format csv` run
+    #[structopt(short, long, parse(from_os_str))]
+    check: Option<PathBuf>,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Creates a day from a template, and registers it in its crate and benchmarks
+    New {
+        /// Year of the puzzle, e.g. `2019`
+        year: u32,
+
+        /// Day of the puzzle, from 1 to 25
+        day: u32,
+    },
+
+    /// Rebuilds and runs the selected days again whenever their module or inputs change
+    Watch,
+}
+
+fn is_selected(selection: &Option<Selection>, value: u32) -> bool {
+    selection.as_ref()
+        .is_none_or(|selection| selection.contains(value))
+}
+
+fn repository_input(day: &Day) -> PathBuf {
+    [REPO_ROOT, &day.year.to_string(), "inputs", &input::file_name(day.day)].iter().collect()
+}
+
+fn read_input(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// An explicit input comes first, then the user's cached one, then the committed one,
+// and the input is only downloaded when none of them exists
+fn load_input(day: &Day, explicit: Option<&Path>, cache: &InputCache) -> Result<String, String> {
+    if let Some(path) = explicit {
+        return read_input(path);
+    }
+
+    let cached = cache.path(day.year, day.day);
+    let committed = repository_input(day);
+    if !cached.exists() && committed.exists() {
+        return read_input(&committed);
+    }
+
+    cache.get(day.year, day.day)
+        .map_err(|e| format!("{}: {}", cached.display(), e))
+}
+
+fn run(day: &Day, input: Result<String, String>, part_selected: impl Fn(u32) -> bool) -> Report {
+    let solved = input.and_then(|input| day.try_solve(&input, part_selected));
+
+    Report { year: day.year, day: day.day, solved }
+}
+
+// Answers that failed the check or that were already accepted are not sent
+fn submit(reports: &[Report], submitter: &mut Submitter) {
+    for report in reports {
+        let answers = report.solved.iter().flat_map(|solved| &solved.answers);
+
+        for answer in answers {
+            let accepted = submitter.history().is_accepted(report.year, report.day, answer.part, &answer.value);
+
+            let status = if let Some(Verdict::Fail { expected }) = &answer.verdict {
+                format!("{}: not submitted, {} was expected", answer.value, expected)
+            } else if accepted {
+                format!("{}: already accepted", answer.value)
+            } else {
+                match submitter.submit(report.year, report.day, answer.part, &answer.value) {
+                    Ok(outcome) => format!("submitted {}: {}", answer.value, outcome),
+                    Err(e) => format!("{}: {}", answer.value, e),
+                }
+            };
+
+            eprintln!("{} day {:02} part {}: {}", report.year, report.day, answer.part, status);
+        }
+    }
+}
+
+fn main() {
+    let options = Options::from_args();
+
+    let cache_dir = options.cache_dir.clone()
+        .or_else(InputCache::user_dir)
+        .unwrap_or_else(|| {
+            eprintln!("No cache directory for this user, please provide one with --cache-dir");
+            std::process::exit(1);
+        });
+    let server = options.session.as_ref()
+        .map(|session| Server::new(&options.server, session));
+
+    let mut submitter = server.clone()
+        .filter(|_| options.submit)
+        .map(|server| Submitter::new(server, cache_dir.join("submissions.csv")).unwrap_or_else(|e| {
+            eprintln!("Failed to load the submission history: {}", e);
+            std::process::exit(1);
+        }));
+
+    let mut cache = InputCache::new(cache_dir);
+    if let Some(server) = server {
+        cache = cache.with_server(server);
+    }
+
+    if let Some(Command::New { year, day }) = options.command {
+        match scaffold::new_day(Path::new(REPO_ROOT), year, day, &cache) {
+            Ok(created) => created.iter()
+                .filter_map(|path| path.strip_prefix(REPO_ROOT).ok())
+                .for_each(|path| println!("Created {}", path.display())),
+            Err(e) => {
+                eprintln!("Failed to create {} day {}: {}", year, day, e);
+                std::process::exit(1);
+            },
+        }
+        return;
+    }
+
+    let days: Vec<_> = advent_of_code_2015::days().into_iter()
+        .chain(aoc_2018::days())
+        .chain(aoc_2019::days())
+        .filter(|day| is_selected(&options.year, day.year))
+        .filter(|day| is_selected(&options.day, day.day))
+        .collect();
+
+    if days.is_empty() {
+        eprintln!("No solution matches the selection");
+        std::process::exit(1);
+    }
+
+    if options.input.is_some() && days.len() != 1 {
+        eprintln!("A custom input requires selecting a single day");
+        std::process::exit(1);
+    }
+
+    // Answers are sent one at a time, the server would reject them otherwise
+    let parts = (1..=2).filter(|&part| is_selected(&options.part, part)).count();
+    if options.submit && (days.len() != 1 || parts != 1) {
+        eprintln!("Submitting requires selecting a single day and a single part");
+        std::process::exit(1);
+    }
+
+    if let Some(Command::Watch) = options.command {
+        let days: Vec<_> = days.iter().map(|day| (day.year, day.day)).collect();
+        watch::watch(Path::new(REPO_ROOT), &watch::forwarded_args(std::env::args_os()), &days);
+    }
+
+    let expected = options.check.as_ref().map(|path| {
+        ExpectedAnswers::load(path).unwrap_or_else(|e| {
+            eprintln!("Failed to load the expected answers: {}", e);
+            std::process::exit(1);
+        })
+    });
+
+    rayon::ThreadPoolBuilder::new()
+        .stack_size(SOLVER_STACK_SIZE)
+        .build_global()
+        .expect("Failed to start the solver threads");
+
+    let mut reports: Vec<_> = days.par_iter()
+        .map(|day| {
+            let input = load_input(day, options.input.as_deref(), &cache);
+
+            run(day, input, |part| is_selected(&options.part, part))
+        })
+        .collect();
+
+    if let Some(expected) = &expected {
+        expected.check(&mut reports);
+    }
+
+    if let Err(e) = output::write(options.format, &reports, io::stdout().lock()) {
+        eprintln!("Failed to write the results: {}", e);
+        std::process::exit(1);
+    }
+
+    if let Some(submitter) = &mut submitter {
+        submit(&reports, submitter);
+    }
+
+    // The summary goes to stderr to keep the JSON and CSV outputs parseable
+    if expected.is_some() {
+        let summary = CheckSummary::new(&reports);
+        eprintln!("{}", summary);
+
+        if summary.failed > 0 {
+            std::process::exit(1);
+        }
+    }
+
+    if reports.iter().any(|report| report.solved.is_err()) {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,439 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::{InputCache, input};
+
+// Where a year's crate keeps its days, and how its benchmarks are written
+struct Layout {
+    year: u32,
+    dir: &'static str,
+    bench: &'static str,
+    // `bench_day::<S>` one-liners (2015, 2019) or a function per day (2018)
+    generic_benches: bool,
+    template: &'static str,
+}
+
+const LAYOUTS: &[Layout] = &[
+    Layout { year: 2015, dir: "2015/rust", bench: "benches/aoc-2015-bench.rs", generic_benches: true, template: TEMPLATE_2015 },
+    Layout { year: 2018, dir: "2018/rust", bench: "benches/aoc.rs", generic_benches: false, template: TEMPLATE },
+    Layout { year: 2019, dir: "2019/rust", bench: "benches/aoc-2019-bench.rs", generic_benches: true, template: TEMPLATE },
+];
+
+// `{dd}` is the zero padded day, `{day}` and `{year}` are as is.
+// `{has_part2}` declares that the last day has a single part
+const TEMPLATE: &str = r#"use aoc_common::{Solution, ParseResult};
+
+const RAW_INPUT_STR: &str = include_str!("../../inputs/day{dd}.txt");
+
+pub struct Day{dd};
+
+impl Solution for Day{dd} {
+    const YEAR: u32 = {year};
+    const DAY: u32 = {day};
+    const EMBEDDED_INPUT: &'static str = RAW_INPUT_STR;{has_part2}
+
+    type Parsed<'a> = Vec<&'a str>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        part2(input)
+    }
+}
+
+pub fn part1(lines: &[&str]) -> usize {
+    lines.len()
+}
+
+pub fn part2(lines: &[&str]) -> usize {
+    lines.len()
+}
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<&str>> {
+    Ok(input.lines().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    #[ignore = "the answer is still to be filled in"]
+    fn p1() {
+        let input = parse_input(RAW_INPUT_STR).unwrap();
+
+        assert_eq!(part1(&input), 0);
+    }
+
+    #[test]
+    #[ignore = "the answer is still to be filled in"]
+    fn p2() {
+        let input = parse_input(RAW_INPUT_STR).unwrap();
+
+        assert_eq!(part2(&input), 0);
+    }
+}
+"#;
+
+const TEMPLATE_2015: &str = r#"use aoc_common::{Solution, ParseResult};
+
+pub fn p1(lines: &[&str]) -> usize {
+    lines.len()
+}
+
+pub fn p2(lines: &[&str]) -> usize {
+    lines.len()
+}
+
+pub struct Day{dd};
+
+impl Solution for Day{dd} {
+    const YEAR: u32 = {year};
+    const DAY: u32 = {day};
+    const EMBEDDED_INPUT: &'static str = include_str!("../../inputs/day{dd}.txt");{has_part2}
+
+    type Parsed<'a> = Vec<&'a str>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> ParseResult<Self::Parsed<'_>> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> Self::Part1 {
+        p1(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> Self::Part2 {
+        p2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    #[ignore = "the answer is still to be filled in"]
+    fn p1() {
+        let input = Day{dd}::parse(Day{dd}::EMBEDDED_INPUT).unwrap();
+
+        assert_eq!(super::p1(&input), 0);
+    }
+
+    #[test]
+    #[ignore = "the answer is still to be filled in"]
+    fn p2() {
+        let input = Day{dd}::parse(Day{dd}::EMBEDDED_INPUT).unwrap();
+
+        assert_eq!(super::p2(&input), 0);
+    }
+}
+"#;
+
+fn render(template: &str, year: u32, day: u32) -> String {
+    template
+        .replace("{dd}", &format!("{:02}", day))
+        .replace("{day}", &day.to_string())
+        .replace("{year}", &year.to_string())
+        .replace("{has_part2}", if day == 25 { "\n    const HAS_PART2: bool = false;" } else { "" })
+}
+
+// The day a line is about, if it starts with `prefix` followed by a day number,
+// commented out or not. Longer names like `day16_scaling` are about no day
+fn day_of(line: &str, prefix: &str) -> Option<u32> {
+    let line = line.trim_start();
+    let line = line.strip_prefix("// ").unwrap_or(line);
+    let rest = line.strip_prefix(prefix)?;
+    let digits = rest.get(..2)?;
+
+    match rest[2..].chars().next() {
+        Some(c) if c.is_alphanumeric() || c == '_' => None,
+        _ => digits.parse().ok(),
+    }
+}
+
+// Adds `entry` among the other lines starting with `prefix`, keeping them sorted by day.
+// An entry that was commented out is enabled instead
+fn insert_sorted(text: &str, entry: &str, prefix: &str, day: u32) -> Result<String, String> {
+    let mut lines: Vec<_> = text.lines().map(str::to_owned).collect();
+    let commented = format!("// {}", entry.trim_start());
+
+    if let Some(line) = lines.iter_mut().find(|line| line.trim_start() == commented) {
+        *line = entry.to_owned();
+    } else if lines.iter().any(|line| line.trim_start() == entry.trim_start()) {
+        return Err(format!("`{}` is already there", entry.trim()));
+    } else {
+        let days: Vec<_> = lines.iter()
+            .enumerate()
+            .filter_map(|(idx, line)| Some((idx, day_of(line, prefix)?)))
+            .collect();
+
+        let idx = days.iter()
+            .find(|&&(_, other)| other > day)
+            .map(|&(idx, _)| idx)
+            .or_else(|| days.last().map(|&(idx, _)| idx + 1))
+            .ok_or_else(|| format!("no line starts with `{}`", prefix))?;
+
+        lines.insert(idx, entry.to_owned());
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn register_module(lib: &str, day: u32) -> Result<String, String> {
+    let lib = insert_sorted(lib, &format!("pub mod day{:02};", day), "pub mod day", day)?;
+
+    insert_sorted(&lib, &format!("        Day::new::<day{0:02}::Day{0:02}>(),", day), "Day::new::<day", day)
+}
+
+// Adds the day to the targets of the `benches` group, keeping them sorted by day
+fn add_bench_target(bench: &str, day: u32) -> Result<String, String> {
+    let (start, terminator) = bench.find("criterion_group!(benches,")
+        .map(|start| (start, ')'))
+        .or_else(|| {
+            let group = bench.find("name = benches;")?;
+            Some((group + bench[group..].find("targets =")?, '}'))
+        })
+        .ok_or("no `benches` group")?;
+    let end = start + bench[start..].find(terminator).ok_or("unterminated `benches` group")?;
+    let targets = &bench[start..end];
+
+    let next_target = targets.match_indices("day")
+        .map(|(idx, _)| idx)
+        .find(|&idx| day_of(&targets[idx..], "day").is_some_and(|other| other > day));
+
+    Ok(match next_target {
+        Some(idx) => format!("{}day{:02}, {}", &bench[..start + idx], day, &bench[start + idx..]),
+        None => {
+            let last_target = start + targets.trim_end().len();
+            format!("{}, day{:02}{}", &bench[..last_target], day, &bench[last_target..])
+        },
+    })
+}
+
+fn register_bench(bench: &str, year: u32, day: u32, generic: bool) -> Result<String, String> {
+    let bench = if generic {
+        let entry = format!("fn day{0:02}(c: &mut Criterion) {{ bench_day::<day{0:02}::Day{0:02}>(c) }}", day);
+        insert_sorted(bench, &entry, "fn day", day)?
+    } else {
+        let function = format!(
+"fn day{dd}(c: &mut Criterion) {{
+    use aoc_{year}::day{dd};
+
+    const GLOBI_INPUT: &str = include_str!(\"../../inputs/day{dd}.txt\");
+
+    let input = day{dd}::parse_input(GLOBI_INPUT).expect(\"Invalid input\");
+    c.bench_function(\"day{dd} p1\", |b| b.iter(|| day{dd}::part1(&input)));
+    c.bench_function(\"day{dd} p2\", |b| b.iter(|| day{dd}::part2(&input)));
+}}
+
+", dd = format!("{:02}", day), year = year);
+
+        let idx = bench.match_indices("\nfn day")
+            .map(|(idx, _)| idx + 1)
+            .find(|&idx| day_of(&bench[idx..], "fn day").is_some_and(|other| other > day))
+            .or_else(|| bench.find("criterion_group!"))
+            .ok_or("no place for the benchmark function")?;
+
+        format!("{}{}{}", &bench[..idx], function, &bench[idx..])
+    };
+
+    add_bench_target(&bench, day)
+}
+
+// The module of a day, whether it exists yet or not
+pub fn module_path(root: &Path, year: u32, day: u32) -> Option<PathBuf> {
+    let layout = LAYOUTS.iter().find(|layout| layout.year == year)?;
+
+    Some(root.join(layout.dir).join("src").join(format!("day{:02}.rs", day)))
+}
+
+// The edited contents of a file, to be written once every edit is known to succeed
+fn edited(path: PathBuf, edit: impl FnOnce(&str) -> Result<String, String>) -> Result<(PathBuf, String), String> {
+    let text = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let updated = edit(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    Ok((path, updated))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// Creates the day's module and registers it in the crate and its benchmarks.
+// The input is taken from the cache (and downloaded if possible), or left empty.
+// Returns the created files
+pub fn new_day(root: &Path, year: u32, day: u32, cache: &InputCache) -> Result<Vec<PathBuf>, String> {
+    let layout = LAYOUTS.iter()
+        .find(|layout| layout.year == year)
+        .ok_or_else(|| format!("{} has no Rust crate", year))?;
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {}", day));
+    }
+
+    let crate_dir = root.join(layout.dir);
+    let module = module_path(root, year, day).expect("The year has a layout");
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+
+    // Nothing is written unless the day can be registered everywhere
+    let registrations = [
+        edited(crate_dir.join("src").join("lib.rs"), |lib| register_module(lib, day))?,
+        edited(crate_dir.join(layout.bench), |bench| register_bench(bench, year, day, layout.generic_benches))?,
+    ];
+
+    let mut created = vec![module.clone()];
+
+    // Written first: the module embeds it
+    let input_path = root.join(year.to_string()).join("inputs").join(input::file_name(day));
+    if !input_path.exists() {
+        let input = cache.get(year, day).unwrap_or_else(|e| {
+            eprintln!("No input for {} day {} ({}), leaving it empty", year, day, e);
+            String::new()
+        });
+        write(&input_path, &input)?;
+        created.push(input_path);
+    }
+
+    write(&module, &render(layout.template, year, day))?;
+    for (path, contents) in &registrations {
+        write(path, contents)?;
+    }
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB: &str = "\
+pub mod day01;
+// pub mod day02;
+pub mod day04;
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::new::<day01::Day01>(),
+        Day::new::<day04::Day04>(),
+    ]
+}
+";
+
+    #[test]
+    fn modules() {
+        assert_eq!(register_module(LIB, 2).unwrap(), "\
+pub mod day01;
+pub mod day02;
+pub mod day04;
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::new::<day01::Day01>(),
+        Day::new::<day02::Day02>(),
+        Day::new::<day04::Day04>(),
+    ]
+}
+");
+
+        let registered = register_module(LIB, 7).unwrap();
+        assert!(registered.contains("pub mod day04;\npub mod day07;\n"));
+        assert!(registered.contains("        Day::new::<day07::Day07>(),\n    ]"));
+
+        assert!(register_module(LIB, 1).is_err());
+    }
+
+    #[test]
+    fn generic_benches() {
+        let bench = "\
+fn day01(c: &mut Criterion) { bench_day::<day01::Day01>(c) }
+fn day03(c: &mut Criterion) { bench_day::<day03::Day03>(c) }
+
+fn day01_scaling(c: &mut Criterion) { bench_scaling::<day01::Day01>(c) }
+
+criterion_group!(benches,
+    day01, day03
+);
+";
+
+        assert_eq!(register_bench(bench, 2019, 2, true).unwrap(), "\
+fn day01(c: &mut Criterion) { bench_day::<day01::Day01>(c) }
+fn day02(c: &mut Criterion) { bench_day::<day02::Day02>(c) }
+fn day03(c: &mut Criterion) { bench_day::<day03::Day03>(c) }
+
+fn day01_scaling(c: &mut Criterion) { bench_scaling::<day01::Day01>(c) }
+
+criterion_group!(benches,
+    day01, day02, day03
+);
+");
+
+        let registered = register_bench(bench, 2019, 24, true).unwrap();
+        assert!(registered.contains("fn day03(c: &mut Criterion) { bench_day::<day03::Day03>(c) }\nfn day24("));
+        assert!(registered.contains("    day01, day03, day24\n);"));
+    }
+
+    #[test]
+    fn function_benches() {
+        let bench = "\
+fn day01(c: &mut Criterion) {
+}
+
+fn day01_2(c: &mut Criterion) {
+}
+
+criterion_group!{
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = day01, day01_2
+}
+";
+
+        let registered = register_bench(bench, 2018, 2, false).unwrap();
+        assert!(registered.contains("fn day01_2(c: &mut Criterion) {\n}\n\nfn day02(c: &mut Criterion) {\n    use aoc_2018::day02;\n"));
+        assert!(registered.contains("c.bench_function(\"day02 p2\", |b| b.iter(|| day02::part2(&input)));\n}\n\ncriterion_group!"));
+        assert!(registered.contains("targets = day01, day01_2, day02\n}"));
+    }
+
+    #[test]
+    fn templates() {
+        let module = render(TEMPLATE, 2019, 22);
+
+        assert!(module.contains("include_str!(\"../../inputs/day22.txt\")"));
+        assert!(module.contains("impl Solution for Day22 {\n    const YEAR: u32 = 2019;\n    const DAY: u32 = 22;\n"));
+        assert!(["{dd}", "{day}", "{year}", "{has_part2}"].iter().all(|placeholder| !module.contains(placeholder)));
+        assert_eq!(module.matches("#[ignore").count(), 2);
+        assert!(!module.contains("HAS_PART2"));
+
+        for template in [TEMPLATE, TEMPLATE_2015] {
+            assert!(render(template, 2019, 25).contains("DAY: u32 = 25;\n    const EMBEDDED_INPUT"));
+            assert!(render(template, 2019, 25).contains("\n    const HAS_PART2: bool = false;\n\n    type Parsed"));
+        }
+    }
+
+    #[test]
+    fn all_or_nothing() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let crate_dir = root.join("2019/rust");
+        fs::create_dir_all(crate_dir.join("src")).unwrap();
+        fs::create_dir_all(crate_dir.join("benches")).unwrap();
+        fs::create_dir_all(root.join("2019/inputs")).unwrap();
+        fs::write(crate_dir.join("src/lib.rs"), LIB).unwrap();
+        fs::write(crate_dir.join("benches/aoc-2019-bench.rs"), "fn day01(c: &mut Criterion) {}\n").unwrap();
+
+        assert!(new_day(&root, 2019, 2, &InputCache::new(root.join("cache"))).is_err());
+        assert_eq!(fs::read_to_string(crate_dir.join("src/lib.rs")).unwrap(), LIB);
+        assert!(!crate_dir.join("src/day02.rs").exists());
+        assert!(!root.join("2019/inputs/day02.txt").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,60 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub struct Selection(Vec<RangeInclusive<u32>>);
+
+impl Selection {
+    pub fn contains(&self, value: u32) -> bool {
+        self.0.iter()
+            .any(|range| range.contains(&value))
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_bound = |bound: &str| bound.trim()
+            .parse()
+            .map_err(|_| format!("invalid number {:?} in selection {:?}", bound, s));
+
+        s.split(',')
+            .map(|item| match item.find('-') {
+                Some(idx) => {
+                    let (low, high) = (parse_bound(&item[..idx])?, parse_bound(&item[idx + 1..])?);
+                    if low > high {
+                        return Err(format!("empty range {:?} in selection {:?}", item, s))
+                    }
+                    Ok(low..=high)
+                },
+                None => parse_bound(item).map(|value| value..=value),
+            })
+            .collect::<Result<_, _>>()
+            .map(Selection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let selection: Selection = "1,3-5, 12".parse().unwrap();
+
+        let selected: Vec<_> = (1..=25)
+            .filter(|&day| selection.contains(day))
+            .collect();
+
+        assert_eq!(selected, [1, 3, 4, 5, 12]);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!("".parse::<Selection>().is_err());
+        assert!("1-".parse::<Selection>().is_err());
+        assert!("5-3".parse::<Selection>().is_err());
+        assert!("day1".parse::<Selection>().is_err());
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use serde::Deserialize;
+
+use crate::scaffold;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// The files a day depends on: its module and any `inputs/dayNN*` file
+fn watched_files(root: &Path, year: u32, day: u32) -> Vec<PathBuf> {
+    let prefix = format!("day{:02}", day);
+    let inputs = fs::read_dir(root.join(year.to_string()).join("inputs"))
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|entry| entry.path());
+
+    scaffold::module_path(root, year, day).into_iter()
+        .chain(inputs)
+        .collect()
+}
+
+type Snapshot = HashMap<PathBuf, (u32, u32, Option<SystemTime>)>;
+
+fn snapshot(root: &Path, days: &[(u32, u32)]) -> Snapshot {
+    days.iter()
+        .flat_map(|&(year, day)| {
+            watched_files(root, year, day).into_iter()
+                .map(move |path| {
+                    let modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+                    (path, (year, day, modified))
+                })
+        })
+        .collect()
+}
+
+// The days with a file that was created, modified or removed between the snapshots
+fn affected_days(before: &Snapshot, after: &Snapshot) -> BTreeSet<(u32, u32)> {
+    let changed = |(path, &(year, day, modified)): (&PathBuf, &(u32, u32, Option<SystemTime>))| {
+        let previous = before.get(path).and_then(|&(_, _, modified)| modified);
+        (previous != modified).then_some((year, day))
+    };
+    let removed = before.iter()
+        .filter(|(path, _)| !after.contains_key(*path))
+        .map(|(_, &(year, day, _))| (year, day));
+
+    after.iter()
+        .filter_map(changed)
+        .chain(removed)
+        .collect()
+}
+
+// A row of the runner's CSV output
+#[derive(Debug, Deserialize)]
+struct Row {
+    part: Option<u32>,
+    answer: Option<String>,
+    elapsed_ms: Option<f64>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Run {
+    answers: Vec<(u32, String, f64)>,
+    error: Option<String>,
+}
+
+fn parse_run(csv_output: &[u8]) -> Result<Run, csv::Error> {
+    let rows = csv::Reader::from_reader(csv_output)
+        .deserialize()
+        .collect::<Result<Vec<Row>, _>>()?;
+
+    Ok(Run {
+        answers: rows.iter()
+            .filter_map(|row| Some((row.part?, row.answer.clone()?, row.elapsed_ms.unwrap_or(0.))))
+            .collect(),
+        error: rows.into_iter().find_map(|row| row.error),
+    })
+}
+
+// The answers along with how they changed since the previous run
+fn describe(run: &Run, previous: Option<&Run>) -> Vec<String> {
+    let previous_answer = |part| previous?.answers.iter()
+        .find(|&&(other, _, _)| other == part)
+        .map(|(_, answer, _)| answer.as_str());
+
+    let mut lines: Vec<_> = run.answers.iter()
+        .map(|(part, answer, elapsed_ms)| {
+            let change = match previous_answer(*part) {
+                None => String::new(),
+                Some(previous) if previous == answer => " (unchanged)".to_owned(),
+                Some(previous) => format!(" (was {})", previous),
+            };
+            format!("  Part {}: {}{} in {:.3}ms", part, answer, change, elapsed_ms)
+        })
+        .collect();
+
+    if let Some(error) = &run.error {
+        lines.push(format!("  {}", error));
+    }
+
+    lines
+}
+
+// The runner's options that take a value, and the ones each run sets itself
+const VALUE_OPTIONS: &[&str] = &[
+    "-y", "--year", "-d", "--day", "-p", "--part", "-i", "--input", "-f", "--format",
+    "-c", "--check", "--cache-dir", "--session", "--server",
+];
+const REPLACED_OPTIONS: &[&str] = &["-y", "--year", "-d", "--day", "-f", "--format"];
+// Reruns happen on every save, and must not send a guess each time
+const DROPPED_ARGS: &[&str] = &["watch", "--submit"];
+
+// The runner's arguments, without the program name, the `watch` subcommand,
+// `--submit` and the options that select the days or the output format
+pub fn forwarded_args(args: impl IntoIterator<Item = OsString>) -> Vec<OsString> {
+    let mut forwarded = Vec::new();
+    let mut args = args.into_iter().skip(1);
+
+    while let Some(arg) = args.next() {
+        let text = arg.to_string_lossy();
+        let replaced = |option: &&str| match option.strip_prefix("--") {
+            Some(_) => text.starts_with(&format!("{}=", option)),
+            None => text.starts_with(option) && text.len() > option.len(),
+        };
+
+        if DROPPED_ARGS.contains(&text.as_ref()) || REPLACED_OPTIONS.iter().any(replaced) {
+            continue;
+        }
+
+        let value = VALUE_OPTIONS.contains(&text.as_ref())
+            .then(|| args.next())
+            .flatten();
+        if REPLACED_OPTIONS.contains(&text.as_ref()) {
+            continue;
+        }
+
+        forwarded.push(arg);
+        forwarded.extend(value);
+    }
+
+    forwarded
+}
+
+// Rebuilds the runner if needed and runs a single day with it
+fn run_day(root: &Path, args: &[OsString], year: u32, day: u32) -> Result<Run, String> {
+    let mut command = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.current_dir(root)
+        .args(["run", "--release", "--quiet", "--bin", "aoc", "--"])
+        .args(args)
+        .args(["--year", &year.to_string(), "--day", &day.to_string(), "--format", "csv"]);
+
+    // Compilation errors go straight to the terminal
+    let output = command.stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("failed to run cargo: {}", e))?;
+
+    if output.stdout.is_empty() && !output.status.success() {
+        return Err("the build failed".to_owned());
+    }
+
+    parse_run(&output.stdout)
+        .map_err(|e| format!("unreadable output: {}", e))
+}
+
+// Runs `days` again whenever one of their files changes, forever, with the runner's `args`
+pub fn watch(root: &Path, args: &[OsString], days: &[(u32, u32)]) -> ! {
+    let mut files = snapshot(root, days);
+    let mut previous_runs: HashMap<(u32, u32), Run> = HashMap::new();
+
+    eprintln!("Watching {} files of {} days", files.len(), days.len());
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = snapshot(root, days);
+        let affected = affected_days(&files, &current);
+        files = current;
+
+        for (year, day) in affected {
+            println!("{} day {:02}:", year, day);
+
+            match run_day(root, args, year, day) {
+                Ok(run) => {
+                    describe(&run, previous_runs.get(&(year, day)))
+                        .iter()
+                        .for_each(|line| println!("{}", line));
+                    previous_runs.insert((year, day), run);
+                },
+                Err(e) => println!("  {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changes() {
+        let root = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("2019/rust/src")).unwrap();
+        fs::create_dir_all(root.join("2019/inputs")).unwrap();
+        fs::write(root.join("2019/rust/src/day01.rs"), "").unwrap();
+        fs::write(root.join("2019/inputs/day01.txt"), "12").unwrap();
+        fs::write(root.join("2019/inputs/day10.txt"), "#.#").unwrap();
+
+        let days = [(2019, 1), (2019, 2), (2019, 10)];
+        let before = snapshot(&root, &days);
+        assert_eq!(before.len(), 5);
+        assert!(affected_days(&before, &snapshot(&root, &days)).is_empty());
+
+        let modified = SystemTime::now() + Duration::from_secs(10);
+        fs::File::options().write(true).open(root.join("2019/inputs/day01.txt")).unwrap()
+            .set_modified(modified).unwrap();
+        fs::write(root.join("2019/inputs/day02_example.txt"), "1").unwrap();
+        fs::remove_file(root.join("2019/inputs/day10.txt")).unwrap();
+
+        let affected: Vec<_> = affected_days(&before, &snapshot(&root, &days)).into_iter().collect();
+        assert_eq!(affected, [(2019, 1), (2019, 2), (2019, 10)]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn arguments() {
+        let forwarded = |args: &[&str]| -> Vec<String> {
+            forwarded_args(args.iter().map(OsString::from))
+                .into_iter()
+                .map(|arg| arg.into_string().unwrap())
+                .collect()
+        };
+
+        assert_eq!(
+            forwarded(&["aoc", "-y", "2019", "--day=1-3", "-p", "2", "--cache-dir", "watch", "watch", "--check", "a.csv"]),
+            ["-p", "2", "--cache-dir", "watch", "--check", "a.csv"]
+        );
+        assert_eq!(
+            forwarded(&["aoc", "watch", "-y2018", "--format", "json", "--session", "abc", "--server=http://localhost"]),
+            ["--session", "abc", "--server=http://localhost"]
+        );
+        assert_eq!(forwarded(&["aoc", "-d", "7", "-p", "1", "--submit", "watch"]), ["-p", "1"]);
+    }
+
+    #[test]
+    fn diffs() {
+        let first = parse_run(b"\
+year,day,part,input_hash,answer,parse_ms,elapsed_ms,success,error,check
+2019,1,1,abcd,42,0.1,1.5,true,,
+2019,1,2,abcd,7,0.1,0.25,true,,
+").unwrap();
+        assert_eq!(describe(&first, None), ["  Part 1: 42 in 1.500ms", "  Part 2: 7 in 0.250ms"]);
+
+        let second = Run { answers: vec![(1, "42".to_owned(), 1.), (2, "8".to_owned(), 2.)], error: None };
+        assert_eq!(
+            describe(&second, Some(&first)),
+            ["  Part 1: 42 (unchanged) in 1.000ms", "  Part 2: 8 (was 7) in 2.000ms"]
+        );
+
+        let failed = parse_run(b"\
+year,day,part,input_hash,answer,parse_ms,elapsed_ms,success,error,check
+2019,1,,,,,,false,\"panicked: oops\",
+").unwrap();
+        assert_eq!(describe(&failed, Some(&first)), ["  panicked: oops"]);
+    }
+}